@@ -5,10 +5,8 @@ use image::{GenericImageView, ImageBuffer, Rgba, imageops::FilterType};
 use ndarray::Array;
 use ort::{
 	execution_providers::{CUDAExecutionProvider, ExecutionProvider},
-	memory::{AllocationDevice, AllocatorType, MemoryInfo, MemoryType},
 	session::Session,
-	tensor::Shape,
-	value::TensorRefMut
+	value::DeviceTensorBuilder
 };
 use show_image::{AsImageView, WindowOptions, event};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -49,14 +47,7 @@ fn main() -> anyhow::Result<()> {
 
 	let device = CudaDevice::new(0)?;
 	let device_data = device.htod_sync_copy(&input.into_raw_vec())?;
-	let tensor: TensorRefMut<'_, f32> = unsafe {
-		TensorRefMut::from_raw(
-			MemoryInfo::new(AllocationDevice::CUDA, 0, AllocatorType::Device, MemoryType::Default)?,
-			(*device_data.device_ptr() as usize as *mut ()).cast(),
-			Shape::from([1, 3, 512, 512])
-		)
-		.unwrap()
-	};
+	let tensor = unsafe { DeviceTensorBuilder::cuda(0)?.build::<f32>((*device_data.device_ptr() as usize as *mut ()).cast(), [1, 3, 512, 512])? };
 	let outputs = session.run(ort::inputs![tensor])?;
 
 	let output = outputs["output"].try_extract_array::<f32>()?;
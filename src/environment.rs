@@ -11,7 +11,7 @@
 //! # }
 //! ```
 
-use alloc::{boxed::Box, string::String};
+use alloc::{boxed::Box, string::String, vec::Vec};
 use core::{
 	any::Any,
 	ffi::c_void,
@@ -26,6 +26,7 @@ use crate::{
 	AsPointer,
 	error::Result,
 	execution_providers::ExecutionProviderDispatch,
+	memory::CustomAllocator,
 	ortsys,
 	util::{OnceLock, STACK_EXECUTION_PROVIDERS, with_cstr}
 };
@@ -46,7 +47,10 @@ pub struct Environment {
 	pub(crate) execution_providers: SmallVec<ExecutionProviderDispatch, { STACK_EXECUTION_PROVIDERS }>,
 	ptr: NonNull<ort_sys::OrtEnv>,
 	pub(crate) has_global_threadpool: bool,
-	_thread_manager: Option<Box<dyn Any>>
+	_thread_manager: Option<Box<dyn Any>>,
+	/// Allocators registered with the environment; these must outlive `ptr`, which they do since fields are dropped
+	/// after `ReleaseEnv` is called in `Drop`.
+	_allocators: Vec<CustomAllocator>
 }
 
 unsafe impl Send for Environment {}
@@ -115,6 +119,11 @@ impl GlobalThreadPoolOptions {
 		Ok(self)
 	}
 
+	/// Enables flush-to-zero and denormal-as-zero for the global thread pool's threads, which can greatly speed up
+	/// models which produce many denormal values, at the cost of slightly changing numerical results near zero. See
+	/// [`SessionBuilder::with_denormal_as_zero`] for sessions which use their own thread pools.
+	///
+	/// [`SessionBuilder::with_denormal_as_zero`]: crate::session::builder::SessionBuilder::with_denormal_as_zero
 	pub fn with_flush_to_zero(mut self) -> Result<Self> {
 		ortsys![unsafe SetGlobalDenormalAsZero(self.ptr_mut())?];
 		Ok(self)
@@ -213,7 +222,8 @@ pub struct EnvironmentBuilder {
 	name: String,
 	telemetry: bool,
 	execution_providers: SmallVec<ExecutionProviderDispatch, { STACK_EXECUTION_PROVIDERS }>,
-	global_thread_pool_options: Option<GlobalThreadPoolOptions>
+	global_thread_pool_options: Option<GlobalThreadPoolOptions>,
+	allocators: Vec<CustomAllocator>
 }
 
 impl EnvironmentBuilder {
@@ -222,7 +232,8 @@ impl EnvironmentBuilder {
 			name: String::from("default"),
 			telemetry: true,
 			execution_providers: SmallVec::new(),
-			global_thread_pool_options: None
+			global_thread_pool_options: None,
+			allocators: Vec::new()
 		}
 	}
 
@@ -275,6 +286,20 @@ impl EnvironmentBuilder {
 		self
 	}
 
+	/// Registers a [`CustomAllocator`] with the environment. Sessions created with
+	/// [`SessionBuilder::with_env_allocators`] will use it for allocations matching its
+	/// [`MemoryInfo`](crate::memory::MemoryInfo), instead of creating their own allocator.
+	///
+	/// The allocator is kept alive for as long as the environment exists. Only one allocator may be registered per
+	/// [`MemoryInfo`](crate::memory::MemoryInfo); registering a duplicate will cause [`commit`](Self::commit) to fail.
+	///
+	/// [`SessionBuilder::with_env_allocators`]: crate::session::builder::SessionBuilder::with_env_allocators
+	#[must_use = "commit() must be called in order for the environment to take effect"]
+	pub fn with_allocator(mut self, allocator: CustomAllocator) -> Self {
+		self.allocators.push(allocator);
+		self
+	}
+
 	pub(crate) fn commit_internal(self) -> Result<Environment> {
 		let (env_ptr, thread_manager, has_global_threadpool) = if let Some(mut thread_pool_options) = self.global_thread_pool_options {
 			let env_ptr = with_cstr(self.name.as_bytes(), &|name| {
@@ -342,12 +367,17 @@ impl EnvironmentBuilder {
 			ortsys![unsafe DisableTelemetryEvents(env_ptr)?];
 		}
 
+		for allocator in &self.allocators {
+			ortsys![unsafe RegisterAllocator(env_ptr, allocator.ptr().cast_mut())?];
+		}
+
 		Ok(Environment {
 			execution_providers: self.execution_providers,
 			// we already asserted the env pointer is non-null in the `CreateEnvWithCustomLogger` call
 			ptr: unsafe { NonNull::new_unchecked(env_ptr) },
 			has_global_threadpool,
-			_thread_manager: thread_manager
+			_thread_manager: thread_manager,
+			_allocators: self.allocators
 		})
 	}
 
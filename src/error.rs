@@ -2,7 +2,7 @@ use alloc::{
 	format,
 	string::{String, ToString}
 };
-use core::{convert::Infallible, ffi::c_char, fmt, ptr};
+use core::{convert::Infallible, ffi::c_char, fmt, mem, ptr};
 
 use crate::{char_p_to_string, ortsys, util::with_cstr};
 
@@ -17,9 +17,11 @@ impl<T> IntoStatus for Result<T, Error> {
 	fn into_status(self) -> ort_sys::OrtStatusPtr {
 		let (code, message) = match &self {
 			Ok(_) => return ort_sys::OrtStatusPtr(ptr::null_mut()),
-			Err(e) => (ort_sys::OrtErrorCode::ORT_FAIL, e.to_string())
+			// Interior nul bytes can't be represented in a C string; replace them rather than panicking, since this is
+			// usually called from a callback where a panic could not unwind.
+			Err(e) => (ort_sys::OrtErrorCode::ORT_FAIL, e.to_string().replace('\0', "\u{FFFD}"))
 		};
-		with_cstr(message.as_bytes(), &|message| Ok(ortsys![unsafe CreateStatus(code, message.as_ptr())])).expect("invalid error message")
+		with_cstr(message.as_bytes(), &|message| Ok(ortsys![unsafe CreateStatus(code, message.as_ptr())])).expect("message has no nul bytes")
 	}
 }
 
@@ -27,6 +29,7 @@ impl<T> IntoStatus for Result<T, Error> {
 #[derive(Debug)]
 pub struct Error {
 	code: ErrorCode,
+	raw_code: i32,
 	msg: String
 }
 
@@ -37,10 +40,7 @@ impl Error {
 	/// related operation fails.
 	#[cfg(feature = "std")]
 	pub fn wrap<T: std::error::Error + Send + Sync + 'static>(err: T) -> Self {
-		Error {
-			code: ErrorCode::GenericFailure,
-			msg: err.to_string()
-		}
+		Error::new_with_code(ErrorCode::GenericFailure, err.to_string())
 	}
 
 	/// Wrap a custom, user-provided error in an [`ort::Error`](Error)..
@@ -49,34 +49,143 @@ impl Error {
 	/// related operation fails.
 	#[cfg(not(feature = "std"))]
 	pub fn wrap<T: core::fmt::Display + Send + Sync + 'static>(err: T) -> Self {
-		Error {
-			code: ErrorCode::GenericFailure,
-			msg: err.to_string()
-		}
+		Error::new_with_code(ErrorCode::GenericFailure, err.to_string())
 	}
 
 	/// Creates a custom [`Error`] with the given message.
 	pub fn new(msg: impl Into<String>) -> Self {
-		Error {
-			code: ErrorCode::GenericFailure,
-			msg: msg.into()
-		}
+		Error::new_with_code(ErrorCode::GenericFailure, msg)
 	}
 
 	/// Creates a custom [`Error`] with the given [`ErrorCode`] and message.
 	pub fn new_with_code(code: ErrorCode, msg: impl Into<String>) -> Self {
-		Error { code, msg: msg.into() }
+		Error {
+			code,
+			raw_code: ort_sys::OrtErrorCode::from(code) as i32,
+			msg: msg.into()
+		}
 	}
 
+	/// Returns the category of this error, which can be used to handle specific failures without matching on the error
+	/// message.
+	///
+	/// ```
+	/// # use ort::{Error, ErrorCode};
+	/// let error = Error::new_with_code(ErrorCode::InvalidArgument, "bad input");
+	/// assert_eq!(error.code(), ErrorCode::InvalidArgument);
+	/// ```
 	pub fn code(&self) -> ErrorCode {
 		self.code
 	}
 
+	/// Returns the numeric `OrtErrorCode` of this error exactly as reported by ONNX Runtime. Unlike [`Error::code`],
+	/// this preserves codes introduced by newer versions of ONNX Runtime which [`ErrorCode`] does not model yet (and
+	/// which [`Error::code`] reports as [`ErrorCode::GenericFailure`]).
+	///
+	/// For errors which did not originate from ONNX Runtime, this is the code corresponding to [`Error::code`].
+	///
+	/// ```
+	/// # use ort::{Error, ErrorCode};
+	/// let error = Error::new_with_code(ErrorCode::InvalidArgument, "bad input");
+	/// assert_eq!(error.raw_code(), 2);
+	/// ```
+	pub fn raw_code(&self) -> i32 {
+		self.raw_code
+	}
+
+	/// Returns the message describing this error.
 	pub fn message(&self) -> &str {
 		self.msg.as_str()
 	}
+
+	/// If this error occurred while executing a node (e.g. in [`Session::run`]), returns the node's operator type &
+	/// name, e.g. `("Conv", "conv_1")`.
+	///
+	/// ONNX Runtime does not report the failing node in a structured way, so this is parsed from the error message
+	/// and may not be available for all errors.
+	///
+	/// [`Session::run`]: crate::session::Session::run
+	pub fn failed_node(&self) -> Option<(&str, &str)> {
+		// "Non-zero status code returned while running Conv node. Name:'conv_1' Status Message: ..."
+		let (_, rest) = self.msg.split_once("while running ")?;
+		let (op_type, rest) = rest.split_once(" node. Name:'")?;
+		let (name, _) = rest.split_once('\'')?;
+		Some((op_type, name))
+	}
+
+	/// Returns the name of the execution provider which caused this error, e.g. `CUDAExecutionProvider`, if it can be
+	/// determined.
+	///
+	/// Like [`Error::failed_node`], this is inferred from the error message, so it is only available for errors which
+	/// mention the execution provider or one of its libraries (e.g. a `CUDA failure` or `CUBLAS failure`).
+	pub fn execution_provider(&self) -> Option<&'static str> {
+		EXECUTION_PROVIDER_MARKERS
+			.iter()
+			.find(|(marker, _)| self.msg.contains(marker))
+			.map(|(_, provider)| *provider)
+	}
+
+	/// Returns `true` if this error was caused by an allocation failure, e.g. a CUDA device running out of memory.
+	///
+	/// This can be used to retry a failed [`Session::run`] on a session with a different execution provider:
+	///
+	/// ```no_run
+	/// # use ort::{session::Session, value::TensorRef};
+	/// # fn main() -> ort::Result<()> {
+	/// # let mut gpu_session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// # let mut cpu_session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let outputs = match gpu_session.run(ort::inputs![TensorRef::from_array_view(&input)?]) {
+	/// 	Err(e) if e.is_out_of_memory() => cpu_session.run(ort::inputs![TensorRef::from_array_view(&input)?])?,
+	/// 	res => res?
+	/// };
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// [`Session::run`]: crate::session::Session::run
+	pub fn is_out_of_memory(&self) -> bool {
+		OUT_OF_MEMORY_MARKERS.iter().any(|marker| self.msg.contains(marker))
+	}
 }
 
+/// Substrings of error messages which identify the execution provider that caused them. More specific markers come
+/// first, since a message may mention several providers' libraries.
+const EXECUTION_PROVIDER_MARKERS: &[(&str, &str)] = &[
+	("TensorrtExecutionProvider", "TensorrtExecutionProvider"),
+	("TensorRT EP", "TensorrtExecutionProvider"),
+	("providers/tensorrt/", "TensorrtExecutionProvider"),
+	("CUDAExecutionProvider", "CUDAExecutionProvider"),
+	("CUDA failure", "CUDAExecutionProvider"),
+	("CUDNN failure", "CUDAExecutionProvider"),
+	("CUBLAS failure", "CUDAExecutionProvider"),
+	("providers/cuda/", "CUDAExecutionProvider"),
+	("ROCmExecutionProvider", "ROCmExecutionProvider"),
+	("HIP failure", "ROCmExecutionProvider"),
+	("providers/rocm/", "ROCmExecutionProvider"),
+	("DmlExecutionProvider", "DmlExecutionProvider"),
+	("providers/dml/", "DmlExecutionProvider"),
+	("OpenVINOExecutionProvider", "OpenVINOExecutionProvider"),
+	("providers/openvino/", "OpenVINOExecutionProvider"),
+	("CoreMLExecutionProvider", "CoreMLExecutionProvider"),
+	("providers/coreml/", "CoreMLExecutionProvider"),
+	("QNNExecutionProvider", "QNNExecutionProvider"),
+	("providers/qnn/", "QNNExecutionProvider"),
+	("CPUExecutionProvider", "CPUExecutionProvider")
+];
+
+/// Substrings of error messages caused by allocation failures.
+const OUT_OF_MEMORY_MARKERS: &[&str] = &[
+	"out of memory",
+	"Failed to allocate memory",
+	"cudaErrorMemoryAllocation",
+	"CUBLAS_STATUS_ALLOC_FAILED",
+	"CUDNN_STATUS_ALLOC_FAILED",
+	"hipErrorOutOfMemory",
+	"E_OUTOFMEMORY",
+	"bad_alloc"
+];
+
 impl fmt::Display for Error {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		f.write_str(&self.msg)
@@ -89,10 +198,7 @@ impl std::error::Error for Error {}
 #[cfg(feature = "std")]
 impl From<Box<dyn std::error::Error + Send + Sync + 'static>> for Error {
 	fn from(err: Box<dyn std::error::Error + Send + Sync + 'static>) -> Self {
-		Error {
-			code: ErrorCode::GenericFailure,
-			msg: err.to_string()
-		}
+		Error::new_with_code(ErrorCode::GenericFailure, err.to_string())
 	}
 }
 
@@ -132,23 +238,63 @@ impl From<alloc::ffi::IntoStringError> for Error {
 	}
 }
 
+/// The category of an [`Error`], mirroring ONNX Runtime's `OrtErrorCode`.
+///
+/// Errors originating from `ort` itself use the code that best describes the failure, usually
+/// [`ErrorCode::GenericFailure`] or [`ErrorCode::InvalidArgument`]. This enum is non-exhaustive, as newer versions of
+/// ONNX Runtime may introduce new error codes.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum ErrorCode {
+	/// `ORT_OK`; not an error.
 	Ok,
+	/// `ORT_FAIL`; a general or unknown failure.
 	GenericFailure,
+	/// `ORT_INVALID_ARGUMENT`; an argument or input was invalid, e.g. a tensor had the wrong shape or type.
 	InvalidArgument,
+	/// `ORT_NO_SUCHFILE`; a file (i.e. a model) could not be found.
 	NoSuchFile,
+	/// `ORT_NO_MODEL`; no model was loaded.
 	NoModel,
+	/// `ORT_ENGINE_ERROR`; an internal error in ONNX Runtime.
 	EngineError,
+	/// `ORT_RUNTIME_EXCEPTION`; an exception was thrown during execution.
 	RuntimeException,
+	/// `ORT_INVALID_PROTOBUF`; the model file could not be parsed.
 	InvalidProtobuf,
+	/// `ORT_MODEL_LOADED`; a model was already loaded.
 	ModelLoaded,
+	/// `ORT_NOT_IMPLEMENTED`; the requested functionality is not implemented.
 	NotImplemented,
+	/// `ORT_INVALID_GRAPH`; the model graph is invalid.
 	InvalidGraph,
+	/// `ORT_EP_FAIL`; an execution provider failed.
 	ExecutionProviderFailure
 }
 
+impl ErrorCode {
+	/// Maps a numeric `OrtErrorCode`, falling back to [`ErrorCode::GenericFailure`] for unknown codes.
+	fn from_raw(raw_code: i32) -> Self {
+		[
+			Self::Ok,
+			Self::GenericFailure,
+			Self::InvalidArgument,
+			Self::NoSuchFile,
+			Self::NoModel,
+			Self::EngineError,
+			Self::RuntimeException,
+			Self::InvalidProtobuf,
+			Self::ModelLoaded,
+			Self::NotImplemented,
+			Self::InvalidGraph,
+			Self::ExecutionProviderFailure
+		]
+		.into_iter()
+		.find(|&code| ort_sys::OrtErrorCode::from(code) as i32 == raw_code)
+		.unwrap_or(Self::GenericFailure)
+	}
+}
+
 impl From<ort_sys::OrtErrorCode> for ErrorCode {
 	fn from(value: ort_sys::OrtErrorCode) -> Self {
 		match value {
@@ -201,20 +347,55 @@ pub unsafe fn status_to_result(status: ort_sys::OrtStatusPtr) -> Result<(), Erro
 	if status.is_null() {
 		Ok(())
 	} else {
-		let code = ErrorCode::from(ortsys![unsafe GetErrorCode(status)]);
+		// Newer versions of ONNX Runtime may return codes `ort_sys::OrtErrorCode` has no variant for, so read the code as
+		// a plain integer instead.
+		let get_error_code: unsafe extern "system" fn(*const ort_sys::OrtStatus) -> i32 = unsafe { mem::transmute(ortsys![GetErrorCode]) };
+		let raw_code = unsafe { get_error_code(status) };
+		let code = ErrorCode::from_raw(raw_code);
 		let raw: *const c_char = ortsys![unsafe GetErrorMessage(status)];
 		match char_p_to_string(raw) {
 			Ok(msg) => {
 				ortsys![unsafe ReleaseStatus(status)];
-				Err(Error { code, msg })
+				Err(Error { code, raw_code, msg })
 			}
 			Err(err) => {
 				ortsys![unsafe ReleaseStatus(status)];
 				Err(Error {
 					code,
+					raw_code,
 					msg: format!("(failed to convert UTF-8: {err})")
 				})
 			}
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{Error, ErrorCode};
+
+	#[test]
+	fn test_error_classification() {
+		let error = Error::new_with_code(
+			ErrorCode::RuntimeException,
+			"Non-zero status code returned while running MatMul node. Name:'/fc/MatMul' Status Message: \
+			 /onnxruntime_src/onnxruntime/core/providers/cuda/cuda_call.cc:123 CUBLAS failure 3: CUBLAS_STATUS_ALLOC_FAILED"
+		);
+		assert_eq!(error.failed_node(), Some(("MatMul", "/fc/MatMul")));
+		assert_eq!(error.execution_provider(), Some("CUDAExecutionProvider"));
+		assert!(error.is_out_of_memory());
+
+		let error = Error::new_with_code(ErrorCode::NotImplemented, "Could not find an implementation for Conv(11) node with name 'conv_1'");
+		assert_eq!(error.failed_node(), None);
+		assert_eq!(error.execution_provider(), None);
+		assert!(!error.is_out_of_memory());
+	}
+
+	#[test]
+	fn test_error_raw_code() {
+		assert_eq!(Error::new("failure").raw_code(), 1);
+		assert_eq!(Error::new_with_code(ErrorCode::ExecutionProviderFailure, "failure").raw_code(), 11);
+		assert_eq!(ErrorCode::from_raw(7), ErrorCode::InvalidProtobuf);
+		assert_eq!(ErrorCode::from_raw(42), ErrorCode::GenericFailure);
+	}
+}
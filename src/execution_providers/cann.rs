@@ -136,6 +136,10 @@ impl ExecutionProvider for CANNExecutionProvider {
 		"CANNExecutionProvider"
 	}
 
+	fn with_device_id(self, device_id: i32) -> Self {
+		CANNExecutionProvider::with_device_id(self, device_id)
+	}
+
 	fn supported_by_platform(&self) -> bool {
 		cfg!(all(target_os = "linux", any(target_arch = "aarch64", target_arch = "x86_64")))
 	}
@@ -185,7 +185,41 @@ impl CUDAExecutionProvider {
 	/// > **NOTE**: The very first `run()` performs a variety of tasks under the hood like making CUDA memory
 	/// > allocations, capturing the CUDA graph for the model, and then performing a graph replay to ensure that the
 	/// > graph runs. Due to this, the latency associated with the first `run()` is bound to be high. Subsequent
-	/// > `run()`s only perform graph replays of the graph captured and cached in the first `run()`.
+	/// > `run()`s only perform graph replays of the graph captured and cached in the first `run()`. Do a warmup run
+	/// > with representative inputs before serving latency-sensitive requests.
+	///
+	/// Since replaying a graph with differently shaped inputs would silently compute garbage, sessions using CUDA
+	/// graphs remember the shapes of the values bound to each graph's first [`Session::run_binding`], and later runs
+	/// of the same graph return an error with [`ErrorCode::InvalidArgument`](crate::error::ErrorCode::InvalidArgument)
+	/// if their shapes differ. To serve several fixed shapes from one session, capture a graph for each with
+	/// [`RunOptions::with_gpu_graph_id`]. Only [`Session::run_binding`] & [`Session::run_binding_with_options`] perform
+	/// this check; runs through [`Session::run`] or its other variants are **not** checked, which is another reason to
+	/// always use I/O binding with CUDA graphs.
+	///
+	/// ```ignore
+	/// let mut session = Session::builder()?
+	/// 	.with_execution_providers([CUDAExecutionProvider::default().with_cuda_graph(true).build()])?
+	/// 	.commit_from_file("model.onnx")?;
+	///
+	/// let allocator = Allocator::new(&session, MemoryInfo::new(AllocationDevice::CUDA, 0, AllocatorType::Device, MemoryType::Default)?)?;
+	/// let input = Tensor::<f32>::new(&allocator, [1_usize, 3, 224, 224])?;
+	/// let output = Tensor::<f32>::new(&allocator, [1_usize, 1000])?;
+	///
+	/// let mut binding = session.create_binding()?;
+	/// binding.bind_input("input", &input)?;
+	/// binding.bind_output("output", output)?;
+	/// // Captures the graph; subsequent runs replay it.
+	/// session.run_binding(&binding)?;
+	/// loop {
+	/// 	// copy the next request into `input`'s memory, then:
+	/// 	let outputs = session.run_binding(&binding)?;
+	/// }
+	/// ```
+	///
+	/// [`Session::run`]: crate::session::Session::run
+	/// [`Session::run_binding`]: crate::session::Session::run_binding
+	/// [`Session::run_binding_with_options`]: crate::session::Session::run_binding_with_options
+	/// [`RunOptions::with_gpu_graph_id`]: crate::session::run_options::RunOptions::with_gpu_graph_id
 	#[must_use]
 	pub fn with_cuda_graph(mut self, enable: bool) -> Self {
 		self.options.set("enable_cuda_graph", if enable { "1" } else { "0" });
@@ -262,6 +296,10 @@ impl ExecutionProvider for CUDAExecutionProvider {
 		"CUDAExecutionProvider"
 	}
 
+	fn with_device_id(self, device_id: i32) -> Self {
+		CUDAExecutionProvider::with_device_id(self, device_id)
+	}
+
 	fn supported_by_platform(&self) -> bool {
 		cfg!(any(all(target_os = "linux", any(target_arch = "aarch64", target_arch = "x86_64")), all(target_os = "windows", target_arch = "x86_64")))
 	}
@@ -289,7 +327,11 @@ impl ExecutionProvider for CUDAExecutionProvider {
 
 			let status = crate::ortsys![unsafe SessionOptionsAppendExecutionProvider_CUDA_V2(session_builder.ptr_mut(), cuda_options)];
 			crate::ortsys![unsafe ReleaseCUDAProviderOptions(cuda_options)];
-			return unsafe { crate::error::status_to_result(status) };
+			unsafe { crate::error::status_to_result(status) }?;
+			if self.options.get("enable_cuda_graph") == Some("1") {
+				session_builder.enable_gpu_graph_shape_validation();
+			}
+			return Ok(());
 		}
 
 		Err(Error::new(format!("`{}` was not registered because its corresponding Cargo feature is not enabled.", self.as_str())))
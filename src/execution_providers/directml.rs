@@ -40,6 +40,10 @@ impl ExecutionProvider for DirectMLExecutionProvider {
 		"DmlExecutionProvider"
 	}
 
+	fn with_device_id(self, device_id: i32) -> Self {
+		DirectMLExecutionProvider::with_device_id(self, device_id)
+	}
+
 	fn supported_by_platform(&self) -> bool {
 		cfg!(target_os = "windows")
 	}
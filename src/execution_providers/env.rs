@@ -0,0 +1,116 @@
+//! Configuration of execution providers through environment variables. See [`execution_providers_from_env`].
+
+use alloc::{format, string::String, vec::Vec};
+
+use super::{
+	CANNExecutionProvider, CPUExecutionProvider, CUDAExecutionProvider, CoreMLExecutionProvider, DirectMLExecutionProvider, ExecutionProviderDispatch,
+	MIGraphXExecutionProvider, NNAPIExecutionProvider, OneDNNExecutionProvider, OpenVINOExecutionProvider, QNNExecutionProvider, ROCmExecutionProvider,
+	TensorRTExecutionProvider, WebGPUExecutionProvider, XNNPACKExecutionProvider
+};
+use crate::error::{Error, ErrorCode, Result};
+
+/// The environment variable listing the execution providers to register. See [`execution_providers_from_env`].
+pub const PROVIDER_ENV_VAR: &str = "ORT_RS_PROVIDER";
+
+/// Creates execution providers from the configuration in environment variables, so the same binary can e.g. run on
+/// the CPU in CI and with CUDA in production. Also available as
+/// [`SessionBuilder::with_execution_providers_from_env`](crate::session::builder::SessionBuilder::with_execution_providers_from_env).
+///
+/// The following variables are read:
+/// - **`ORT_RS_PROVIDER`**: A comma-separated list of execution providers to register, in order of preference, e.g.
+///   `tensorrt,cuda`. Names are case-insensitive; recognized names are `cpu`, `cuda`, `tensorrt`, `rocm`, `directml`,
+///   `openvino`, `coreml`, `onednn`, `xnnpack`, `qnn`, `webgpu`, `nnapi`, `cann`, and `migraphx`. If unset or empty, no
+///   execution providers are returned, so sessions use the CPU.
+/// - **`ORT_RS_<PROVIDER>_DEVICE`**: The ID of the device to use for `PROVIDER`, e.g. `ORT_RS_CUDA_DEVICE=1`. Supported
+///   by `cuda`, `tensorrt`, `rocm`, `directml`, `qnn`, `webgpu`, `cann`, and `migraphx`. If unset, the provider's
+///   default device is used.
+///
+/// Each provider is otherwise created with its default options. As with providers passed to
+/// [`SessionBuilder::with_execution_providers`](crate::session::builder::SessionBuilder::with_execution_providers),
+/// the Cargo feature for each provider must be enabled for it to be registered.
+///
+/// Returns an error with [`ErrorCode::InvalidArgument`] if a provider name is not recognized, or if a device ID is not
+/// an integer.
+pub fn execution_providers_from_env() -> Result<Vec<ExecutionProviderDispatch>> {
+	providers_from_vars(&|key| std::env::var(key).ok())
+}
+
+fn providers_from_vars(var: &dyn Fn(&str) -> Option<String>) -> Result<Vec<ExecutionProviderDispatch>> {
+	let Some(providers) = var(PROVIDER_ENV_VAR) else {
+		return Ok(Vec::new());
+	};
+
+	let device = |provider: &str| -> Result<Option<i32>> {
+		let key = format!("ORT_RS_{provider}_DEVICE");
+		match var(&key) {
+			Some(value) => value
+				.trim()
+				.parse()
+				.map(Some)
+				.map_err(|_| Error::new_with_code(ErrorCode::InvalidArgument, format!("Invalid device ID `{value}` in `{key}`"))),
+			None => Ok(None)
+		}
+	};
+	macro_rules! with_device {
+		($ep:ty, $provider:literal) => {{
+			let ep = <$ep>::default();
+			match device($provider)? {
+				Some(id) => ep.with_device_id(id),
+				None => ep
+			}
+			.build()
+		}};
+	}
+
+	providers
+		.split(',')
+		.map(str::trim)
+		.filter(|name| !name.is_empty())
+		.map(|name| {
+			Ok(match name.to_ascii_lowercase().as_str() {
+				"cpu" => CPUExecutionProvider::default().build(),
+				"cuda" => with_device!(CUDAExecutionProvider, "CUDA"),
+				"tensorrt" => with_device!(TensorRTExecutionProvider, "TENSORRT"),
+				"rocm" => with_device!(ROCmExecutionProvider, "ROCM"),
+				"directml" => with_device!(DirectMLExecutionProvider, "DIRECTML"),
+				"openvino" => OpenVINOExecutionProvider::default().build(),
+				"coreml" => CoreMLExecutionProvider::default().build(),
+				"onednn" => OneDNNExecutionProvider::default().build(),
+				"xnnpack" => XNNPACKExecutionProvider::default().build(),
+				"qnn" => with_device!(QNNExecutionProvider, "QNN"),
+				"webgpu" => with_device!(WebGPUExecutionProvider, "WEBGPU"),
+				"nnapi" => NNAPIExecutionProvider::default().build(),
+				"cann" => with_device!(CANNExecutionProvider, "CANN"),
+				"migraphx" => with_device!(MIGraphXExecutionProvider, "MIGRAPHX"),
+				_ => {
+					return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Unrecognized execution provider `{name}` in `{PROVIDER_ENV_VAR}`")));
+				}
+			})
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::providers_from_vars;
+
+	#[test]
+	fn test_providers_from_vars() -> crate::Result<()> {
+		let vars = |provider: &'static str, device: Option<&'static str>| {
+			move |key: &str| match key {
+				"ORT_RS_PROVIDER" => Some(provider.to_string()),
+				"ORT_RS_CUDA_DEVICE" => device.map(str::to_string),
+				_ => None
+			}
+		};
+
+		let providers = providers_from_vars(&vars(" TensorRT, cuda ,", Some("1")))?;
+		let names: Vec<_> = providers.iter().map(|ep| ep.inner.as_str()).collect();
+		assert_eq!(names, ["TensorrtExecutionProvider", "CUDAExecutionProvider"]);
+
+		assert!(providers_from_vars(&|_| None)?.is_empty());
+		assert!(providers_from_vars(&vars("cuda", Some("first"))).is_err());
+		assert!(providers_from_vars(&vars("cpu,tpu", None)).is_err());
+		Ok(())
+	}
+}
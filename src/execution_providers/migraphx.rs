@@ -79,6 +79,10 @@ impl ExecutionProvider for MIGraphXExecutionProvider {
 		"MIGraphXExecutionProvider"
 	}
 
+	fn with_device_id(self, device_id: i32) -> Self {
+		MIGraphXExecutionProvider::with_device_id(self, device_id)
+	}
+
 	fn supported_by_platform(&self) -> bool {
 		cfg!(any(all(target_os = "linux", target_arch = "x86_64"), all(target_os = "windows", target_arch = "x86_64")))
 	}
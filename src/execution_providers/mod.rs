@@ -14,7 +14,12 @@
 //! }
 //! ```
 
-use alloc::{ffi::CString, string::ToString, sync::Arc, vec::Vec};
+use alloc::{
+	ffi::CString,
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec
+};
 use core::{
 	ffi::c_char,
 	fmt::{self, Debug},
@@ -63,6 +68,11 @@ pub mod webgpu;
 pub use self::webgpu::WebGPUExecutionProvider;
 pub mod azure;
 pub use self::azure::AzureExecutionProvider;
+#[cfg(feature = "std")]
+mod env;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use self::env::{PROVIDER_ENV_VAR, execution_providers_from_env};
 
 /// ONNX Runtime works with different hardware acceleration libraries through its extensible **Execution Providers**
 /// (EP) framework to optimally execute the ONNX models on the hardware platform. This interface enables flexibility for
@@ -103,29 +113,30 @@ pub trait ExecutionProvider: Send + Sync {
 	/// enabled), you'll instead want to manually register this EP via [`ExecutionProvider::register`] and detect
 	/// and handle any errors returned by that function.
 	fn is_available(&self) -> Result<bool> {
-		let mut providers: *mut *mut c_char = ptr::null_mut();
-		let mut num_providers = 0;
-		ortsys![unsafe GetAvailableProviders(&mut providers, &mut num_providers)?];
-		if providers.is_null() {
-			return Ok(false);
-		}
-
-		for i in 0..num_providers {
-			let avail = match char_p_to_string(unsafe { *providers.offset(i as isize) }) {
-				Ok(avail) => avail,
-				Err(e) => {
-					let _ = ortsys![unsafe ReleaseAvailableProviders(providers, num_providers)];
-					return Err(e);
-				}
-			};
-			if self.as_str() == avail {
-				let _ = ortsys![unsafe ReleaseAvailableProviders(providers, num_providers)];
-				return Ok(true);
-			}
-		}
+		Ok(available_providers()?.iter().any(|p| p == self.as_str()))
+	}
 
-		let _ = ortsys![unsafe ReleaseAvailableProviders(providers, num_providers)];
-		Ok(false)
+	/// Selects the device this execution provider should run on, by its index, allowing the device to be configured in
+	/// generic code regardless of the execution provider used:
+	/// ```
+	/// # use ort::execution_providers::{CUDAExecutionProvider, ExecutionProvider};
+	/// fn on_device<P: ExecutionProvider>(provider: P, device_id: i32) -> P {
+	/// 	provider.with_device_id(device_id)
+	/// }
+	///
+	/// let provider = on_device(CUDAExecutionProvider::default(), 1).build();
+	/// ```
+	///
+	/// This is implemented by the CUDA, TensorRT, ROCm, MIGraphX, DirectML, CANN, QNN, and WebGPU execution providers.
+	/// Execution providers which cannot select a device by index, like the CPU execution provider, ignore it by
+	/// default.
+	#[must_use]
+	fn with_device_id(self, device_id: i32) -> Self
+	where
+		Self: Sized
+	{
+		let _ = device_id;
+		self
 	}
 
 	/// Attempts to register this execution provider on the given session.
@@ -196,6 +207,34 @@ impl Debug for ExecutionProviderDispatch {
 	}
 }
 
+/// Returns the identifiers of all execution providers ONNX Runtime was compiled with, as reported by ONNX Runtime
+/// (e.g. `CUDAExecutionProvider`).
+///
+/// This is useful for diagnosing why an execution provider failed to register - if it's not in this list, it was not
+/// included in the build of ONNX Runtime being used. Note that the CPU execution provider is always available.
+///
+/// ```
+/// # fn main() -> ort::Result<()> {
+/// let providers = ort::available_providers()?;
+/// assert!(providers.iter().any(|p| p == "CPUExecutionProvider"));
+/// # 	Ok(())
+/// # }
+/// ```
+pub fn available_providers() -> Result<Vec<String>> {
+	let mut providers: *mut *mut c_char = ptr::null_mut();
+	let mut num_providers = 0;
+	ortsys![unsafe GetAvailableProviders(&mut providers, &mut num_providers)?];
+	if providers.is_null() {
+		return Ok(Vec::new());
+	}
+
+	let names = (0..num_providers)
+		.map(|i| char_p_to_string(unsafe { *providers.offset(i as isize) }))
+		.collect::<Result<Vec<String>>>();
+	ortsys![unsafe ReleaseAvailableProviders(providers, num_providers)?];
+	names
+}
+
 /// Sets the current GPU device of the active EP to the device specified by `device_id`.
 ///
 /// This only works for [`CUDAExecutionProvider`] & [`ROCmExecutionProvider`].
@@ -222,6 +261,14 @@ impl ExecutionProviderOptions {
 			.insert(CString::new(key).expect("unexpected nul in key string"), CString::new(value).expect("unexpected nul in value string"));
 	}
 
+	#[allow(unused)]
+	pub fn get(&self, key: &str) -> Option<&str> {
+		self.0
+			.iter()
+			.find(|(k, _)| k.as_bytes() == key.as_bytes())
+			.and_then(|(_, v)| v.to_str().ok())
+	}
+
 	#[allow(unused)]
 	pub fn to_ffi(&self) -> ExecutionProviderOptionsFFI {
 		let (key_ptrs, value_ptrs) = self.0.iter().map(|(k, v)| (k.as_ptr(), v.as_ptr())).unzip();
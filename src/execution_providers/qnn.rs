@@ -197,6 +197,10 @@ impl ExecutionProvider for QNNExecutionProvider {
 		"QNNExecutionProvider"
 	}
 
+	fn with_device_id(self, device_id: i32) -> Self {
+		QNNExecutionProvider::with_device_id(self, device_id)
+	}
+
 	fn supported_by_platform(&self) -> bool {
 		cfg!(all(target_arch = "aarch64", any(target_os = "windows", target_os = "linux", target_os = "android")))
 	}
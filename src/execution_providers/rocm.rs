@@ -8,6 +8,13 @@ use crate::{
 	session::builder::SessionBuilder
 };
 
+/// [ROCm execution provider](https://onnxruntime.ai/docs/execution-providers/ROCm-ExecutionProvider.html) for AMD
+/// GPUs.
+///
+/// Memory on the GPU is described by [`AllocationDevice::HIP`](crate::memory::AllocationDevice::HIP) (also available as
+/// [`AllocationDevice::ROCM`](crate::memory::AllocationDevice::ROCM)), which can be used to allocate device tensors
+/// with [`Allocator::new`](crate::memory::Allocator::new) or to keep outputs on the device with
+/// [`IoBinding::bind_output_to_device`](crate::io_binding::IoBinding::bind_output_to_device).
 #[derive(Debug, Default, Clone)]
 pub struct ROCmExecutionProvider {
 	options: ExecutionProviderOptions
@@ -108,6 +115,10 @@ impl ExecutionProvider for ROCmExecutionProvider {
 		"ROCmExecutionProvider"
 	}
 
+	fn with_device_id(self, device_id: i32) -> Self {
+		ROCmExecutionProvider::with_device_id(self, device_id)
+	}
+
 	fn supported_by_platform(&self) -> bool {
 		cfg!(all(target_arch = "x86_64", target_os = "linux"))
 	}
@@ -279,6 +279,10 @@ impl ExecutionProvider for TensorRTExecutionProvider {
 		"TensorrtExecutionProvider"
 	}
 
+	fn with_device_id(self, device_id: i32) -> Self {
+		TensorRTExecutionProvider::with_device_id(self, device_id)
+	}
+
 	fn supported_by_platform(&self) -> bool {
 		cfg!(any(all(target_os = "linux", any(target_arch = "aarch64", target_arch = "x86_64")), all(target_os = "windows", target_arch = "x86_64")))
 	}
@@ -311,7 +315,11 @@ impl ExecutionProvider for TensorRTExecutionProvider {
 
 			let status = crate::ortsys![unsafe SessionOptionsAppendExecutionProvider_TensorRT_V2(session_builder.ptr_mut(), trt_options)];
 			crate::ortsys![unsafe ReleaseTensorRTProviderOptions(trt_options)];
-			return unsafe { crate::error::status_to_result(status) };
+			unsafe { crate::error::status_to_result(status) }?;
+			if self.options.get("trt_cuda_graph_enable") == Some("1") {
+				session_builder.enable_gpu_graph_shape_validation();
+			}
+			return Ok(());
 		}
 
 		Err(Error::new(format!("`{}` was not registered because its corresponding Cargo feature is not enabled.", self.as_str())))
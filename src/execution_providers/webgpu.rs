@@ -82,6 +82,11 @@ impl WebGPUValidationMode {
 	}
 }
 
+/// The WebGPU execution provider, which runs models on the GPU via WebGPU (native builds use Dawn).
+///
+/// WebGPU is not included in the default ONNX Runtime builds; it requires a build of ONNX Runtime configured with
+/// `--use_webgpu` (or, on the web, the WebGPU-enabled `onnxruntime-web` package). Use
+/// [`available_providers`](super::available_providers) to check whether the ONNX Runtime binary in use includes it.
 #[derive(Debug, Default, Clone)]
 pub struct WebGPUExecutionProvider {
 	options: ExecutionProviderOptions
@@ -118,6 +123,21 @@ impl WebGPUExecutionProvider {
 		self
 	}
 
+	/// Configures the execution provider to use an existing WebGPU instance, adapter, and device instead of creating
+	/// its own. ONNX Runtime requires a non-zero [device ID](WebGPUExecutionProvider::with_device_id) to be set when
+	/// using a custom device.
+	///
+	/// # Safety
+	/// The pointers must be valid `WGPUInstance`, `WGPUAdapter`, and `WGPUDevice` handles respectively, and must
+	/// outlive the environment/session created with the execution provider.
+	#[must_use]
+	pub unsafe fn with_device_handles(mut self, instance: *mut (), adapter: *mut (), device: *mut ()) -> Self {
+		self.options.set("WebGPU:webgpuInstance", (instance as usize).to_string());
+		self.options.set("WebGPU:webgpuAdapter", (adapter as usize).to_string());
+		self.options.set("WebGPU:webgpuDevice", (device as usize).to_string());
+		self
+	}
+
 	#[must_use]
 	pub fn with_storage_buffer_cache_mode(mut self, mode: WebGPUBufferCacheMode) -> Self {
 		self.options.set("WebGPU:storageBufferCacheMode", mode.as_str());
@@ -184,6 +204,10 @@ impl ExecutionProvider for WebGPUExecutionProvider {
 		"WebGpuExecutionProvider"
 	}
 
+	fn with_device_id(self, device_id: i32) -> Self {
+		WebGPUExecutionProvider::with_device_id(self, device_id)
+	}
+
 	fn supported_by_platform(&self) -> bool {
 		cfg!(any(target_os = "windows", target_os = "linux", target_arch = "wasm32"))
 	}
@@ -12,8 +12,9 @@ use crate::{
 	memory::MemoryInfo,
 	ortsys,
 	session::{Session, SharedSessionInner},
+	tensor::Shape,
 	util::{MiniMap, with_cstr},
-	value::{DynValue, Value, ValueInner, ValueTypeMarker}
+	value::{DynValue, Value, ValueInner, ValueType, ValueTypeMarker}
 };
 
 /// Enables binding of session inputs and/or outputs to pre-allocated memory.
@@ -109,6 +110,20 @@ impl IoBinding {
 		})
 	}
 
+	/// Returns the names & shapes of the tensors bound to inputs, and the tensors bound to outputs via
+	/// [`IoBinding::bind_output`].
+	pub(crate) fn bound_shapes(&self) -> impl Iterator<Item = (&str, &Shape)> {
+		let inputs = self.held_inputs.iter().map(|(name, value)| (name.as_str(), &value.dtype));
+		let outputs = self
+			.output_values
+			.iter()
+			.filter_map(|(name, value)| value.as_ref().map(|value| (name.as_str(), value.dtype())));
+		inputs.chain(outputs).filter_map(|(name, dtype)| match dtype {
+			ValueType::Tensor { shape, .. } => Some((name, shape)),
+			_ => None
+		})
+	}
+
 	/// Bind a [`Value`] to a session input.
 	///
 	/// Upon invocation, the value's data will be queued to be copied to the device the session is allocated on. The
@@ -151,6 +166,40 @@ impl IoBinding {
 	}
 
 	/// Bind a session output to a device which is specified by `mem_info`.
+	///
+	/// ONNX Runtime will allocate the output on that device during [`Session::run_binding`], and the output will be
+	/// returned without being copied to the host. This allows the output to be passed to another session or processed
+	/// with a device library (like [`cudarc`](https://crates.io/crates/cudarc)) without a round trip through the CPU.
+	///
+	/// ```no_run
+	/// # use ort::{
+	/// # 	execution_providers::CUDAExecutionProvider,
+	/// # 	memory::{AllocationDevice, AllocatorType, MemoryInfo, MemoryType},
+	/// # 	session::Session,
+	/// # 	value::Tensor
+	/// # };
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?
+	/// 	.with_execution_providers([CUDAExecutionProvider::default().build()])?
+	/// 	.commit_from_file("model.onnx")?;
+	/// let input = Tensor::<f32>::from_array(([1usize, 3, 224, 224], vec![0.0; 3 * 224 * 224]))?;
+	///
+	/// let mut binding = session.create_binding()?;
+	/// binding.bind_input(&session.inputs[0].name, &input)?;
+	/// let output_name = session.outputs[0].name.clone();
+	/// binding.bind_output_to_device(
+	/// 	&output_name,
+	/// 	&MemoryInfo::new(AllocationDevice::CUDA, 0, AllocatorType::Device, MemoryType::Default)?
+	/// )?;
+	///
+	/// let mut outputs = session.run_binding(&binding)?;
+	/// let output = outputs.remove(&output_name).unwrap().downcast::<ort::value::DynTensorValueType>()?;
+	/// assert_eq!(output.memory_info().allocation_device(), AllocationDevice::CUDA);
+	/// // A CUDA device pointer, valid for as long as `output` is alive.
+	/// let device_ptr = output.data_ptr()?;
+	/// # Ok(())
+	/// # }
+	/// ```
 	pub fn bind_output_to_device<S: Into<String>>(&mut self, name: S, mem_info: &MemoryInfo) -> Result<()> {
 		let name: String = name.into();
 		let ptr = self.ptr_mut();
@@ -20,6 +20,8 @@ extern crate core;
 pub mod __private {
 	pub extern crate alloc;
 	pub extern crate core;
+
+	pub use crate::session::input::has_duplicate_names;
 }
 #[macro_use]
 pub(crate) mod private;
@@ -59,7 +61,8 @@ pub(crate) use self::logging::{debug, error, info, trace, warning as warn};
 use self::util::OnceLock;
 pub use self::{
 	environment::init,
-	error::{Error, ErrorCode, Result}
+	error::{Error, ErrorCode, Result},
+	execution_providers::available_providers
 };
 
 /// The minor version of ONNX Runtime used by this version of `ort`.
@@ -1,8 +1,5 @@
 #[cfg(feature = "tracing")]
-use core::{
-	ffi::{self, CStr},
-	ptr
-};
+use core::ffi::{self, CStr};
 
 macro_rules! trace {
 	($($arg:tt)+) => {{
@@ -65,12 +62,17 @@ pub(crate) extern "system" fn custom_logger(
 	code_location: *const ffi::c_char,
 	message: *const ffi::c_char
 ) {
-	assert_ne!(code_location, ptr::null());
-	let code_location = unsafe { CStr::from_ptr(code_location) }.to_str().unwrap_or("<decode error>");
-	assert_ne!(message, ptr::null());
-	let message = unsafe { CStr::from_ptr(message) }.to_str().unwrap_or("<decode error>");
-	assert_ne!(id, ptr::null());
-	let id = unsafe { CStr::from_ptr(id) }.to_str().unwrap_or("<decode error>");
+	// This is called from ONNX Runtime, so it must not panic.
+	let to_str = |s: *const ffi::c_char| {
+		if s.is_null() {
+			"<null>"
+		} else {
+			unsafe { CStr::from_ptr(s) }.to_str().unwrap_or("<decode error>")
+		}
+	};
+	let code_location = to_str(code_location);
+	let message = to_str(message);
+	let id = to_str(id);
 
 	let span = tracing::span!(tracing::Level::TRACE, "ort", id = id, location = code_location);
 
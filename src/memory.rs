@@ -1,16 +1,17 @@
 //! Types for managing memory & device allocations.
 
-use alloc::sync::Arc;
+use alloc::{boxed::Box, format, sync::Arc};
 use core::{
+	any::Any,
 	ffi::{c_char, c_int, c_void},
-	mem,
+	fmt, mem,
 	ptr::{self, NonNull},
 	slice, str
 };
 
 use crate::{
 	AsPointer,
-	error::{Result, status_to_result},
+	error::{Error, ErrorCode, Result, status_to_result},
 	ortsys,
 	session::{Session, SharedSessionInner}
 };
@@ -243,6 +244,170 @@ impl Drop for AllocatedBlock<'_> {
 	}
 }
 
+/// An allocator implemented by user-provided callbacks, which can be registered with the [`Environment`] so that
+/// sessions allocate memory through it.
+///
+/// This is useful for integrating ONNX Runtime with an application's own memory tracking: once registered, sessions
+/// created with [`SessionBuilder::with_env_allocators`] will use this allocator for all allocations matching its
+/// [`MemoryInfo`] (typically CPU memory), instead of creating their own.
+///
+/// ```no_run
+/// # use std::{alloc::{self, Layout}, sync::atomic::{AtomicUsize, Ordering}};
+/// # use ort::{memory::{CustomAllocator, MemoryInfo, AllocationDevice, AllocatorType, MemoryType}, session::Session};
+/// # fn main() -> ort::Result<()> {
+/// static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+///
+/// // Store the size of each allocation in a 64-byte header so `free` can reconstruct the layout.
+/// // SAFETY: `alloc` returns 64-byte aligned pointers to at least `size` bytes (or null), and `free` only releases
+/// // pointers returned by `alloc`.
+/// let allocator = unsafe {
+/// 	CustomAllocator::new(
+/// 		MemoryInfo::new(AllocationDevice::CPU, 0, AllocatorType::Device, MemoryType::Default)?,
+/// 		|size| {
+/// 			ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+/// 			let ptr = alloc::alloc(Layout::from_size_align_unchecked(size + 64, 64));
+/// 			if ptr.is_null() {
+/// 				return ptr.cast();
+/// 			}
+/// 			ptr.cast::<usize>().write(size);
+/// 			ptr.add(64).cast()
+/// 		},
+/// 		|ptr| {
+/// 			let ptr = ptr.cast::<u8>().sub(64);
+/// 			let size = ptr.cast::<usize>().read();
+/// 			ALLOCATED_BYTES.fetch_sub(size, Ordering::Relaxed);
+/// 			alloc::dealloc(ptr, Layout::from_size_align_unchecked(size + 64, 64));
+/// 		}
+/// 	)
+/// };
+/// ort::init().with_allocator(allocator).commit()?;
+///
+/// let session = Session::builder()?.with_env_allocators()?.commit_from_file("tests/data/upsample.onnx")?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Thread safety
+/// ONNX Runtime may call the callbacks concurrently from any thread, including its intra-op & inter-op thread pool
+/// threads, hence the `Send + Sync` bounds. `free` may be called on a different thread than the one the memory was
+/// allocated on. Neither callback may call back into ONNX Runtime.
+///
+/// See [`CustomAllocator::new`] for the requirements on the pointers returned by `alloc`. Panics in either callback
+/// are caught; a panic in `alloc` is treated as a failed allocation.
+///
+/// [`Environment`]: crate::environment::Environment
+/// [`SessionBuilder::with_env_allocators`]: crate::session::builder::SessionBuilder::with_env_allocators
+pub struct CustomAllocator {
+	ptr: NonNull<ort_sys::OrtAllocator>,
+	_inner: Box<dyn Any>
+}
+
+// The callbacks are required to be `Send + Sync`, and `MemoryInfo` is immutable.
+unsafe impl Send for CustomAllocator {}
+unsafe impl Sync for CustomAllocator {}
+
+#[repr(C)]
+struct CustomAllocatorInner<A, F> {
+	// must be the first field so that the `OrtAllocator` pointer ONNX Runtime passes to callbacks can be cast back to
+	// `CustomAllocatorInner`
+	sys: ort_sys::OrtAllocator,
+	info: MemoryInfo,
+	alloc: A,
+	free: F
+}
+
+impl CustomAllocator {
+	/// Creates a new custom allocator described by `memory_info`, which allocates memory with `alloc` and releases
+	/// it with `free`.
+	///
+	/// See the [type-level documentation](CustomAllocator) for the threading requirements on the callbacks.
+	///
+	/// # Safety
+	/// ONNX Runtime reads & writes through the pointers returned by `alloc` without any further checks, so:
+	/// - `alloc(size)` must return either null, if the allocation fails, or a pointer to at least `size` bytes of
+	///   memory that is valid for reads & writes, on the device described by `memory_info`, until it is passed to
+	///   `free`.
+	/// - Pointers returned by `alloc` must be aligned to at least 64 bytes, the alignment ONNX Runtime assumes for
+	///   tensor data.
+	/// - `free` must correctly release any pointer previously returned by `alloc`. It is never called with a null
+	///   pointer.
+	pub unsafe fn new<A, F>(memory_info: MemoryInfo, alloc: A, free: F) -> Self
+	where
+		A: Fn(usize) -> *mut c_void + Send + Sync + 'static,
+		F: Fn(*mut c_void) + Send + Sync + 'static
+	{
+		let mut inner = Box::new(CustomAllocatorInner {
+			sys: ort_sys::OrtAllocator {
+				version: ort_sys::ORT_API_VERSION,
+				Alloc: Some(custom_alloc::<A, F>),
+				Free: Some(custom_free::<A, F>),
+				Info: Some(custom_info::<A, F>),
+				Reserve: Some(custom_reserve::<A, F>)
+			},
+			info: memory_info,
+			alloc,
+			free
+		});
+		let ptr = NonNull::from(&mut inner.sys);
+		Self { ptr, _inner: inner as Box<dyn Any> }
+	}
+
+	/// Returns the [`MemoryInfo`] describing this allocator.
+	pub fn memory_info(&self) -> MemoryInfo {
+		let memory_info_ptr = unsafe { self.ptr.as_ref().Info.unwrap_unchecked()(self.ptr.as_ptr()) };
+		MemoryInfo::from_raw(unsafe { NonNull::new_unchecked(memory_info_ptr.cast_mut()) }, false)
+	}
+}
+
+impl fmt::Debug for CustomAllocator {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("CustomAllocator").field("ptr", &self.ptr).finish_non_exhaustive()
+	}
+}
+
+impl AsPointer for CustomAllocator {
+	type Sys = ort_sys::OrtAllocator;
+
+	fn ptr(&self) -> *const Self::Sys {
+		self.ptr.as_ptr()
+	}
+}
+
+unsafe extern "system" fn custom_alloc<A: Fn(usize) -> *mut c_void, F: Fn(*mut c_void)>(this: *mut ort_sys::OrtAllocator, size: usize) -> *mut c_void {
+	let inner = unsafe { &*this.cast::<CustomAllocatorInner<A, F>>() };
+	match crate::util::catch_panic("Custom allocator `alloc`", || Ok((inner.alloc)(size))) {
+		Ok(ptr) => ptr,
+		Err(e) => {
+			crate::error!("{e}");
+			let _ = e;
+			ptr::null_mut()
+		}
+	}
+}
+
+unsafe extern "system" fn custom_reserve<A: Fn(usize) -> *mut c_void, F: Fn(*mut c_void)>(this: *const ort_sys::OrtAllocator, size: usize) -> *mut c_void {
+	unsafe { custom_alloc::<A, F>(this.cast_mut(), size) }
+}
+
+unsafe extern "system" fn custom_free<A: Fn(usize) -> *mut c_void, F: Fn(*mut c_void)>(this: *mut ort_sys::OrtAllocator, p: *mut c_void) {
+	if p.is_null() {
+		return;
+	}
+	let inner = unsafe { &*this.cast::<CustomAllocatorInner<A, F>>() };
+	if let Err(e) = crate::util::catch_panic("Custom allocator `free`", || {
+		(inner.free)(p);
+		Ok(())
+	}) {
+		crate::error!("{e}");
+		let _ = e;
+	}
+}
+
+unsafe extern "system" fn custom_info<A: Fn(usize) -> *mut c_void, F: Fn(*mut c_void)>(this: *const ort_sys::OrtAllocator) -> *const ort_sys::OrtMemoryInfo {
+	let inner = unsafe { &*this.cast::<CustomAllocatorInner<A, F>>() };
+	inner.info.ptr()
+}
+
 /// Represents possible devices that have their own device allocator.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 // &'static str should be valid here since they're only ever defined in C++ with `const char *` literals
@@ -256,16 +421,66 @@ impl AllocationDevice {
 	pub const CANN_PINNED: AllocationDevice = AllocationDevice("CannPinned\0");
 	pub const DIRECTML: AllocationDevice = AllocationDevice("DML\0");
 	pub const DIRECTML_CPU: AllocationDevice = AllocationDevice("DML CPU\0");
+	/// Alias for [`AllocationDevice::DIRECTML`].
+	pub const DML: AllocationDevice = AllocationDevice::DIRECTML;
+	/// AMD GPU memory, used by the [ROCm](crate::execution_providers::ROCmExecutionProvider) and
+	/// [MIGraphX](crate::execution_providers::MIGraphXExecutionProvider) execution providers.
 	pub const HIP: AllocationDevice = AllocationDevice("Hip\0");
+	/// Pinned host memory which AMD GPUs can access directly; see [`AllocationDevice::HIP`].
 	pub const HIP_PINNED: AllocationDevice = AllocationDevice("HipPinned\0");
+	/// Alias for [`AllocationDevice::HIP`].
+	pub const ROCM: AllocationDevice = AllocationDevice::HIP;
+	/// Alias for [`AllocationDevice::HIP_PINNED`].
+	pub const ROCM_PINNED: AllocationDevice = AllocationDevice::HIP_PINNED;
 	pub const OPENVINO_CPU: AllocationDevice = AllocationDevice("OpenVINO_CPU\0");
 	pub const OPENVINO_GPU: AllocationDevice = AllocationDevice("OpenVINO_GPU\0");
+	/// Memory shared with OpenVINO's remote tensor API.
+	pub const OPENVINO_RT: AllocationDevice = AllocationDevice("OpenVINO_RT\0");
+	/// Memory shared with OpenVINO's remote tensor API on an NPU.
+	pub const OPENVINO_RT_NPU: AllocationDevice = AllocationDevice("OpenVINO_RT_NPU\0");
+	/// GPU buffers used by the [WebGPU](crate::execution_providers::WebGPUExecutionProvider) execution provider.
+	pub const WEBGPU_BUFFER: AllocationDevice = AllocationDevice("WebGPU_Buffer\0");
+	/// `MLTensor`s used by the WebNN execution provider.
+	pub const WEBNN_TENSOR: AllocationDevice = AllocationDevice("WebNN_Tensor\0");
 	pub const XNNPACK: AllocationDevice = AllocationDevice("XnnpackExecutionProvider\0");
 	pub const TVM: AllocationDevice = AllocationDevice("TVM\0");
 
+	/// Every device name recognized by ONNX Runtime, excluding aliases like [`AllocationDevice::ROCM`].
+	pub const ALL: &'static [AllocationDevice] = &[
+		AllocationDevice::CPU,
+		AllocationDevice::CUDA,
+		AllocationDevice::CUDA_PINNED,
+		AllocationDevice::CANN,
+		AllocationDevice::CANN_PINNED,
+		AllocationDevice::DIRECTML,
+		AllocationDevice::DIRECTML_CPU,
+		AllocationDevice::HIP,
+		AllocationDevice::HIP_PINNED,
+		AllocationDevice::OPENVINO_CPU,
+		AllocationDevice::OPENVINO_GPU,
+		AllocationDevice::OPENVINO_RT,
+		AllocationDevice::OPENVINO_RT_NPU,
+		AllocationDevice::WEBGPU_BUFFER,
+		AllocationDevice::WEBNN_TENSOR,
+		AllocationDevice::XNNPACK,
+		AllocationDevice::TVM
+	];
+
+	/// Looks up a device by the name ONNX Runtime uses for it, e.g. `CudaPinned`. Returns `None` if the name is not
+	/// one of [`AllocationDevice::ALL`].
+	pub fn from_name(name: &str) -> Option<AllocationDevice> {
+		AllocationDevice::ALL.iter().copied().find(|device| device.as_str() == name)
+	}
+
 	pub fn as_str(&self) -> &'static str {
 		&self.0[..self.0.len() - 1]
 	}
+
+	/// Returns `true` if this device describes pinned host memory, which is CPU memory that a non-CPU execution
+	/// provider can access directly, e.g. [`AllocationDevice::CUDA_PINNED`].
+	pub fn is_pinned(&self) -> bool {
+		matches!(*self, AllocationDevice::CUDA_PINNED | AllocationDevice::CANN_PINNED | AllocationDevice::HIP_PINNED)
+	}
 }
 
 impl PartialEq<str> for AllocationDevice {
@@ -293,9 +508,24 @@ impl From<AllocatorType> for ort_sys::OrtAllocatorType {
 }
 
 /// Memory types for allocated memory.
+///
+/// [`CPUInput`](MemoryType::CPUInput) and [`CPUOutput`](MemoryType::CPUOutput) describe host memory used by a
+/// non-CPU execution provider, and are what distinguish pinned staging buffers (e.g. [`AllocationDevice::CUDA_PINNED`])
+/// from regular device memory. When staging data for an EP like CUDA:
+/// - Use `CPUInput` for buffers that are filled on the host and read by the EP, i.e. pinned *inputs* bound with
+///   [`IoBinding::bind_input`](crate::io_binding::IoBinding::bind_input).
+/// - Use `CPUOutput` for buffers the EP writes its results into, i.e. pinned *outputs* bound with
+///   [`IoBinding::bind_output`](crate::io_binding::IoBinding::bind_output) or
+///   [`IoBinding::bind_output_to_device`](crate::io_binding::IoBinding::bind_output_to_device).
+///
+/// ONNX Runtime looks up allocators by both device and memory type, so a mismatched memory type can fail to find the
+/// EP's pinned allocator, or cause data to be staged through an additional copy.
+/// [`MemoryInfo::new`] rejects combinations that are never valid, like pinned memory with
+/// [`MemoryType::Default`]. Note that [`AllocationDevice::CPU`] with [`MemoryType::Default`] is valid, and is how
+/// regular CPU memory is described.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MemoryType {
-	/// Any CPU memory used by non-CPU execution provider.
+	/// CPU memory read by a non-CPU execution provider, e.g. pinned memory used to stage inputs.
 	CPUInput,
 	/// CPU-accessible memory output by a non-CPU execution provider, i.e. [`AllocationDevice::CUDA_PINNED`].
 	CPUOutput,
@@ -389,7 +619,34 @@ impl MemoryInfo {
 	/// # Ok(())
 	/// # }
 	/// ```
+	///
+	/// # Errors
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if:
+	/// - `allocation_device` is pinned host memory (e.g. [`AllocationDevice::CUDA_PINNED`]) and `memory_type` is
+	///   [`MemoryType::Default`]; pinned memory must use [`MemoryType::CPUInput`] or [`MemoryType::CPUOutput`].
+	/// - `device_id` is negative.
+	/// - `allocation_device` is [`AllocationDevice::CPU`] and `device_id` is not `0`; ONNX Runtime only has one CPU
+	///   device, so such memory would never match the CPU allocator.
 	pub fn new(allocation_device: AllocationDevice, device_id: c_int, allocator_type: AllocatorType, memory_type: MemoryType) -> Result<Self> {
+		if device_id < 0 {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Device ID must not be negative, got {device_id}")));
+		}
+		if allocation_device == AllocationDevice::CPU && device_id != 0 {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("CPU memory must use device ID 0, got {device_id}")));
+		}
+		if allocation_device.is_pinned() && memory_type == MemoryType::Default {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!(
+					"Pinned memory on device `{}` must use `MemoryType::CPUInput` (for inputs) or `MemoryType::CPUOutput` (for outputs), not `MemoryType::Default`",
+					allocation_device.as_str()
+				)
+			));
+		}
+		Self::new_unchecked(allocation_device, device_id, allocator_type, memory_type)
+	}
+
+	fn new_unchecked(allocation_device: AllocationDevice, device_id: c_int, allocator_type: AllocatorType, memory_type: MemoryType) -> Result<Self> {
 		let mut memory_info_ptr: *mut ort_sys::OrtMemoryInfo = ptr::null_mut();
 		ortsys![
 			unsafe CreateMemoryInfo(allocation_device.as_str().as_ptr().cast(), allocator_type.into(), device_id, memory_type.into(), &mut memory_info_ptr)?;
@@ -507,8 +764,49 @@ impl MemoryInfo {
 
 	/// Returns `true` if this memory is accessible by the CPU; meaning that, if a value were allocated on this device,
 	/// it could be extracted to an `ndarray` or slice.
+	///
+	/// This is the case for memory on a CPU device (including pinned memory like [`AllocationDevice::CUDA_PINNED`]),
+	/// and for memory with a [`MemoryType`] of [`MemoryType::CPUInput`] or [`MemoryType::CPUOutput`]. Pure device
+	/// memory, i.e. [`AllocationDevice::CUDA`] with [`MemoryType::Default`], is not CPU-accessible; attempting to read
+	/// it from the CPU is undefined behavior.
+	///
+	/// ```
+	/// # use ort::memory::{MemoryInfo, MemoryType, AllocationDevice, AllocatorType};
+	/// # fn main() -> ort::Result<()> {
+	/// let mem = MemoryInfo::new(AllocationDevice::CUDA_PINNED, 0, AllocatorType::Device, MemoryType::CPUOutput)?;
+	/// assert!(mem.is_cpu_accessible());
+	///
+	/// let mem = MemoryInfo::new(AllocationDevice::CUDA, 0, AllocatorType::Device, MemoryType::Default)?;
+	/// assert!(!mem.is_cpu_accessible());
+	/// # Ok(())
+	/// # }
+	/// ```
 	pub fn is_cpu_accessible(&self) -> bool {
-		self.device_type() == DeviceType::CPU
+		self.device_type() == DeviceType::CPU || matches!(self.memory_type(), MemoryType::CPUInput | MemoryType::CPUOutput)
+	}
+
+	/// Returns `true` if memory described by `other` can be used where memory described by `self` is expected; that is,
+	/// if both describe the same [`AllocationDevice`], device ID, and [`MemoryType`].
+	///
+	/// Unlike `==`, this ignores the [`AllocatorType`], since it only describes how memory was allocated, not where it
+	/// resides. This is useful to check that memory allocated by an [`Allocator`] matches the [`MemoryInfo`] a tensor
+	/// is about to be created with, i.e. via [`TensorRefMut::from_raw`](crate::value::TensorRefMut::from_raw):
+	///
+	/// ```
+	/// # use ort::memory::{Allocator, MemoryInfo, MemoryType, AllocationDevice, AllocatorType};
+	/// # fn main() -> ort::Result<()> {
+	/// let allocator = Allocator::default();
+	/// let info = MemoryInfo::new(AllocationDevice::CPU, 0, AllocatorType::Device, MemoryType::Default)?;
+	/// assert!(info.is_compatible_with(&allocator.memory_info()));
+	///
+	/// let info = MemoryInfo::new(AllocationDevice::CUDA, 0, AllocatorType::Device, MemoryType::Default)?;
+	/// assert!(!info.is_compatible_with(&allocator.memory_info()));
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[doc(alias = "compatible_with")]
+	pub fn is_compatible_with(&self, other: &MemoryInfo) -> bool {
+		self.allocation_device() == other.allocation_device() && self.device_id() == other.device_id() && self.memory_type() == other.memory_type()
 	}
 }
 
@@ -520,10 +818,12 @@ impl Default for MemoryInfo {
 
 impl Clone for MemoryInfo {
 	fn clone(&self) -> Self {
-		MemoryInfo::new(self.allocation_device(), self.device_id(), self.allocator_type(), self.memory_type()).expect("failed to clone memory info")
+		MemoryInfo::new_unchecked(self.allocation_device(), self.device_id(), self.allocator_type(), self.memory_type()).expect("failed to clone memory info")
 	}
 }
 
+/// Two [`MemoryInfo`]s are equal if they describe the same [`AllocationDevice`], device ID, [`AllocatorType`], and
+/// [`MemoryType`]. See also [`MemoryInfo::is_compatible_with`], which ignores the allocator type.
 impl PartialEq<MemoryInfo> for MemoryInfo {
 	fn eq(&self, other: &MemoryInfo) -> bool {
 		let mut out = 0;
@@ -550,7 +850,16 @@ impl Drop for MemoryInfo {
 
 #[cfg(test)]
 mod tests {
-	use super::{AllocationDevice, AllocatorType, MemoryInfo, MemoryType};
+	use std::{
+		alloc::{self, Layout},
+		sync::{
+			Arc,
+			atomic::{AtomicUsize, Ordering}
+		}
+	};
+
+	use super::{AllocationDevice, AllocatorType, CustomAllocator, MemoryInfo, MemoryType};
+	use crate::{AsPointer, ErrorCode};
 
 	#[test]
 	fn test_memory_info_eq() -> crate::Result<()> {
@@ -561,4 +870,97 @@ mod tests {
 		assert_ne!(a, c);
 		Ok(())
 	}
+
+	#[test]
+	fn test_memory_info_compatible() -> crate::Result<()> {
+		let a = MemoryInfo::new(AllocationDevice::CUDA, 1, AllocatorType::Device, MemoryType::Default)?;
+		let b = MemoryInfo::new(AllocationDevice::CUDA, 1, AllocatorType::Arena, MemoryType::Default)?;
+		assert_ne!(a, b);
+		assert!(a.is_compatible_with(&b));
+		let c = MemoryInfo::new(AllocationDevice::CUDA, 0, AllocatorType::Device, MemoryType::Default)?;
+		assert!(!a.is_compatible_with(&c));
+		let d = MemoryInfo::new(AllocationDevice::CUDA, 1, AllocatorType::Device, MemoryType::CPUOutput)?;
+		assert!(!a.is_compatible_with(&d));
+		Ok(())
+	}
+
+	#[test]
+	fn test_memory_info_hip() -> crate::Result<()> {
+		let mem = MemoryInfo::new(AllocationDevice::ROCM, 0, AllocatorType::Device, MemoryType::Default)?;
+		assert_eq!(mem.allocation_device(), AllocationDevice::HIP);
+		assert!(!mem.is_cpu_accessible());
+		let pinned = MemoryInfo::new(AllocationDevice::HIP_PINNED, 0, AllocatorType::Device, MemoryType::CPUOutput)?;
+		assert!(pinned.is_cpu_accessible());
+		Ok(())
+	}
+
+	#[test]
+	fn test_memory_info_cpu_accessible() -> crate::Result<()> {
+		assert!(MemoryInfo::new(AllocationDevice::CPU, 0, AllocatorType::Device, MemoryType::Default)?.is_cpu_accessible());
+		assert!(MemoryInfo::new(AllocationDevice::CUDA_PINNED, 0, AllocatorType::Device, MemoryType::CPUOutput)?.is_cpu_accessible());
+		assert!(MemoryInfo::new(AllocationDevice::CUDA, 0, AllocatorType::Device, MemoryType::CPUInput)?.is_cpu_accessible());
+		assert!(!MemoryInfo::new(AllocationDevice::CUDA, 0, AllocatorType::Device, MemoryType::Default)?.is_cpu_accessible());
+		Ok(())
+	}
+
+	#[test]
+	fn test_memory_info_validation() {
+		for device in [AllocationDevice::CUDA_PINNED, AllocationDevice::HIP_PINNED, AllocationDevice::CANN_PINNED] {
+			let err = MemoryInfo::new(device, 0, AllocatorType::Device, MemoryType::Default).expect_err("pinned memory cannot use the default memory type");
+			assert_eq!(err.code(), ErrorCode::InvalidArgument);
+		}
+		let err = MemoryInfo::new(AllocationDevice::CUDA, -1, AllocatorType::Device, MemoryType::Default).expect_err("device ID cannot be negative");
+		assert_eq!(err.code(), ErrorCode::InvalidArgument);
+		let err = MemoryInfo::new(AllocationDevice::CPU, 1, AllocatorType::Arena, MemoryType::Default).expect_err("there is only one CPU device");
+		assert_eq!(err.code(), ErrorCode::InvalidArgument);
+	}
+
+	#[test]
+	fn test_allocation_device_from_name() {
+		assert_eq!(AllocationDevice::from_name("CudaPinned"), Some(AllocationDevice::CUDA_PINNED));
+		assert_eq!(AllocationDevice::from_name("Hip"), Some(AllocationDevice::ROCM));
+		assert_eq!(AllocationDevice::from_name("Cuda\0"), None);
+		assert_eq!(AllocationDevice::from_name("cuda"), None);
+		for device in AllocationDevice::ALL {
+			assert_eq!(AllocationDevice::from_name(device.as_str()), Some(*device));
+		}
+	}
+
+	#[test]
+	fn test_custom_allocator() -> crate::Result<()> {
+		const SIZE: usize = 16;
+		let layout = Layout::from_size_align(SIZE, 64).expect("valid layout");
+		let live = Arc::new(AtomicUsize::new(0));
+		// SAFETY: `alloc` returns 64-byte aligned pointers to `SIZE` bytes, and is only called with `SIZE` below.
+		let allocator = unsafe {
+			CustomAllocator::new(
+				MemoryInfo::new(AllocationDevice::CPU, 0, AllocatorType::Arena, MemoryType::Default)?,
+				{
+					let live = Arc::clone(&live);
+					move |size| {
+						assert_eq!(size, SIZE);
+						live.fetch_add(1, Ordering::SeqCst);
+						alloc::alloc(layout).cast()
+					}
+				},
+				{
+					let live = Arc::clone(&live);
+					move |ptr| {
+						live.fetch_sub(1, Ordering::SeqCst);
+						alloc::dealloc(ptr.cast(), layout);
+					}
+				}
+			)
+		};
+		assert_eq!(allocator.memory_info().allocator_type(), AllocatorType::Arena);
+
+		let sys = unsafe { &*allocator.ptr() };
+		let ptr = unsafe { sys.Alloc.expect("Alloc is set")(allocator.ptr().cast_mut(), SIZE) };
+		assert!(!ptr.is_null());
+		assert_eq!(ptr as usize % 64, 0);
+		assert_eq!(live.load(Ordering::SeqCst), 1);
+		unsafe { sys.Free.expect("Free is set")(allocator.ptr().cast_mut(), ptr) };
+		assert_eq!(live.load(Ordering::SeqCst), 0);
+		Ok(())
+	}
 }
@@ -6,7 +6,7 @@ use super::{
 	io::{self, InputOutputCharacteristic},
 	kernel::{Kernel, KernelAttributes, KernelContext}
 };
-use crate::{Result, error::IntoStatus};
+use crate::{Result, error::IntoStatus, util::catch_panic};
 
 #[repr(C)] // <- important! a defined layout allows us to store extra data after the `OrtCustomOp` that we can retrieve later
 pub(crate) struct BoundOperator {
@@ -74,10 +74,10 @@ impl BoundOperator {
 		kernel_ptr: *mut *mut ort_sys::c_void
 	) -> ort_sys::OrtStatusPtr {
 		let safe = Self::safe(op);
-		let kernel = match safe
-			.operator
-			.create_kernel(&KernelAttributes::from_ptr(NonNull::new(info.cast_mut()).expect("infallible"), false))
-		{
+		let kernel = match catch_panic("Operator::create_kernel", || {
+			safe.operator
+				.create_kernel(&KernelAttributes::from_ptr(NonNull::new(info.cast_mut()).expect("infallible"), false))
+		}) {
 			Ok(kernel) => kernel,
 			e => return e.into_status()
 		};
@@ -87,7 +87,8 @@ impl BoundOperator {
 
 	pub(crate) extern "system" fn compute_kernel(kernel_ptr: *mut ort_sys::c_void, context: *mut ort_sys::OrtKernelContext) -> ort_sys::OrtStatusPtr {
 		let context = KernelContext::new(context);
-		unsafe { &mut *kernel_ptr.cast::<Box<dyn Kernel>>() }.compute(&context).into_status()
+		let kernel = unsafe { &mut *kernel_ptr.cast::<Box<dyn Kernel>>() };
+		catch_panic("Kernel::compute", || kernel.compute(&context)).into_status()
 	}
 
 	pub(crate) extern "system" fn destroy_kernel(op_kernel: *mut ort_sys::c_void) {
@@ -200,6 +201,6 @@ impl BoundOperator {
 	pub(crate) extern "system" fn infer_output_shape(op: *const ort_sys::OrtCustomOp, ctx: *mut ort_sys::OrtShapeInferContext) -> ort_sys::OrtStatusPtr {
 		let safe = Self::safe(op);
 		let mut ctx = ShapeInferenceContext { ptr: ctx };
-		safe.operator.infer_shape(&mut ctx).into_status()
+		catch_panic("Operator::infer_shape", || safe.operator.infer_shape(&mut ctx)).into_status()
 	}
 }
@@ -111,7 +111,18 @@ pub(crate) struct AsyncInferenceContext<'r, 's> {
 	pub(crate) output_value_ptrs: SmallVec<*mut ort_sys::OrtValue, { STACK_SESSION_OUTPUTS }>
 }
 
-pub(crate) extern "system" fn async_callback(user_data: *mut c_void, _: *mut *mut ort_sys::OrtValue, _: usize, status: ort_sys::OrtStatusPtr) {
+pub(crate) extern "system" fn async_callback(user_data: *mut c_void, outputs: *mut *mut ort_sys::OrtValue, num_outputs: usize, status: ort_sys::OrtStatusPtr) {
+	// The waker is user code which may panic; don't let that unwind into ONNX Runtime.
+	if let Err(e) = crate::util::catch_panic("Async inference completion", || {
+		async_callback_inner(user_data, outputs, num_outputs, status);
+		Ok(())
+	}) {
+		crate::error!("{e}");
+		let _ = e;
+	}
+}
+
+fn async_callback_inner(user_data: *mut c_void, _: *mut *mut ort_sys::OrtValue, _: usize, status: ort_sys::OrtStatusPtr) {
 	let ctx = unsafe { Box::from_raw(user_data.cast::<AsyncInferenceContext<'_, '_>>()) };
 
 	// Reconvert name ptrs to CString so drop impl is called and memory is freed
@@ -1,10 +1,11 @@
-use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use alloc::{boxed::Box, format, sync::Arc, vec::Vec};
 #[cfg(feature = "fetch-models")]
 use core::fmt::Write;
 use core::{
 	any::Any,
 	ffi::c_void,
 	marker::PhantomData,
+	mem,
 	ptr::{self, NonNull}
 };
 #[cfg(feature = "std")]
@@ -14,30 +15,97 @@ use std::path::PathBuf;
 
 use smallvec::SmallVec;
 
-use super::SessionBuilder;
-#[cfg(feature = "std")]
-use crate::error::{Error, ErrorCode};
+use super::{LoadPhase, SessionBuilder};
 use crate::{
 	AsPointer,
-	environment::get_environment,
-	error::Result,
+	environment::{Environment, get_environment},
+	error::{Error, ErrorCode, Result},
 	execution_providers::apply_execution_providers,
 	memory::Allocator,
 	ortsys,
-	session::{InMemorySession, Input, Output, Session, SharedSessionInner, dangerous}
+	session::{InMemorySession, Input, ModelSource, Output, RetainedModel, Session, SharedSessionInner, dangerous},
+	util::MiniMap
 };
 
 impl SessionBuilder {
+	fn apply_global_thread_pool(&mut self, env: &Environment) -> Result<()> {
+		if env.has_global_threadpool {
+			if !self.no_global_thread_pool {
+				ortsys![unsafe DisablePerSessionThreads(self.ptr_mut())?];
+			}
+		} else if self.require_global_thread_pool {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				"Session requires the global thread pool (via `with_disable_per_session_threads`), but the environment was not configured with one; call `ort::init().with_global_thread_pool(...).commit()` before creating the session"
+			));
+		}
+		Ok(())
+	}
+
+	/// Checks that every initializer overridden with [`SessionBuilder::with_initializer`] is one of the newly created
+	/// session's overridable initializers. The session is released if the check fails.
+	fn check_initializer_names(&self, session_ptr: NonNull<ort_sys::OrtSession>) -> Result<()> {
+		if self.initializers.is_empty() {
+			return Ok(());
+		}
+		let result = dangerous::extract_overridable_initializer_names(session_ptr, &Allocator::default()).and_then(|names| {
+			match self.initializers.iter().find(|(name, _)| !names.contains(name)) {
+				Some((name, _)) => Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Cannot override initializer `{name}`, since the model has no overridable initializer with that name")
+				)),
+				None => Ok(())
+			}
+		});
+		if result.is_err() {
+			ortsys![unsafe ReleaseSession(session_ptr.as_ptr())];
+		}
+		result
+	}
+
+	fn report_load_phase(&self, phase: LoadPhase) {
+		if let Some(callback) = &self.load_callback {
+			(callback.borrow_mut())(phase);
+		}
+	}
+
 	/// Downloads a pre-trained ONNX model from the given URL and builds the session.
 	#[cfg(all(feature = "fetch-models", feature = "std"))]
 	#[cfg_attr(docsrs, doc(cfg(all(feature = "fetch-models", feature = "std"))))]
 	pub fn commit_from_url(self, model_url: impl AsRef<str>) -> Result<Session> {
-		let downloaded_path = SessionBuilder::download(model_url.as_ref())?;
+		let downloaded_path = SessionBuilder::download(model_url.as_ref(), &mut |_, _| {})?;
 		self.commit_from_file(downloaded_path)
 	}
 
+	/// Downloads a pre-trained ONNX model from the given URL and builds the session, reporting the download's progress
+	/// to `progress`.
+	///
+	/// `progress` is called periodically as the model is downloaded with the number of bytes downloaded so far, and the
+	/// total size of the model if the server reported it. If the model was already downloaded, `progress` is not
+	/// called.
+	///
+	/// ```no_run
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session =
+	/// 	Session::builder()?.commit_from_url_with_progress("https://example.com/model.onnx", |downloaded, total| {
+	/// 		match total {
+	/// 			Some(total) => eprint!("\rDownloading model: {downloaded}/{total} bytes"),
+	/// 			None => eprint!("\rDownloading model: {downloaded} bytes")
+	/// 		}
+	/// 	})?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
 	#[cfg(all(feature = "fetch-models", feature = "std"))]
-	fn download(url: &str) -> Result<PathBuf> {
+	#[cfg_attr(docsrs, doc(cfg(all(feature = "fetch-models", feature = "std"))))]
+	pub fn commit_from_url_with_progress(self, model_url: impl AsRef<str>, mut progress: impl FnMut(u64, Option<u64>)) -> Result<Session> {
+		let downloaded_path = SessionBuilder::download(model_url.as_ref(), &mut progress)?;
+		self.commit_from_file(downloaded_path)
+	}
+
+	#[cfg(all(feature = "fetch-models", feature = "std"))]
+	fn download(url: &str, progress: &mut dyn FnMut(u64, Option<u64>)) -> Result<PathBuf> {
 		let mut download_dir = ort_sys::internal::dirs::cache_dir()
 			.expect("could not determine cache directory")
 			.join("models");
@@ -62,23 +130,52 @@ impl SessionBuilder {
 				.headers()
 				.get("Content-Length")
 				.and_then(|h| h.to_str().ok())
-				.and_then(|s| s.parse::<usize>().ok())
-				.expect("Missing Content-Length header");
-			crate::info!(len, "Downloading {} bytes", len);
+				.and_then(|s| s.parse::<u64>().ok());
+			#[cfg(feature = "tracing")]
+			if let Some(len) = len {
+				crate::info!(len, "Downloading {} bytes", len);
+			}
 
 			let mut reader = resp.into_body().into_with_config().limit(u64::MAX).reader();
 			let temp_filepath = download_dir.join(format!("tmp_{}.{model_filename}", ort_sys::internal::random_identifier()));
 
-			let f = std::fs::File::create(&temp_filepath).expect("Failed to create model file");
+			let f = std::fs::File::create(&temp_filepath).map_err(|e| Error::new(format!("Failed to create model file: {e}")))?;
 			let mut writer = std::io::BufWriter::new(f);
 
-			let bytes_io_count = std::io::copy(&mut reader, &mut writer).map_err(Error::wrap)?;
-			if bytes_io_count != len as u64 {
-				return Err(Error::new(format!("Failed to download entire model; file only has {bytes_io_count} bytes, expected {len}")));
-			}
-
+			// Stream the body to disk in chunks so we can report progress without buffering the whole model in memory.
+			let copy_result = (|| -> std::io::Result<u64> {
+				let mut buf = vec![0u8; 64 * 1024];
+				let mut downloaded = 0;
+				loop {
+					let n = match std::io::Read::read(&mut reader, &mut buf) {
+						Ok(0) => break,
+						Ok(n) => n,
+						Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+						Err(e) => return Err(e)
+					};
+					std::io::Write::write_all(&mut writer, &buf[..n])?;
+					downloaded += n as u64;
+					progress(downloaded, len);
+				}
+				std::io::Write::flush(&mut writer)?;
+				Ok(downloaded)
+			})();
 			drop(writer);
 
+			let bytes_io_count = match copy_result {
+				Ok(count) => count,
+				Err(e) => {
+					let _ = std::fs::remove_file(&temp_filepath);
+					return Err(Error::wrap(e));
+				}
+			};
+			if let Some(len) = len {
+				if bytes_io_count != len {
+					let _ = std::fs::remove_file(&temp_filepath);
+					return Err(Error::new(format!("Failed to download entire model; file only has {bytes_io_count} bytes, expected {len}")));
+				}
+			}
+
 			match std::fs::rename(&temp_filepath, &model_filepath) {
 				Ok(()) => Ok(model_filepath),
 				Err(e) => {
@@ -103,20 +200,76 @@ impl SessionBuilder {
 		self.commit_from_file_inner(model_filepath.as_ref())
 	}
 
+	/// Builds a session from the same model as an existing `session`, e.g. to run one model on both the CPU and a GPU
+	/// by committing builders with different execution providers.
+	///
+	/// By default, the model is read again from the file `session` was loaded from, so if the file has changed since
+	/// `session` was committed, the new session uses the new model. If `session` was committed with
+	/// [`SessionBuilder::with_retained_model`] enabled, the new session instead shares its copy of the model, which is
+	/// not read from disk again; if that model was loaded from a file, its external data files are still looked up
+	/// relative to the file's directory.
+	///
+	/// ONNX Runtime cannot create a session from another session's loaded graph, so the model is parsed & optimized
+	/// again, and the new session holds its own copy of the model's weights. To share memory between sessions, share
+	/// their weights with [`SessionBuilder::with_prepacked_weights`], or load large weights once with
+	/// [`SessionBuilder::with_external_initializer`].
+	///
+	/// ```no_run
+	/// # use ort::{execution_providers::CUDAExecutionProvider, session::Session};
+	/// # fn main() -> ort::Result<()> {
+	/// let cpu_session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let gpu_session = Session::builder()?
+	/// 	.with_execution_providers([CUDAExecutionProvider::default().build().error_on_failure()])?
+	/// 	.commit_from_session_model(&cpu_session)?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if `session` was committed from memory without
+	/// [`SessionBuilder::with_retained_model`]; commit the new session from the same bytes instead.
+	pub fn commit_from_session_model(mut self, session: &Session) -> Result<Session> {
+		match session.model.as_ref() {
+			#[cfg(feature = "std")]
+			Some(ModelSource::File(path)) => self.commit_from_file_inner(path),
+			Some(ModelSource::Retained(model)) => {
+				if let Some(dir) = model.dir.as_ref() {
+					self.add_config_entry("session.model_external_initializers_file_folder_path", dir)?;
+				}
+				self.commit_from_memory_inner(&model.bytes, Some(ModelSource::Retained(model.clone())))
+			}
+			None => Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				"Session was committed from memory and does not retain its model; commit the new session from the same model bytes, or enable `SessionBuilder::with_retained_model`"
+			))
+		}
+	}
+
 	#[cfg(feature = "std")]
 	fn commit_from_file_inner(mut self, model_filepath: &Path) -> Result<Session> {
 		if !model_filepath.exists() {
 			return Err(Error::new_with_code(ErrorCode::NoSuchFile, format!("File at `{}` does not exist", model_filepath.display())));
 		}
 
+		let absolute_path = std::path::absolute(model_filepath).unwrap_or_else(|_| model_filepath.to_path_buf());
+		let model = if self.retain_model {
+			ModelSource::Retained(RetainedModel {
+				bytes: std::fs::read(model_filepath).map_err(Error::wrap)?.into(),
+				dir: absolute_path.parent().map(|dir| dir.to_string_lossy().into_owned())
+			})
+		} else {
+			ModelSource::File(absolute_path)
+		};
+
 		let model_path = crate::util::path_to_os_char(model_filepath);
 
 		let env = get_environment()?;
+		self.report_load_phase(LoadPhase::RegisteringExecutionProviders);
 		apply_execution_providers(&mut self, &env.execution_providers, "environment")?;
 
-		if env.has_global_threadpool && !self.no_global_thread_pool {
-			ortsys![unsafe DisablePerSessionThreads(self.ptr_mut())?];
-		}
+		self.apply_global_thread_pool(env)?;
+
+		self.report_load_phase(LoadPhase::CreatingSession);
 
 		let mut session_ptr: *mut ort_sys::OrtSession = ptr::null_mut();
 		if let Some(prepacked_weights) = self.prepacked_weights.as_ref() {
@@ -126,6 +279,7 @@ impl SessionBuilder {
 		}
 
 		let session_ptr = unsafe { NonNull::new_unchecked(session_ptr) };
+		self.check_initializer_names(session_ptr)?;
 
 		let allocator = match &self.memory_info {
 			Some(info) => {
@@ -137,6 +291,7 @@ impl SessionBuilder {
 		};
 
 		// Extract input and output properties
+		self.report_load_phase(LoadPhase::ReadingMetadata);
 		let num_input_nodes = dangerous::extract_inputs_count(session_ptr)?;
 		let num_output_nodes = dangerous::extract_outputs_count(session_ptr)?;
 		let inputs = (0..num_input_nodes)
@@ -153,21 +308,42 @@ impl SessionBuilder {
 		if let Some(thread_manager) = self.thread_manager.take() {
 			extras.push(Box::new(thread_manager) as Box<dyn Any>);
 		}
+		if !self.initializers.is_empty() {
+			extras.push(Box::new(mem::take(&mut self.initializers)) as Box<dyn Any>);
+		}
 
-		Ok(Session {
+		let session = Session {
 			inner: Arc::new(SharedSessionInner {
 				session_ptr,
 				allocator,
 				_extras: extras
 			}),
 			inputs,
-			outputs
-		})
+			outputs,
+			validate_inputs: self.validate_inputs,
+			captured_graph_shapes: self.validate_gpu_graph_shapes.then(MiniMap::new),
+			profiling: self.profiling,
+			model: Some(model),
+			#[cfg(feature = "std")]
+			optimized_model_path: self.optimized_model_path.take()
+		};
+		self.report_load_phase(LoadPhase::Finished);
+		Ok(session)
 	}
 
-	/// Load an ONNX graph from memory and commit the session
-	/// For `.ort` models, we enable `session.use_ort_model_bytes_directly`.
-	/// For more information, check [Load ORT format model from an in-memory byte array](https://onnxruntime.ai/docs/performance/model-optimizations/ort-format-models.html#load-ort-format-model-from-an-in-memory-byte-array).
+	/// Load a model from memory and commit the session, referencing `model_bytes` in place instead of copying it where
+	/// possible. The returned [`InMemorySession`] borrows `model_bytes`, so the buffer (e.g. a memory-mapped file) is
+	/// guaranteed to outlive the session.
+	///
+	/// Zero-copy loading is **only supported for models in the `.ort` format**; this enables
+	/// `session.use_ort_model_bytes_directly` and `session.use_ort_model_bytes_for_initializers`, so ONNX Runtime uses
+	/// the graph & initializer data directly from `model_bytes`. For more information, see [Load ORT format model from
+	/// an in-memory byte array](https://onnxruntime.ai/docs/performance/model-optimizations/ort-format-models.html#load-ort-format-model-from-an-in-memory-byte-array).
+	///
+	/// `.onnx` (protobuf) models are always deserialized into ONNX Runtime's own graph representation, copying all
+	/// initializers, so passing one here saves no memory compared to [`SessionBuilder::commit_from_memory`]. To
+	/// avoid holding two copies of a large `.onnx` model, either convert it to the `.ort` format, or drop the model
+	/// bytes after using [`SessionBuilder::commit_from_memory`], which does not need them after the session is created.
 	///
 	/// If you wish to store the model bytes and the [`InMemorySession`] in the same struct, look for crates that
 	/// facilitate creating self-referential structs, such as [`ouroboros`](https://github.com/joshua-maros/ouroboros).
@@ -176,20 +352,35 @@ impl SessionBuilder {
 		self.add_config_entry("session.use_ort_model_bytes_directly", "1")?;
 		self.add_config_entry("session.use_ort_model_bytes_for_initializers", "1")?;
 
-		let session = self.commit_from_memory(model_bytes)?;
+		let session = self.commit_from_memory_inner(model_bytes, None)?;
 		Ok(InMemorySession { session, phantom: PhantomData })
 	}
 
 	/// Load an ONNX graph from memory and commit the session.
-	pub fn commit_from_memory(mut self, model_bytes: &[u8]) -> Result<Session> {
+	///
+	/// ONNX Runtime copies everything it needs out of `model_bytes` while creating the session, so the buffer can be
+	/// dropped as soon as this function returns. To load an `.ort` format model without copying, see
+	/// [`SessionBuilder::commit_from_memory_directly`].
+	pub fn commit_from_memory(self, model_bytes: &[u8]) -> Result<Session> {
+		let model = self.retain_model.then(|| {
+			ModelSource::Retained(RetainedModel {
+				bytes: Arc::from(model_bytes),
+				dir: None
+			})
+		});
+		self.commit_from_memory_inner(model_bytes, model)
+	}
+
+	fn commit_from_memory_inner(mut self, model_bytes: &[u8], model: Option<ModelSource>) -> Result<Session> {
 		let mut session_ptr: *mut ort_sys::OrtSession = ptr::null_mut();
 
 		let env = get_environment()?;
+		self.report_load_phase(LoadPhase::RegisteringExecutionProviders);
 		apply_execution_providers(&mut self, &env.execution_providers, "environment")?;
 
-		if env.has_global_threadpool && !self.no_global_thread_pool {
-			ortsys![unsafe DisablePerSessionThreads(self.ptr_mut())?];
-		}
+		self.apply_global_thread_pool(env)?;
+
+		self.report_load_phase(LoadPhase::CreatingSession);
 
 		let model_data = model_bytes.as_ptr().cast::<c_void>();
 		let model_data_length = model_bytes.len();
@@ -206,6 +397,7 @@ impl SessionBuilder {
 		}
 
 		let session_ptr = unsafe { NonNull::new_unchecked(session_ptr) };
+		self.check_initializer_names(session_ptr)?;
 
 		let allocator = match &self.memory_info {
 			Some(info) => {
@@ -217,6 +409,7 @@ impl SessionBuilder {
 		};
 
 		// Extract input and output properties
+		self.report_load_phase(LoadPhase::ReadingMetadata);
 		let num_input_nodes = dangerous::extract_inputs_count(session_ptr)?;
 		let num_output_nodes = dangerous::extract_outputs_count(session_ptr)?;
 		let inputs = (0..num_input_nodes)
@@ -233,6 +426,9 @@ impl SessionBuilder {
 		if let Some(thread_manager) = self.thread_manager.take() {
 			extras.push(Box::new(thread_manager) as Box<dyn Any>);
 		}
+		if !self.initializers.is_empty() {
+			extras.push(Box::new(mem::take(&mut self.initializers)) as Box<dyn Any>);
+		}
 
 		let session = Session {
 			inner: Arc::new(SharedSessionInner {
@@ -241,8 +437,15 @@ impl SessionBuilder {
 				_extras: extras
 			}),
 			inputs,
-			outputs
+			outputs,
+			validate_inputs: self.validate_inputs,
+			captured_graph_shapes: self.validate_gpu_graph_shapes.then(MiniMap::new),
+			profiling: self.profiling,
+			model,
+			#[cfg(feature = "std")]
+			optimized_model_path: self.optimized_model_path.take()
 		};
+		self.report_load_phase(LoadPhase::Finished);
 		Ok(session)
 	}
 }
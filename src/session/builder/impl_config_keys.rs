@@ -1,8 +1,14 @@
+use alloc::vec::Vec;
+
 use super::SessionBuilder;
 use crate::Result;
 
 // https://github.com/microsoft/onnxruntime/blob/main/include/onnxruntime/core/session/onnxruntime_session_options_config_keys.h
 
+const DISABLED_OPTIMIZERS_KEY: &str = "optimization.disable_specified_optimizers";
+/// The name of ONNX Runtime's constant folding graph transformer.
+const CONSTANT_FOLDING_OPTIMIZER: &str = "ConstantFolding";
+
 impl SessionBuilder {
 	/// Enable/disable the usage of prepacking.
 	///
@@ -20,11 +26,22 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
-	/// Enable flush-to-zero and denormal-as-zero.
+	/// Enable/disable flush-to-zero and denormal-as-zero for the session's intra-op & inter-op threads.
+	///
+	/// Arithmetic on denormal (very small, subnormal) floating point numbers is much slower than on normal numbers on
+	/// most CPUs; models which produce many values close to zero, like audio/DSP models, can run many times slower as a
+	/// result. With this option enabled, denormal results are flushed to zero and denormal inputs are treated as zero,
+	/// which can slightly change numerical results near zero.
+	///
+	/// This only applies to the session's own thread pools. If the session uses the environment's global thread pool,
+	/// configure it with [`GlobalThreadPoolOptions::with_flush_to_zero`] instead.
 	///
 	/// This option is **disabled** by default, as it may hurt model accuracy.
-	pub fn with_denormal_as_zero(mut self) -> Result<Self> {
-		self.add_config_entry("session.set_denormal_as_zero", "1")?;
+	///
+	/// [`GlobalThreadPoolOptions::with_flush_to_zero`]: crate::environment::GlobalThreadPoolOptions::with_flush_to_zero
+	#[doc(alias = "with_flush_to_zero")]
+	pub fn with_denormal_as_zero(mut self, enable: bool) -> Result<Self> {
+		self.add_config_entry("session.set_denormal_as_zero", if enable { "1" } else { "0" })?;
 		Ok(self)
 	}
 
@@ -69,8 +86,61 @@ impl SessionBuilder {
 	}
 
 	/// Accepts a comma-separated list of optimizers to disable.
+	///
+	/// This replaces any optimizers previously disabled, including by [`SessionBuilder::with_constant_folding`].
 	pub fn with_disabled_optimizers(mut self, optimizers: &str) -> Result<Self> {
-		self.add_config_entry("optimization.disable_specified_optimizers", optimizers)?;
+		self.add_config_entry(DISABLED_OPTIMIZERS_KEY, optimizers)?;
+		Ok(self)
+	}
+
+	/// Enable/disable constant folding, independently of the [optimization level].
+	///
+	/// Constant folding pre-computes nodes whose inputs are all constant, replacing them with initializers. Since the
+	/// folded nodes are computed on the CPU, results may differ slightly from running them on other execution
+	/// providers; disabling it can help isolate numerical differences between models. Constant folding is a
+	/// [`GraphOptimizationLevel::Level1`] optimization, so this option has no effect if optimizations are disabled.
+	///
+	/// This option is **enabled** by default.
+	///
+	/// To see how many nodes were folded, compare the node counts of the optimized models with & without constant
+	/// folding using [`count_graph_nodes`]:
+	/// ```no_run
+	/// # use ort::session::{Session, builder::count_graph_nodes};
+	/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+	/// let mut counts = [0; 2];
+	/// for (constant_folding, count) in [true, false].into_iter().zip(&mut counts) {
+	/// 	let path = format!("optimized_{constant_folding}.onnx");
+	/// 	let _session = Session::builder()?
+	/// 		.with_constant_folding(constant_folding)?
+	/// 		.with_optimized_model_path(&path)?
+	/// 		.commit_from_file("model.onnx")?;
+	/// 	*count = count_graph_nodes(&std::fs::read(&path)?)?;
+	/// }
+	/// println!("constant folding removed {} nodes", counts[1] - counts[0]);
+	/// # Ok(())
+	/// # }
+	/// ```
+	///
+	/// [optimization level]: SessionBuilder::with_optimization_level
+	/// [`GraphOptimizationLevel::Level1`]: super::GraphOptimizationLevel::Level1
+	/// [`count_graph_nodes`]: super::count_graph_nodes
+	pub fn with_constant_folding(mut self, enable: bool) -> Result<Self> {
+		let current = self.config_entry(DISABLED_OPTIMIZERS_KEY)?;
+		if enable && current.is_none() {
+			return Ok(self);
+		}
+
+		let mut optimizers: Vec<&str> = current
+			.as_deref()
+			.unwrap_or_default()
+			.split(',')
+			.map(str::trim)
+			.filter(|name| !name.is_empty() && *name != CONSTANT_FOLDING_OPTIMIZER)
+			.collect();
+		if !enable {
+			optimizers.push(CONSTANT_FOLDING_OPTIMIZER);
+		}
+		self.add_config_entry(DISABLED_OPTIMIZERS_KEY, &optimizers.join(","))?;
 		Ok(self)
 	}
 
@@ -1,5 +1,5 @@
-use alloc::{rc::Rc, sync::Arc};
-use core::{any::Any, ffi::c_void, ptr};
+use alloc::{format, rc::Rc, string::String, sync::Arc};
+use core::{any::Any, cell::RefCell, ffi::c_void, ptr};
 #[cfg(feature = "std")]
 use std::{borrow::Cow, path::Path};
 
@@ -9,7 +9,7 @@ use crate::util::path_to_os_char;
 use crate::{
 	AsPointer,
 	environment::{self, ThreadManager},
-	error::Result,
+	error::{Error, ErrorCode, Result},
 	execution_providers::{ExecutionProviderDispatch, apply_execution_providers},
 	memory::MemoryInfo,
 	operator::OperatorDomain,
@@ -36,27 +36,54 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Registers the execution providers configured by environment variables, like `ORT_RS_PROVIDER=cuda`. See
+	/// [`execution_providers_from_env`] for the supported variables.
+	///
+	/// ```no_run
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// // ORT_RS_PROVIDER=cuda ORT_RS_CUDA_DEVICE=1 ./server
+	/// let session = Session::builder()?
+	/// 	.with_execution_providers_from_env()?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// [`execution_providers_from_env`]: crate::execution_providers::execution_providers_from_env
+	#[cfg(feature = "std")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+	pub fn with_execution_providers_from_env(self) -> Result<Self> {
+		let execution_providers = crate::execution_providers::execution_providers_from_env()?;
+		self.with_execution_providers(execution_providers)
+	}
+
 	/// Configure the session to use a number of threads to parallelize the execution within nodes. If ONNX Runtime was
 	/// built with OpenMP (as is the case with Microsoft's prebuilt binaries), this will have no effect on the number of
 	/// threads used. Instead, you can configure the number of threads OpenMP uses via the `OMP_NUM_THREADS` environment
 	/// variable.
 	///
+	/// A value of `0` lets ONNX Runtime decide the number of threads, which is typically the number of physical cores.
+	///
 	/// For configuring the number of threads used when the session execution mode is set to `Parallel`, see
 	/// [`SessionBuilder::with_inter_threads()`].
 	pub fn with_intra_threads(mut self, num_threads: usize) -> Result<Self> {
-		ortsys![unsafe SetIntraOpNumThreads(self.ptr_mut(), num_threads as _)?];
+		let num_threads = thread_count(num_threads)?;
+		ortsys![unsafe SetIntraOpNumThreads(self.ptr_mut(), num_threads)?];
 		Ok(self)
 	}
 
 	/// Configure the session to use a number of threads to parallelize the execution of the graph. If nodes can be run
 	/// in parallel, this sets the maximum number of threads to use to run them in parallel.
 	///
-	/// This has no effect when the session execution mode is set to `Sequential`.
+	/// This has no effect when the session execution mode is set to `Sequential`; see
+	/// [`SessionBuilder::with_parallel_execution()`]. A value of `0` lets ONNX Runtime decide the number of threads.
 	///
 	/// For configuring the number of threads used to parallelize the execution within nodes, see
 	/// [`SessionBuilder::with_intra_threads()`].
 	pub fn with_inter_threads(mut self, num_threads: usize) -> Result<Self> {
-		ortsys![unsafe SetInterOpNumThreads(self.ptr_mut(), num_threads as _)?];
+		let num_threads = thread_count(num_threads)?;
+		ortsys![unsafe SetInterOpNumThreads(self.ptr_mut(), num_threads)?];
 		Ok(self)
 	}
 
@@ -85,24 +112,28 @@ impl SessionBuilder {
 	/// After performing optimization (configurable with [`SessionBuilder::with_optimization_level`]), serializes the
 	/// newly optimized model to the given path (for 'offline' graph optimization).
 	///
-	/// Note that the file will only be created after the model is committed.
+	/// Note that the file will only be created after the model is committed. The optimized graph can then be inspected
+	/// with [`Session::graph_summary`](crate::session::Session::graph_summary).
 	#[cfg(feature = "std")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 	pub fn with_optimized_model_path<S: AsRef<Path>>(mut self, path: S) -> Result<Self> {
-		let path = crate::util::path_to_os_char(path);
-		ortsys![unsafe SetOptimizedModelFilePath(self.ptr_mut(), path.as_ptr())?];
+		let os_path = crate::util::path_to_os_char(&path);
+		ortsys![unsafe SetOptimizedModelFilePath(self.ptr_mut(), os_path.as_ptr())?];
+		self.optimized_model_path = Some(path.as_ref().to_path_buf());
 		Ok(self)
 	}
 
 	/// Enables profiling. Profile information will be writen to `profiling_file` after profiling completes.
-	/// See [`Session::end_profiling`].
+	/// See [`Session::end_profiling`], or [`Session::profile`] to end profiling automatically.
 	///
 	/// [`Session::end_profiling`]: crate::session::Session::end_profiling
+	/// [`Session::profile`]: crate::session::Session::profile
 	#[cfg(feature = "std")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 	pub fn with_profiling<S: AsRef<Path>>(mut self, profiling_file: S) -> Result<Self> {
 		let profiling_file = crate::util::path_to_os_char(profiling_file);
 		ortsys![unsafe EnableProfiling(self.ptr_mut(), profiling_file.as_ptr())?];
+		self.profiling = true;
 		Ok(self)
 	}
 
@@ -125,11 +156,27 @@ impl SessionBuilder {
 	}
 
 	/// Registers a custom operator library at the given library path.
+	///
+	/// ONNX Runtime loads the library and keeps it loaded for as long as any session created from this builder is
+	/// alive. This can be called multiple times to register multiple libraries.
+	///
+	/// ```no_run
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_operator_library("libortextensions.so")?
+	/// 	.with_operator_library("libmy_ops.so")?
+	/// 	.commit_from_file("model.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
 	#[cfg(feature = "std")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 	pub fn with_operator_library(mut self, lib_path: impl AsRef<Path>) -> Result<Self> {
+		let lib_path = lib_path.as_ref();
 		let path_cstr = path_to_os_char(lib_path);
-		ortsys![unsafe RegisterCustomOpsLibrary_V2(self.ptr_mut(), path_cstr.as_ptr())?];
+		unsafe { crate::error::status_to_result(ortsys![RegisterCustomOpsLibrary_V2](self.ptr_mut(), path_cstr.as_ptr())) }
+			.map_err(|e| Error::new_with_code(e.code(), format!("Failed to register operator library at `{}`: {e}", lib_path.display())))?;
 		Ok(self)
 	}
 
@@ -166,6 +213,39 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Overrides the value of the initializer `name` in the model, e.g. to change a constant threshold per deployment
+	/// without re-exporting the model.
+	///
+	/// Only initializers ONNX Runtime considers overridable can be replaced: in models with IR version 4 or later,
+	/// these are the initializers also listed in the graph's inputs; in older models, all initializers are overridable.
+	/// Committing the session returns an error with [`ErrorCode::InvalidArgument`] if the session has no overridable
+	/// initializer with this name.
+	///
+	/// The value must have the same type & shape as the initializer it replaces, and must be accessible by the device
+	/// the initializer's consumers run on. The session holds onto `value` for its lifetime, and ONNX Runtime uses its
+	/// memory directly, so modifying the value after the session is committed is not supported.
+	///
+	/// ```no_run
+	/// # use ort::{session::Session, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let threshold = Tensor::from_array(([0usize; 0], vec![0.8_f32]))?;
+	/// let session = Session::builder()?
+	/// 	.with_initializer("score_threshold", threshold)?
+	/// 	.commit_from_file("detector.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_initializer(mut self, name: impl AsRef<str>, value: impl Into<DynValue>) -> Result<Self> {
+		let ptr = self.ptr_mut();
+		let value = Arc::new(value.into());
+		with_cstr(name.as_ref().as_bytes(), &|name| {
+			ortsys![unsafe AddInitializer(ptr, name.as_ptr(), value.ptr())?];
+			Ok(())
+		})?;
+		self.initializers.push((String::from(name.as_ref()), value));
+		Ok(self)
+	}
+
 	#[cfg(feature = "std")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 	pub fn with_external_initializer_file_in_memory(mut self, file_name: impl AsRef<Path>, buffer: Cow<'static, [u8]>) -> Result<Self> {
@@ -203,15 +283,98 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Shares a [`PrepackedWeights`] container with this session, allowing multiple sessions created from the same
+	/// model to reuse one copy of their prepacked weights rather than each prepacking their own.
+	///
+	/// ```no_run
+	/// # use ort::session::{Session, builder::PrepackedWeights};
+	/// # fn main() -> ort::Result<()> {
+	/// let weights = PrepackedWeights::new();
+	/// let session_a = Session::builder()?.with_prepacked_weights(&weights)?.commit_from_file("model.onnx")?;
+	/// let session_b = Session::builder()?.with_prepacked_weights(&weights)?.commit_from_file("model.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
 	pub fn with_prepacked_weights(mut self, weights: &PrepackedWeights) -> Result<Self> {
 		self.prepacked_weights = Some(weights.clone());
 		Ok(self)
 	}
 
+	/// Enables/disables validation of input tensors before each run. Disabled by default.
+	///
+	/// When enabled, each input tensor's element type and shape are checked against the model's declared inputs before
+	/// running (symbolic dimensions match any size), producing an error naming the input and its expected & actual
+	/// shapes instead of ONNX Runtime's less descriptive message.
+	///
+	/// ```
+	/// # use ort::{session::Session, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?
+	/// 	.with_input_validation(true)?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = Tensor::from_array(([1usize, 3], vec![0.0_f32; 3]))?;
+	/// assert!(session.run(ort::inputs![input]).is_err());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_input_validation(mut self, enable: bool) -> Result<Self> {
+		self.validate_inputs = enable;
+		Ok(self)
+	}
+
+	/// Enables/disables keeping a copy of the serialized model in the committed session. Disabled by default.
+	///
+	/// When enabled, the session holds a reference-counted copy of the model for
+	/// [`SessionBuilder::commit_from_session_model`], so sessions built from it share that copy instead of reading the
+	/// model file again, and always use exactly the same model even if the file changes. This is also required to use
+	/// [`SessionBuilder::commit_from_session_model`] with sessions committed with
+	/// [`SessionBuilder::commit_from_memory`]. Note that the copy is kept for the session's whole lifetime, in addition
+	/// to the memory ONNX Runtime uses for the loaded model. Sessions committed with
+	/// [`SessionBuilder::commit_from_memory_directly`] never keep a copy.
+	pub fn with_retained_model(mut self, enable: bool) -> Result<Self> {
+		self.retain_model = enable;
+		Ok(self)
+	}
+
 	/// Configures this environment to use its own thread pool instead of defaulting to the
 	/// [`Environment`](crate::environment::Environment)'s global thread pool if one was defined.
 	pub fn with_independent_thread_pool(mut self) -> Result<Self> {
 		self.no_global_thread_pool = true;
+		self.require_global_thread_pool = false;
+		Ok(self)
+	}
+
+	/// Requires this session to use the [`Environment`](crate::environment::Environment)'s global thread pool instead
+	/// of creating its own intra-op & inter-op thread pools.
+	///
+	/// Sessions already share the global thread pool by default if the environment was configured with one. The
+	/// difference is that with this option, committing the session returns an error with
+	/// [`ErrorCode::InvalidArgument`] if the environment has *no* global
+	/// thread pool, rather than silently creating per-session threads. This catches the case where a session is
+	/// created before [`ort::init`](crate::init) is committed, which would cause a default environment without a global
+	/// thread pool to be created instead.
+	///
+	/// Sharing one thread pool among many sessions avoids oversubscribing the CPU with `N` sessions &times; `M`
+	/// threads each. Note that per-session thread options like [`SessionBuilder::with_intra_threads`] have no effect
+	/// when the global thread pool is in use; configure the pool via
+	/// [`GlobalThreadPoolOptions`](crate::environment::GlobalThreadPoolOptions) instead.
+	///
+	/// ```no_run
+	/// # use ort::{environment::GlobalThreadPoolOptions, session::Session};
+	/// # fn main() -> ort::Result<()> {
+	/// ort::init()
+	/// 	.with_global_thread_pool(GlobalThreadPoolOptions::default().with_intra_threads(4)?.with_inter_threads(1)?)
+	/// 	.commit()?;
+	///
+	/// // Both sessions run on the same 4 intra-op threads.
+	/// let encoder = Session::builder()?.with_disable_per_session_threads()?.commit_from_file("encoder.onnx")?;
+	/// let decoder = Session::builder()?.with_disable_per_session_threads()?.commit_from_file("decoder.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_disable_per_session_threads(mut self) -> Result<Self> {
+		self.no_global_thread_pool = false;
+		self.require_global_thread_pool = true;
 		Ok(self)
 	}
 
@@ -223,6 +386,54 @@ impl SessionBuilder {
 		self.thread_manager = Some(manager as Rc<dyn Any>);
 		Ok(self)
 	}
+
+	/// Registers a callback which is invoked as the session is being committed, once at the start of each
+	/// [`LoadPhase`], and finally with [`LoadPhase::Finished`] once the session is ready.
+	///
+	/// ONNX Runtime does not report its own progress, so parsing the model, optimizing the graph, and initializing
+	/// execution providers are all covered by a single [`LoadPhase::CreatingSession`] phase. This is still enough to
+	/// tell whether a slow commit is stuck in ONNX Runtime or elsewhere, and to measure how long each phase takes.
+	///
+	/// ```
+	/// # use std::time::Instant;
+	/// # use ort::session::{builder::LoadPhase, Session};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut last = Instant::now();
+	/// let session = Session::builder()?
+	/// 	.with_load_callback(move |phase| {
+	/// 		println!("{phase:?} (previous phase took {:?})", last.elapsed());
+	/// 		last = Instant::now();
+	/// 	})?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_load_callback(mut self, callback: impl FnMut(LoadPhase) + 'static) -> Result<Self> {
+		self.load_callback = Some(Rc::new(RefCell::new(callback)));
+		Ok(self)
+	}
+}
+
+/// A step in committing a [`SessionBuilder`] into a session, as reported to the callback registered with
+/// [`SessionBuilder::with_load_callback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LoadPhase {
+	/// Execution providers configured on the [`Environment`](crate::environment::Environment) are being registered.
+	RegisteringExecutionProviders,
+	/// ONNX Runtime is creating the session; this includes parsing the model, optimizing the graph, and initializing
+	/// execution providers, and is usually by far the longest phase.
+	CreatingSession,
+	/// The session's inputs & outputs are being read.
+	ReadingMetadata,
+	/// The session has been committed.
+	Finished
+}
+
+fn thread_count(num_threads: usize) -> Result<ort_sys::c_int> {
+	ort_sys::c_int::try_from(num_threads).map_err(|_| {
+		Error::new_with_code(ErrorCode::InvalidArgument, format!("Thread count {num_threads} is too large (must be at most {})", ort_sys::c_int::MAX))
+	})
 }
 
 /// ONNX Runtime provides various graph optimizations to improve performance. Graph optimizations are essentially
@@ -325,6 +536,12 @@ impl Drop for PrepackedWeightsInner {
 	}
 }
 
+/// A container for weights prepacked by ONNX Runtime kernels, which can be shared between sessions via
+/// [`SessionBuilder::with_prepacked_weights`].
+///
+/// ONNX Runtime requires the container to outlive every session using it. `PrepackedWeights` is reference counted, and
+/// each session holds its own reference, so the container is only released once it has been dropped along with all
+/// sessions that use it.
 #[derive(Debug, Clone)]
 pub struct PrepackedWeights {
 	inner: Arc<PrepackedWeightsInner>
@@ -1,6 +1,7 @@
-use alloc::{borrow::Cow, rc::Rc, sync::Arc, vec::Vec};
+use alloc::{borrow::Cow, rc::Rc, string::String, sync::Arc, vec, vec::Vec};
 use core::{
 	any::Any,
+	cell::RefCell,
 	ptr::{self, NonNull}
 };
 
@@ -9,8 +10,14 @@ use crate::{AsPointer, error::Result, memory::MemoryInfo, operator::OperatorDoma
 mod impl_commit;
 mod impl_config_keys;
 mod impl_options;
+mod node_count;
 
-pub use self::impl_options::{GraphOptimizationLevel, PrepackedWeights};
+pub use self::{
+	impl_options::{GraphOptimizationLevel, LoadPhase, PrepackedWeights},
+	node_count::count_graph_nodes
+};
+
+type LoadCallback = Rc<RefCell<dyn FnMut(LoadPhase)>>;
 
 /// Creates a session using the builder pattern.
 ///
@@ -35,10 +42,19 @@ pub struct SessionBuilder {
 	memory_info: Option<Rc<MemoryInfo>>,
 	operator_domains: Vec<Arc<OperatorDomain>>,
 	external_initializers: Vec<Rc<DynValue>>,
+	initializers: Vec<(String, Arc<DynValue>)>,
 	external_initializer_buffers: Vec<Cow<'static, [u8]>>,
 	prepacked_weights: Option<PrepackedWeights>,
 	thread_manager: Option<Rc<dyn Any>>,
-	no_global_thread_pool: bool
+	load_callback: Option<LoadCallback>,
+	no_global_thread_pool: bool,
+	require_global_thread_pool: bool,
+	validate_inputs: bool,
+	validate_gpu_graph_shapes: bool,
+	profiling: bool,
+	retain_model: bool,
+	#[cfg(feature = "std")]
+	optimized_model_path: Option<std::path::PathBuf>
 }
 
 impl Clone for SessionBuilder {
@@ -54,10 +70,19 @@ impl Clone for SessionBuilder {
 			memory_info: self.memory_info.clone(),
 			operator_domains: self.operator_domains.clone(),
 			external_initializers: self.external_initializers.clone(),
+			initializers: self.initializers.clone(),
 			external_initializer_buffers: self.external_initializer_buffers.clone(),
 			prepacked_weights: self.prepacked_weights.clone(),
 			thread_manager: self.thread_manager.clone(),
-			no_global_thread_pool: self.no_global_thread_pool
+			load_callback: self.load_callback.clone(),
+			no_global_thread_pool: self.no_global_thread_pool,
+			require_global_thread_pool: self.require_global_thread_pool,
+			validate_inputs: self.validate_inputs,
+			validate_gpu_graph_shapes: self.validate_gpu_graph_shapes,
+			profiling: self.profiling,
+			retain_model: self.retain_model,
+			#[cfg(feature = "std")]
+			optimized_model_path: self.optimized_model_path.clone()
 		}
 	}
 }
@@ -90,13 +115,29 @@ impl SessionBuilder {
 			memory_info: None,
 			operator_domains: Vec::new(),
 			external_initializers: Vec::new(),
+			initializers: Vec::new(),
 			external_initializer_buffers: Vec::new(),
 			prepacked_weights: None,
 			thread_manager: None,
-			no_global_thread_pool: false
+			load_callback: None,
+			no_global_thread_pool: false,
+			require_global_thread_pool: false,
+			validate_inputs: false,
+			validate_gpu_graph_shapes: false,
+			profiling: false,
+			retain_model: false,
+			#[cfg(feature = "std")]
+			optimized_model_path: None
 		})
 	}
 
+	/// Marks that an execution provider capturing GPU graphs (i.e. CUDA graphs) was registered, so the session should
+	/// check that each captured graph is replayed with the shapes it was captured with.
+	#[allow(unused)]
+	pub(crate) fn enable_gpu_graph_shape_validation(&mut self) {
+		self.validate_gpu_graph_shapes = true;
+	}
+
 	pub(crate) fn add_config_entry(&mut self, key: &str, value: &str) -> Result<()> {
 		let ptr = self.ptr_mut();
 		with_cstr(key.as_bytes(), &|key| {
@@ -107,6 +148,25 @@ impl SessionBuilder {
 		})
 	}
 
+	pub(crate) fn config_entry(&self, key: &str) -> Result<Option<String>> {
+		let ptr = self.ptr();
+		with_cstr(key.as_bytes(), &|key| {
+			let mut has_entry = 0;
+			ortsys![unsafe HasSessionConfigEntry(ptr, key.as_ptr(), &mut has_entry)?];
+			if has_entry == 0 {
+				return Ok(None);
+			}
+
+			let mut len = 0;
+			ortsys![unsafe GetSessionConfigEntry(ptr, key.as_ptr(), ptr::null_mut(), &mut len)?];
+			let mut value = vec![0u8; len];
+			ortsys![unsafe GetSessionConfigEntry(ptr, key.as_ptr(), value.as_mut_ptr().cast(), &mut len)?];
+			// `len` includes the null terminator.
+			value.truncate(len.saturating_sub(1));
+			Ok(Some(String::from_utf8_lossy(&value).into_owned()))
+		})
+	}
+
 	/// Adds a custom configuration entry to the session.
 	pub fn with_config_entry(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Result<Self> {
 		self.add_config_entry(key.as_ref(), value.as_ref())?;
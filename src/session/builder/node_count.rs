@@ -0,0 +1,49 @@
+use crate::{
+	Result,
+	session::graph::{GRAPH_NODE_FIELD, MODEL_GRAPH_FIELD, for_each_field}
+};
+
+/// Counts the nodes in the main graph of a serialized ONNX model. Nodes in subgraphs (i.e. the bodies of `If` or
+/// `Loop` nodes) are not counted.
+///
+/// Together with [`SessionBuilder::with_optimized_model_path`], this can be used to measure the effect of a single
+/// optimization pass, like constant folding, by comparing the node counts of the models optimized with & without it.
+/// See [`SessionBuilder::with_constant_folding`] for an example.
+///
+/// Returns an error with [`ErrorCode::InvalidArgument`](crate::error::ErrorCode::InvalidArgument) if `model` is not a valid ONNX model. Models in the ORT format
+/// are not supported.
+///
+/// [`SessionBuilder::with_optimized_model_path`]: super::SessionBuilder::with_optimized_model_path
+/// [`SessionBuilder::with_constant_folding`]: super::SessionBuilder::with_constant_folding
+pub fn count_graph_nodes(model: &[u8]) -> Result<usize> {
+	let mut nodes = 0;
+	for_each_field(model, &mut |field, value| {
+		if let (MODEL_GRAPH_FIELD, Some(graph)) = (field, value) {
+			for_each_field(graph, &mut |field, value| {
+				if let (GRAPH_NODE_FIELD, Some(_)) = (field, value) {
+					nodes += 1;
+				}
+				Ok(())
+			})?;
+		}
+		Ok(())
+	})?;
+	Ok(nodes)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::count_graph_nodes;
+
+	#[test]
+	fn test_count_graph_nodes() -> crate::Result<()> {
+		// ModelProto { ir_version: 8, graph: GraphProto { node: [{}, { input: ["a"] }], name: "g" } }
+		let model = [0x08, 0x08, 0x3a, 0x0a, 0x0a, 0x00, 0x0a, 0x03, 0x0a, 0x01, b'a', 0x12, 0x01, b'g'];
+		assert_eq!(count_graph_nodes(&model)?, 2);
+		assert_eq!(count_graph_nodes(&[])?, 0);
+		assert_eq!(count_graph_nodes(include_bytes!("../../../tests/data/upsample.onnx"))?, 11);
+
+		assert!(count_graph_nodes(&model[..model.len() - 1]).is_err());
+		Ok(())
+	}
+}
@@ -0,0 +1,249 @@
+//! A coarse, read-only view of a model's graph. See [`GraphSummary`].
+
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use core::fmt;
+
+use crate::{Error, ErrorCode, Result};
+
+/// `ModelProto.graph`
+pub(crate) const MODEL_GRAPH_FIELD: u64 = 7;
+/// `GraphProto.node`
+pub(crate) const GRAPH_NODE_FIELD: u64 = 1;
+/// `GraphProto.name`
+const GRAPH_NAME_FIELD: u64 = 2;
+/// `GraphProto.input`
+const GRAPH_INPUT_FIELD: u64 = 11;
+/// `GraphProto.output`
+const GRAPH_OUTPUT_FIELD: u64 = 12;
+/// `ValueInfoProto.name`
+const VALUE_INFO_NAME_FIELD: u64 = 1;
+/// `NodeProto.input`
+const NODE_INPUT_FIELD: u64 = 1;
+/// `NodeProto.output`
+const NODE_OUTPUT_FIELD: u64 = 2;
+/// `NodeProto.name`
+const NODE_NAME_FIELD: u64 = 3;
+/// `NodeProto.op_type`
+const NODE_OP_TYPE_FIELD: u64 = 4;
+/// `NodeProto.domain`
+const NODE_DOMAIN_FIELD: u64 = 7;
+
+/// A summary of the nodes in the main graph of an ONNX model, and how they are connected. Returned by
+/// [`Session::graph_summary`](super::Session::graph_summary).
+///
+/// Nodes in subgraphs (i.e. the bodies of `If` or `Loop` nodes) are not included.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GraphSummary {
+	/// The name of the graph.
+	pub name: String,
+	/// The names of the graph's inputs. Depending on the model's IR version, this may include initializers.
+	pub inputs: Vec<String>,
+	/// The names of the graph's outputs.
+	pub outputs: Vec<String>,
+	/// The graph's nodes, in topological order.
+	pub nodes: Vec<NodeSummary>
+}
+
+/// A single node in a [`GraphSummary`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NodeSummary {
+	/// The name of the node. Names are optional in ONNX, so this may be empty.
+	pub name: String,
+	/// The operator this node executes, e.g. `Conv`.
+	pub op_type: String,
+	/// The domain of the operator; empty for the default ONNX domain. Nodes fused by ONNX Runtime's optimizers
+	/// typically use the `com.microsoft` domain.
+	pub domain: String,
+	/// The names of the values this node consumes. Omitted optional inputs are empty strings.
+	pub inputs: Vec<String>,
+	/// The names of the values this node produces.
+	pub outputs: Vec<String>
+}
+
+impl GraphSummary {
+	/// Parses the main graph of a serialized ONNX model.
+	///
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if `model` is not a valid ONNX model. Models in the ORT
+	/// format are not supported.
+	pub fn from_model(model: &[u8]) -> Result<Self> {
+		let mut summary = GraphSummary::default();
+		for_each_field(model, &mut |field, value| {
+			if let (MODEL_GRAPH_FIELD, Some(graph)) = (field, value) {
+				parse_graph(graph, &mut summary)?;
+			}
+			Ok(())
+		})?;
+		Ok(summary)
+	}
+
+	/// Returns the number of nodes executing each operator, keyed by operator type; operators outside the default ONNX
+	/// domain are prefixed by their domain, e.g. `com.microsoft.FusedConv`.
+	pub fn op_type_counts(&self) -> BTreeMap<String, usize> {
+		let mut counts = BTreeMap::new();
+		for node in &self.nodes {
+			*counts.entry(node.qualified_op_type()).or_insert(0) += 1;
+		}
+		counts
+	}
+}
+
+impl NodeSummary {
+	fn qualified_op_type(&self) -> String {
+		if self.domain.is_empty() || self.domain == "ai.onnx" {
+			self.op_type.clone()
+		} else {
+			format!("{}.{}", self.domain, self.op_type)
+		}
+	}
+}
+
+impl fmt::Display for GraphSummary {
+	/// Formats the graph with one line per node, e.g. `conv_1: Conv(input, weight) -> (conv_1_out)`.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "graph {}({}) -> ({})", self.name, self.inputs.join(", "), self.outputs.join(", "))?;
+		for node in &self.nodes {
+			writeln!(f, "  {}: {}({}) -> ({})", node.name, node.qualified_op_type(), node.inputs.join(", "), node.outputs.join(", "))?;
+		}
+		Ok(())
+	}
+}
+
+fn parse_graph(graph: &[u8], summary: &mut GraphSummary) -> Result<()> {
+	for_each_field(graph, &mut |field, value| {
+		let Some(value) = value else {
+			return Ok(());
+		};
+		match field {
+			GRAPH_NODE_FIELD => summary.nodes.push(parse_node(value)?),
+			GRAPH_NAME_FIELD => summary.name = string(value)?,
+			GRAPH_INPUT_FIELD => summary.inputs.push(value_info_name(value)?),
+			GRAPH_OUTPUT_FIELD => summary.outputs.push(value_info_name(value)?),
+			_ => {}
+		}
+		Ok(())
+	})
+}
+
+fn parse_node(node: &[u8]) -> Result<NodeSummary> {
+	let mut summary = NodeSummary::default();
+	for_each_field(node, &mut |field, value| {
+		let Some(value) = value else {
+			return Ok(());
+		};
+		match field {
+			NODE_INPUT_FIELD => summary.inputs.push(string(value)?),
+			NODE_OUTPUT_FIELD => summary.outputs.push(string(value)?),
+			NODE_NAME_FIELD => summary.name = string(value)?,
+			NODE_OP_TYPE_FIELD => summary.op_type = string(value)?,
+			NODE_DOMAIN_FIELD => summary.domain = string(value)?,
+			_ => {}
+		}
+		Ok(())
+	})?;
+	Ok(summary)
+}
+
+fn value_info_name(value_info: &[u8]) -> Result<String> {
+	let mut name = String::new();
+	for_each_field(value_info, &mut |field, value| {
+		if let (VALUE_INFO_NAME_FIELD, Some(value)) = (field, value) {
+			name = string(value)?;
+		}
+		Ok(())
+	})?;
+	Ok(name)
+}
+
+fn string(value: &[u8]) -> Result<String> {
+	String::from_utf8(value.to_vec()).map_err(|_| malformed("string is not valid UTF-8"))
+}
+
+/// Calls `f` with the field number of each field in a serialized protobuf message, along with the field's contents if
+/// it is length-delimited.
+pub(crate) fn for_each_field<'b>(mut message: &'b [u8], f: &mut dyn FnMut(u64, Option<&'b [u8]>) -> Result<()>) -> Result<()> {
+	while !message.is_empty() {
+		let tag = read_varint(&mut message)?;
+		let value = match tag & 0b111 {
+			// varint
+			0 => {
+				read_varint(&mut message)?;
+				None
+			}
+			// 64-bit
+			1 => {
+				take(&mut message, 8)?;
+				None
+			}
+			// length-delimited
+			2 => {
+				let len = read_varint(&mut message)?;
+				Some(take(&mut message, usize::try_from(len).map_err(|_| malformed("length out of range"))?)?)
+			}
+			// 32-bit
+			5 => {
+				take(&mut message, 4)?;
+				None
+			}
+			wire_type => return Err(malformed(&format!("unsupported wire type {wire_type}")))
+		};
+		f(tag >> 3, value)?;
+	}
+	Ok(())
+}
+
+fn read_varint(input: &mut &[u8]) -> Result<u64> {
+	let mut value = 0u64;
+	for shift in (0..64).step_by(7) {
+		let (&byte, rest) = input.split_first().ok_or_else(|| malformed("unexpected end of data"))?;
+		*input = rest;
+		value |= u64::from(byte & 0x7f) << shift;
+		if byte & 0x80 == 0 {
+			return Ok(value);
+		}
+	}
+	Err(malformed("varint too long"))
+}
+
+fn take<'b>(input: &mut &'b [u8], len: usize) -> Result<&'b [u8]> {
+	if input.len() < len {
+		return Err(malformed("unexpected end of data"));
+	}
+	let (value, rest) = input.split_at(len);
+	*input = rest;
+	Ok(value)
+}
+
+fn malformed(reason: &str) -> Error {
+	Error::new_with_code(ErrorCode::InvalidArgument, format!("Malformed ONNX model: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::GraphSummary;
+
+	#[test]
+	fn test_graph_summary() -> crate::Result<()> {
+		// ModelProto { graph: GraphProto {
+		// 	node: [{ input: ["x"], output: ["y"], name: "n", op_type: "Relu" }],
+		// 	name: "g", input: [{ name: "x" }], output: [{ name: "y" }]
+		// } }
+		let model = [
+			0x3a, 0x1e, 0x0a, 0x0f, 0x0a, 0x01, b'x', 0x12, 0x01, b'y', 0x1a, 0x01, b'n', 0x22, 0x04, b'R', b'e', b'l', b'u', 0x12, 0x01, b'g', 0x5a, 0x03,
+			0x0a, 0x01, b'x', 0x62, 0x03, 0x0a, 0x01, b'y'
+		];
+		let summary = GraphSummary::from_model(&model)?;
+		assert_eq!(summary.name, "g");
+		assert_eq!(summary.inputs, ["x"]);
+		assert_eq!(summary.outputs, ["y"]);
+		assert_eq!(summary.nodes.len(), 1);
+		assert_eq!(summary.nodes[0].op_type, "Relu");
+		assert_eq!(summary.nodes[0].inputs, ["x"]);
+		assert_eq!(summary.to_string(), "graph g(x) -> (y)\n  n: Relu(x) -> (y)\n");
+
+		let summary = GraphSummary::from_model(include_bytes!("../../tests/data/upsample.onnx"))?;
+		assert_eq!(summary.op_type_counts().values().sum::<usize>(), 11);
+
+		assert!(GraphSummary::from_model(&model[..model.len() - 1]).is_err());
+		Ok(())
+	}
+}
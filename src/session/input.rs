@@ -44,6 +44,10 @@ impl<'v, T: ValueTypeMarker + ?Sized> From<&'v Value<T>> for SessionInputValue<'
 
 /// The inputs to a [`Session::run`] call.
 ///
+/// Graph inputs with an [optional type](crate::value::ValueType::Optional) may be omitted from a
+/// [`SessionInputs::ValueMap`]; all other inputs are required, and leaving one unbound will cause [`Session::run`] to
+/// return an error naming the missing input(s).
+///
 /// [`Session::run`]: crate::session::Session::run
 pub enum SessionInputs<'i, 'v, const N: usize = 0> {
 	ValueMap(Vec<(Cow<'i, str>, SessionInputValue<'v>)>),
@@ -65,6 +69,26 @@ impl<'i, 'v, K: Into<Cow<'i, str>>, V: Into<SessionInputValue<'v>>> From<Vec<(K,
 	}
 }
 
+/// Inputs given as `None` are not bound, allowing ONNX Runtime to apply its default behavior for optional inputs.
+///
+/// ```no_run
+/// # use ort::{session::{Session, SessionInputs}, value::Tensor};
+/// # fn main() -> ort::Result<()> {
+/// # 	let mut session = Session::builder()?.commit_from_file("model.onnx")?;
+/// let attention_mask: Option<Tensor<i64>> = None;
+/// let _ = session.run(SessionInputs::from(vec![
+/// 	("input_ids", Some(Tensor::from_array(([5], vec![1_i64, 2, 3, 4, 5]))?)),
+/// 	("attention_mask", attention_mask),
+/// ]))?;
+/// # 	Ok(())
+/// # }
+/// ```
+impl<'i, 'v, K: Into<Cow<'i, str>>, V: Into<SessionInputValue<'v>>> From<Vec<(K, Option<V>)>> for SessionInputs<'i, 'v> {
+	fn from(val: Vec<(K, Option<V>)>) -> Self {
+		SessionInputs::ValueMap(val.into_iter().filter_map(|(k, v)| v.map(|v| (k.into(), v.into()))).collect())
+	}
+}
+
 impl<'i, 'v> From<&'i [SessionInputValue<'v>]> for SessionInputs<'i, 'v> {
 	fn from(val: &'i [SessionInputValue<'v>]) -> Self {
 		SessionInputs::ValueSlice(val)
@@ -108,22 +132,140 @@ impl<'v, const N: usize> From<[SessionInputValue<'v>; N]> for SessionInputs<'_,
 /// # 	Ok(())
 /// # }
 /// ```
+///
+/// When all input names are string literals, passing the same name twice is a compile-time error. For full
+/// compile-time checking of a model's inputs, see [`model_inputs!`](crate::model_inputs).
 #[macro_export]
 macro_rules! inputs {
 	($($v:expr),+ $(,)?) => (
 		[$($crate::__private::core::convert::Into::<$crate::session::SessionInputValue<'_>>::into($v)),+]
 	);
+	($($n:literal => $v:expr),+ $(,)?) => ({
+		const _: () = assert!(!$crate::__private::has_duplicate_names(&[$($n),+]), "duplicate input names in `ort::inputs!`");
+		vec![$(($crate::__private::alloc::borrow::Cow::<str>::from($n), $crate::session::SessionInputValue::<'_>::from($v)),)+]
+	});
 	($($n:expr => $v:expr),+ $(,)?) => (
 		vec![$(($crate::__private::alloc::borrow::Cow::<str>::from($n), $crate::session::SessionInputValue::<'_>::from($v)),)+]
 	);
 }
 
+/// Defines a struct describing a model's inputs, which can be passed directly to [`Session::run`].
+///
+/// Compared to [`inputs!`], this catches a whole class of mistakes at compile time: forgetting an input is a missing
+/// field error, and passing a value of the wrong type is a type error. Each field is bound to the model input of the
+/// same name; use `field as "name": Type` to bind a field to an input whose name isn't a valid Rust identifier.
+///
+/// ```no_run
+/// # use ort::{session::Session, value::{Tensor, TensorRef}};
+/// ort::model_inputs! {
+/// 	/// Inputs to a BERT-like text encoder.
+/// 	pub struct EncoderInputs<'v> {
+/// 		input_ids: Tensor<i64>,
+/// 		attention_mask as "attention_mask:0": TensorRef<'v, i64>
+/// 	}
+/// }
+///
+/// # fn main() -> ort::Result<()> {
+/// let mut session = Session::builder()?.commit_from_file("encoder.onnx")?;
+/// let mask = vec![1_i64; 4];
+/// let outputs = session.run(EncoderInputs {
+/// 	input_ids: Tensor::from_array(([1usize, 4], vec![101_i64, 7592, 2088, 102]))?,
+/// 	attention_mask: TensorRef::from_array_view(([1usize, 4], &*mask))?
+/// })?;
+/// # 	Ok(())
+/// # }
+/// ```
+///
+/// Note that the struct describes only the *Rust* side of the inputs; whether the model actually has inputs with
+/// these names & types is still checked by ONNX Runtime when the session is run (or earlier, by
+/// [`Session::assert_signature`]).
+///
+/// [`Session::run`]: crate::session::Session::run
+/// [`Session::assert_signature`]: crate::session::Session::assert_signature
+#[macro_export]
+macro_rules! model_inputs {
+	(
+		$(#[$meta:meta])*
+		$vis:vis struct $name:ident $(<$lt:lifetime>)? {
+			$($(#[$field_meta:meta])* $field:ident $(as $input_name:literal)?: $ty:ty),+ $(,)?
+		}
+	) => {
+		$(#[$meta])*
+		$vis struct $name $(<$lt>)? {
+			$($(#[$field_meta])* pub $field: $ty),+
+		}
+
+		const _: () = assert!(
+			!$crate::__private::has_duplicate_names(&[$($crate::__model_input_name!($field $(, $input_name)?)),+]),
+			concat!("duplicate input names in `", stringify!($name), "`")
+		);
+
+		impl<'__i, '__v: '__i $(, $lt: '__v)?> ::core::convert::From<$name $(<$lt>)?> for $crate::session::SessionInputs<'__i, '__v>
+		where
+			$($ty: ::core::convert::Into<$crate::session::SessionInputValue<'__v>>),+
+		{
+			fn from(inputs: $name $(<$lt>)?) -> Self {
+				$crate::session::SessionInputs::ValueMap(vec![
+					$((
+						$crate::__private::alloc::borrow::Cow::Borrowed($crate::__model_input_name!($field $(, $input_name)?)),
+						::core::convert::Into::into(inputs.$field)
+					)),+
+				])
+			}
+		}
+	};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __model_input_name {
+	($field:ident) => {
+		stringify!($field)
+	};
+	($field:ident, $input_name:literal) => {
+		$input_name
+	};
+}
+
+/// Returns `true` if any name appears more than once in `names`. Used by [`inputs!`] and [`model_inputs!`] to reject
+/// duplicate input names at compile time.
+#[doc(hidden)]
+pub const fn has_duplicate_names(names: &[&str]) -> bool {
+	const fn str_eq(a: &str, b: &str) -> bool {
+		let (a, b) = (a.as_bytes(), b.as_bytes());
+		if a.len() != b.len() {
+			return false;
+		}
+		let mut i = 0;
+		while i < a.len() {
+			if a[i] != b[i] {
+				return false;
+			}
+			i += 1;
+		}
+		true
+	}
+
+	let mut i = 0;
+	while i < names.len() {
+		let mut j = i + 1;
+		while j < names.len() {
+			if str_eq(names[i], names[j]) {
+				return true;
+			}
+			j += 1;
+		}
+		i += 1;
+	}
+	false
+}
+
 #[cfg(test)]
 mod tests {
 	use std::collections::HashMap;
 
-	use super::SessionInputs;
-	use crate::value::{DynTensor, Tensor};
+	use super::{SessionInputs, has_duplicate_names};
+	use crate::value::{DynTensor, Tensor, TensorRef};
 
 	#[test]
 	#[cfg(feature = "std")]
@@ -150,4 +292,50 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_vec_optional_inputs() -> crate::Result<()> {
+		let v: Vec<f32> = vec![1., 2., 3., 4., 5.];
+		let shape = vec![v.len() as i64];
+
+		let inputs: Vec<(&str, Option<DynTensor>)> = vec![("a", Some(Tensor::from_array((shape, v))?.upcast())), ("b", None)];
+		let SessionInputs::ValueMap(inputs) = SessionInputs::from(inputs) else {
+			unreachable!();
+		};
+		assert_eq!(inputs.len(), 1);
+		assert_eq!(inputs[0].0, "a");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_has_duplicate_names() {
+		assert!(!has_duplicate_names(&["a", "b", "ab"]));
+		assert!(has_duplicate_names(&["a", "b", "a"]));
+		assert!(!has_duplicate_names(&[]));
+	}
+
+	crate::model_inputs! {
+		struct TestInputs<'v> {
+			ids: Tensor<i64>,
+			mask as "attention_mask": TensorRef<'v, i64>
+		}
+	}
+
+	#[test]
+	fn test_model_inputs() -> crate::Result<()> {
+		let mask = vec![1_i64, 1];
+		let inputs = TestInputs {
+			ids: Tensor::from_array(([2usize], vec![5_i64, 6]))?,
+			mask: TensorRef::from_array_view(([2usize], &*mask))?
+		};
+		let SessionInputs::ValueMap(inputs) = SessionInputs::from(inputs) else {
+			unreachable!();
+		};
+		assert_eq!(inputs.len(), 2);
+		assert_eq!(inputs[0].0, "ids");
+		assert_eq!(inputs[1].0, "attention_mask");
+
+		Ok(())
+	}
 }
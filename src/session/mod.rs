@@ -10,11 +10,18 @@
 //! # }
 //! ```
 
-use alloc::{boxed::Box, format, string::String, sync::Arc, vec::Vec};
+use alloc::{
+	boxed::Box,
+	format,
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec
+};
 use core::{
 	any::Any,
 	ffi::{CStr, c_char},
-	iter,
+	fmt,
+	iter::{self, FusedIterator},
 	marker::PhantomData,
 	ops::{Deref, DerefMut},
 	ptr::{self, NonNull},
@@ -32,24 +39,37 @@ use crate::{
 	memory::Allocator,
 	metadata::ModelMetadata,
 	ortsys,
-	util::{STACK_SESSION_INPUTS, STACK_SESSION_OUTPUTS, with_cstr_ptr_array},
-	value::{DynValue, Value, ValueType}
+	tensor::{Shape, TensorElementType},
+	util::{MiniMap, STACK_SESSION_INPUTS, STACK_SESSION_OUTPUTS, with_cstr_ptr_array},
+	value::{DynTensor, DynValue, Value, ValueType}
 };
+#[cfg(feature = "ndarray")]
+use crate::{tensor::PrimitiveTensorElementType, value::TensorRef};
 
 #[cfg(feature = "std")]
 mod r#async;
 pub mod builder;
+mod graph;
 pub mod input;
 pub mod output;
+mod output_pool;
+#[cfg(feature = "std")]
+mod placement;
+#[cfg(feature = "std")]
+mod profiler;
 pub mod run_options;
 #[cfg(feature = "std")]
 pub use self::r#async::InferenceFut;
 #[cfg(feature = "std")]
 use self::r#async::{AsyncInferenceContext, InferenceFutInner};
+#[cfg(feature = "std")]
+pub use self::profiler::{Profiler, ProfilingSampler, SampledRunStats};
 use self::{builder::SessionBuilder, run_options::UntypedRunOptions};
 pub use self::{
+	graph::{GraphSummary, NodeSummary},
 	input::{SessionInputValue, SessionInputs},
 	output::SessionOutputs,
+	output_pool::OutputPool,
 	run_options::{HasSelectedOutputs, NoSelectedOutputs, RunOptions, SelectedOutputMarker}
 };
 
@@ -95,16 +115,104 @@ impl Drop for SharedSessionInner {
 /// # 	Ok(())
 /// # }
 /// ```
+///
+/// ## Concurrency
+/// `Session` is `Send` and `Sync`, but inference methods like [`Session::run`] take `&mut self`. While ONNX Runtime
+/// allows concurrent runs on the CPU execution provider, concurrent runs on other execution providers (e.g. CUDA or
+/// DirectML) have been observed to crash, so `ort` requires exclusive access to run a session. To serve requests from
+/// multiple threads, wrap the session in a `Mutex`, or create one session per worker; sessions created from the same
+/// model can share their weights with
+/// [`SessionBuilder::with_prepacked_weights`](builder::SessionBuilder::with_prepacked_weights).
+///
+/// An `Arc<Mutex<Session>>` can be cloned into threads or async tasks. Since [`SessionOutputs`] borrow the session,
+/// copy what you need out of the outputs before releasing the lock:
+/// ```
+/// # use std::{sync::{Arc, Mutex}, thread};
+/// # use ort::{session::Session, value::TensorRef};
+/// # fn main() -> ort::Result<()> {
+/// let session = Arc::new(Mutex::new(Session::builder()?.commit_from_file("tests/data/upsample.onnx")?));
+/// let workers: Vec<_> = (0..4)
+/// 	.map(|_| {
+/// 		let session = Arc::clone(&session);
+/// 		thread::spawn(move || -> ort::Result<Vec<f32>> {
+/// 			let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+/// 			let mut session = session.lock().expect("session mutex was poisoned");
+/// 			let outputs = session.run(ort::inputs![TensorRef::from_array_view(&input)?])?;
+/// 			Ok(outputs[0].try_extract_tensor::<f32>()?.1.to_vec())
+/// 		})
+/// 	})
+/// 	.collect();
+/// for worker in workers {
+/// 	let output = worker.join().expect("worker panicked")?;
+/// }
+/// # 	Ok(())
+/// # }
+/// ```
+///
+/// Only one run may be in progress per session at a time, so if the model is small enough, creating a session per
+/// worker will give better throughput than sharing one behind a lock.
+///
+/// ## Teardown
+/// The underlying [`ort_sys::OrtSession`] is released once the session and everything that references it - values
+/// returned by [`Session::run`], [`IoBinding`]s, and session [`Allocator`]s - have been dropped. Use
+/// [`Session::close`] to release it at a well-defined point instead.
+///
+/// Sessions must be released before the [environment](crate::environment::Environment) they were created under. The
+/// environment created by [`ort::init`](crate::init) (or implicitly by the first session) is stored in a `static`
+/// and is never released during the lifetime of the process, so this is always upheld in Rust code. However, Rust
+/// does not run destructors for `static`s or leaked values at process exit, whereas ONNX Runtime's own global state is
+/// torn down by the C++ runtime; hosts which embed `ort` (e.g. in a plugin that may be unloaded, or via FFI from
+/// another language) should therefore [`close`](Session::close) all sessions before unloading or exiting, rather than
+/// relying on drop order.
 #[derive(Debug)]
 pub struct Session {
 	pub(crate) inner: Arc<SharedSessionInner>,
 	/// Information about the graph's inputs.
 	pub inputs: Vec<Input>,
 	/// Information about the graph's outputs.
-	pub outputs: Vec<Output>
+	pub outputs: Vec<Output>,
+	validate_inputs: bool,
+	/// The shapes bound to the first run of each captured GPU graph, keyed by graph ID, if graph capture is enabled.
+	captured_graph_shapes: Option<MiniMap<i32, Vec<(String, Shape)>>>,
+	profiling: bool,
+	/// Where the model this session was committed from can be loaded again, for
+	/// [`SessionBuilder::commit_from_session_model`]. `None` for sessions committed from memory without
+	/// [`SessionBuilder::with_retained_model`].
+	model: Option<ModelSource>,
+	#[cfg(feature = "std")]
+	optimized_model_path: Option<std::path::PathBuf>
 }
 
-/// A [`Session`] where the graph data is stored in memory.
+/// Where the model a [`Session`] was committed from can be loaded again.
+#[derive(Debug, Clone)]
+pub(crate) enum ModelSource {
+	/// The (absolute) path of the model file, which is read again when the model is needed.
+	#[cfg(feature = "std")]
+	File(std::path::PathBuf),
+	/// A copy of the serialized model, kept because [`SessionBuilder::with_retained_model`] was enabled.
+	Retained(RetainedModel)
+}
+
+/// A reference-counted copy of the serialized model a [`Session`] was committed from.
+#[derive(Clone)]
+pub(crate) struct RetainedModel {
+	pub(crate) bytes: Arc<[u8]>,
+	/// The directory of the model file, which external data files are looked up relative to; `None` if the model was
+	/// loaded from memory.
+	pub(crate) dir: Option<String>
+}
+
+impl fmt::Debug for RetainedModel {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RetainedModel").field("len", &self.bytes.len()).finish_non_exhaustive()
+	}
+}
+
+/// A [`Session`] which borrows its model data from memory, created by
+/// [`SessionBuilder::commit_from_memory_directly`](crate::session::builder::SessionBuilder::commit_from_memory_directly).
+///
+/// The lifetime `'s` ties the session to the model bytes, since ONNX Runtime may reference them for as long as the
+/// session exists.
 ///
 /// This type is automatically `Deref`'d into a `Session`, so you can use it like you would a regular `Session`. See
 /// [`Session`] for usage details.
@@ -125,6 +233,44 @@ impl DerefMut for InMemorySession<'_> {
 	}
 }
 
+impl InMemorySession<'_> {
+	/// Eagerly releases the underlying session. See [`Session::close`].
+	pub fn close(self) -> Result<()> {
+		self.session.close()
+	}
+}
+
+/// An iterator which lazily runs a [`Session`] on a stream of inputs, created by [`Session::run_iter`].
+pub struct RunIter<'s, 'i, 'v, I, const N: usize> {
+	session: &'s Session,
+	inputs: I,
+	_inputs: PhantomData<fn() -> SessionInputs<'i, 'v, N>>
+}
+
+impl<'s, 'i, 'v: 'i, I: Iterator, const N: usize> Iterator for RunIter<'s, 'i, 'v, I, N>
+where
+	I::Item: Into<SessionInputs<'i, 'v, N>>
+{
+	type Item = Result<SessionOutputs<'s, 's>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let input_values = self.inputs.next()?;
+		Some(self.session.run_shared(input_values))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inputs.size_hint()
+	}
+}
+
+impl<'i, 'v: 'i, I: FusedIterator, const N: usize> FusedIterator for RunIter<'_, 'i, 'v, I, N> where I::Item: Into<SessionInputs<'i, 'v, N>> {}
+
+impl<I, const N: usize> fmt::Debug for RunIter<'_, '_, '_, I, N> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RunIter").field("session", &self.session.ptr()).finish_non_exhaustive()
+	}
+}
+
 /// Information about a [`Session`] input.
 #[derive(Debug)]
 pub struct Input {
@@ -167,6 +313,44 @@ impl Session {
 		Arc::clone(&self.inner)
 	}
 
+	/// Eagerly releases the underlying [`ort_sys::OrtSession`], along with its allocator and any resources (like
+	/// custom operator libraries) held for it.
+	///
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if the session is still referenced elsewhere, e.g. by
+	/// values returned from [`Session::run`], an [`IoBinding`], or a handle obtained via [`Session::inner`]. In that
+	/// case, the session is instead released once the last of those references is dropped. See the [teardown
+	/// notes](Session#teardown) for when this is necessary.
+	///
+	/// ```
+	/// # use ort::{session::Session, value::TensorRef};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let output_name = session.outputs[0].name.clone();
+	/// let output = session.run(ort::inputs![TensorRef::from_array_view(&input)?])?.remove(&output_name);
+	///
+	/// // `output` still references the session, so it cannot be closed yet.
+	/// assert!(session.close().is_err());
+	/// drop(output);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn close(self) -> Result<()> {
+		match Arc::try_unwrap(self.inner) {
+			Ok(inner) => {
+				drop(inner);
+				Ok(())
+			}
+			Err(inner) => Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!(
+					"Session could not be closed because it is still referenced {} other time(s); it will be released when the last reference is dropped",
+					Arc::strong_count(&inner) - 1
+				)
+			))
+		}
+	}
+
 	/// Returns a list of initializers which are overridable (i.e. also graph inputs).
 	#[must_use]
 	pub fn overridable_initializers(&self) -> Vec<OverridableInitializer> {
@@ -206,15 +390,52 @@ impl Session {
 	/// # }
 	/// ```
 	pub fn run<'s, 'i, 'v: 'i, const N: usize>(&'s mut self, input_values: impl Into<SessionInputs<'i, 'v, N>>) -> Result<SessionOutputs<'s, 's>> {
+		self.run_shared(input_values)
+	}
+
+	/// Lazily runs each of `inputs` through the graph, returning an iterator which yields the outputs of each run.
+	///
+	/// Each input is only pulled from `inputs` and run when the next item is requested from the returned iterator, so
+	/// `inputs` can be an unbounded stream, and a slow consumer naturally applies backpressure to the producer. The
+	/// session is borrowed mutably for as long as the iterator exists, but the [`SessionOutputs`] it yields can be kept
+	/// after moving on to the next item.
+	///
+	/// ```
+	/// # use ort::{session::Session, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let frames = (0..4).map(|_| ndarray::Array4::<f32>::zeros((1, 64, 64, 3)));
+	///
+	/// let inputs = frames.map(|frame| ort::inputs![Tensor::from_array(frame).expect("frame should be a valid tensor")]);
+	/// for outputs in session.run_iter(inputs) {
+	/// 	let outputs = outputs?;
+	/// 	// ...
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn run_iter<'s, 'i, 'v: 'i, const N: usize, I>(&'s mut self, inputs: I) -> RunIter<'s, 'i, 'v, I::IntoIter, N>
+	where
+		I: IntoIterator,
+		I::Item: Into<SessionInputs<'i, 'v, N>>
+	{
+		RunIter {
+			session: self,
+			inputs: inputs.into_iter(),
+			_inputs: PhantomData
+		}
+	}
+
+	fn run_shared<'s, 'i, 'v: 'i, const N: usize>(&'s self, input_values: impl Into<SessionInputs<'i, 'v, N>>) -> Result<SessionOutputs<'s, 's>> {
 		match input_values.into() {
 			SessionInputs::ValueSlice(input_values) => {
-				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), None)
+				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), None, None)
 			}
 			SessionInputs::ValueArray(input_values) => {
-				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), None)
+				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), None, None)
 			}
 			SessionInputs::ValueMap(input_values) => {
-				self.run_inner(input_values.iter().map(|(k, _)| k.as_ref()).collect(), input_values.iter().map(|(_, v)| v).collect(), None)
+				self.run_inner(input_values.iter().map(|(k, _)| k.as_ref()).collect(), input_values.iter().map(|(_, v)| v).collect(), None, None)
 			}
 		}
 	}
@@ -252,13 +473,247 @@ impl Session {
 	) -> Result<SessionOutputs<'r, 's>> {
 		match input_values.into() {
 			SessionInputs::ValueSlice(input_values) => {
-				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), Some(&run_options.inner))
+				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), Some(&run_options.inner), None)
+			}
+			SessionInputs::ValueArray(input_values) => {
+				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), Some(&run_options.inner), None)
+			}
+			SessionInputs::ValueMap(input_values) => self.run_inner(
+				input_values.iter().map(|(k, _)| k.as_ref()).collect(),
+				input_values.iter().map(|(_, v)| v).collect(),
+				Some(&run_options.inner),
+				None
+			)
+		}
+	}
+
+	/// Run input data through the ONNX graph, computing only the outputs named in `output_names`.
+	///
+	/// The returned [`SessionOutputs`] contain only the requested outputs, in the order they were requested. Parts of
+	/// the graph that only contribute to outputs which were not requested are not executed, which can save a
+	/// significant amount of time and memory for models with expensive auxiliary outputs.
+	///
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if any name in `output_names` is not an output of the
+	/// model.
+	///
+	/// ```
+	/// # use ort::{session::Session, value::TensorRef};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let output_name = session.outputs[0].name.clone();
+	/// let outputs = session
+	/// 	.run_with_outputs_selected(ort::inputs![TensorRef::from_array_view(&input)?], &[output_name.as_str()])?;
+	/// assert_eq!(outputs.len(), 1);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn run_with_outputs_selected<'s, 'i, 'v: 'i, const N: usize>(
+		&'s mut self,
+		input_values: impl Into<SessionInputs<'i, 'v, N>>,
+		output_names: &[&str]
+	) -> Result<SessionOutputs<'s, 's>> {
+		let selected_outputs = output_names
+			.iter()
+			.map(|name| match self.outputs.iter().find(|output| output.name == *name) {
+				Some(output) => Ok(output.name.as_str()),
+				None => Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!(
+						"Output `{name}` is not an output of this model; available outputs are: {}",
+						self.outputs
+							.iter()
+							.map(|output| format!("`{}`", output.name))
+							.collect::<Vec<_>>()
+							.join(", ")
+					)
+				))
+			})
+			.collect::<Result<SmallVec<&str, { STACK_SESSION_OUTPUTS }>>>()?;
+		let selected_outputs = Some((selected_outputs, iter::repeat_with(|| None).take(output_names.len()).collect()));
+		self.run_with_selected_outputs(input_values, selected_outputs)
+	}
+
+	/// Run input data through the ONNX graph, allocating outputs from `allocator` where possible.
+	///
+	/// Only outputs whose shapes are fully known ahead of time, i.e. tensor outputs without any dynamic/symbolic
+	/// dimensions in [`Session::outputs`], can be allocated up front; they are allocated from `allocator` before the
+	/// run and filled in by ONNX Runtime. Outputs with dynamic shapes are allocated by ONNX Runtime as usual, as in
+	/// [`Session::run`]. If `allocator` allocates memory on a different device than the one an output is computed on,
+	/// ONNX Runtime copies the output into it.
+	///
+	/// This allows request-scoped memory management, e.g. with an allocator whose memory is reclaimed after each
+	/// request, or accounting of output memory. For full control over where all outputs are placed, including those
+	/// with dynamic shapes, use [`IoBinding::bind_output_to_device`].
+	///
+	/// ```
+	/// # use ort::{memory::Allocator, session::Session, value::TensorRef};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let allocator = Allocator::default();
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let outputs = session.run_with_allocator(ort::inputs![TensorRef::from_array_view(&input)?], &allocator)?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn run_with_allocator<'s, 'i, 'v: 'i, const N: usize>(
+		&'s mut self,
+		input_values: impl Into<SessionInputs<'i, 'v, N>>,
+		allocator: &Allocator
+	) -> Result<SessionOutputs<'s, 's>> {
+		let (names, values) = self
+			.outputs
+			.iter()
+			.map(|output| {
+				let value = match &output.output_type {
+					ValueType::Tensor { ty, shape, .. } if shape.iter().all(|&dim| dim >= 0) => Some(DynTensor::new(allocator, *ty, shape.clone())?.into_dyn()),
+					_ => None
+				};
+				Ok((output.name.as_str(), value))
+			})
+			.collect::<Result<Vec<_>>>()?
+			.into_iter()
+			.unzip();
+		self.run_with_selected_outputs(input_values, Some((names, values)))
+	}
+
+	/// Run input data through the ONNX graph, writing outputs into buffers recycled from `pool` where possible. See
+	/// [`OutputPool`] for how buffers are recycled.
+	///
+	/// Recycled buffers are checked against the outputs' declared types & shapes before the run, so the model is only
+	/// ever run once. An output whose shape depends on the input *data* rather than only the input shapes (e.g. the
+	/// number of detections after non-maximum suppression) may still not fit a recycled buffer; in that case, ONNX
+	/// Runtime's error is returned, and the buffers bound to the failed run are removed from the pool so that retrying
+	/// allocates new ones.
+	pub fn run_pooled<'s, 'i, 'v: 'i, const N: usize>(
+		&'s mut self,
+		input_values: impl Into<SessionInputs<'i, 'v, N>>,
+		pool: &mut OutputPool
+	) -> Result<SessionOutputs<'s, 's>> {
+		let input_values = input_values.into();
+		let input_shapes = self.input_shapes(&input_values);
+		let input_shapes = input_shapes.as_deref();
+
+		let names: SmallVec<&str, { STACK_SESSION_OUTPUTS }> = self.outputs.iter().map(|output| output.name.as_str()).collect();
+		let buffers: SmallVec<Option<DynValue>, { STACK_SESSION_OUTPUTS }> = self
+			.outputs
+			.iter()
+			.map(|output| pool.acquire(&output.name, &output.output_type, input_shapes))
+			.collect();
+		let bound: SmallVec<DynValue, { STACK_SESSION_OUTPUTS }> = buffers.iter().flatten().map(DynValue::clone_of).collect();
+		let outputs = match self.run_inputs_with_selected_outputs(&input_values, Some((names.clone(), buffers))) {
+			Ok(outputs) => outputs,
+			Err(e) => {
+				for buffer in &bound {
+					pool.discard(buffer);
+				}
+				return Err(e);
+			}
+		};
+
+		pool.record(bound.len(), names.len() - bound.len());
+		drop(bound);
+		for name in names {
+			if let Some(value) = outputs.get(name) {
+				pool.release(name, value, input_shapes);
+			}
+		}
+		Ok(outputs)
+	}
+
+	/// Returns the names & shapes of the given inputs, or `None` if any input is not a tensor.
+	fn input_shapes<'a, const N: usize>(
+		&'a self,
+		input_values: &'a SessionInputs<'_, '_, N>
+	) -> Option<SmallVec<(&'a str, &'a Shape), { STACK_SESSION_INPUTS }>> {
+		let shape = |value: &'a SessionInputValue<'_>| match value.dtype() {
+			ValueType::Tensor { shape, .. } => Some(shape),
+			_ => None
+		};
+		match input_values {
+			SessionInputs::ValueSlice(values) => self
+				.inputs
+				.iter()
+				.zip(values.iter())
+				.map(|(input, value)| Some((input.name.as_str(), shape(value)?)))
+				.collect(),
+			SessionInputs::ValueArray(values) => self
+				.inputs
+				.iter()
+				.zip(values.iter())
+				.map(|(input, value)| Some((input.name.as_str(), shape(value)?)))
+				.collect(),
+			SessionInputs::ValueMap(values) => values.iter().map(|(name, value)| Some((name.as_ref(), shape(value)?))).collect()
+		}
+	}
+
+	/// Run `ndarray` arrays through the ONNX graph, performing inference. Each input is given as a `(name, array view)`
+	/// pair; the arrays are viewed as [`TensorRef`]s without copying.
+	///
+	/// This is a shorthand for prototyping; it is equivalent to passing
+	/// `ort::inputs![name => TensorRef::from_array_view(array)?]` to [`Session::run`]. All arrays must have the same
+	/// element type; to mix element types (e.g. `i64` token IDs and `f32` features), use [`crate::inputs!`].
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let input_name = session.inputs[0].name.clone();
+	/// let outputs = session.run_arrays([(input_name.as_str(), input.view())])?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error with [`ErrorCode::InvalidArgument`] naming the input if an array is not in standard
+	/// (contiguous, row-major) layout, e.g. a transposed or sliced view; use
+	/// [`as_standard_layout`](ndarray::ArrayBase::as_standard_layout) to get a contiguous copy. Otherwise, returns any
+	/// error [`Session::run`] would.
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn run_arrays<'s, 'a, T, D>(&'s mut self, inputs: impl IntoIterator<Item = (&'a str, ndarray::ArrayView<'a, T, D>)>) -> Result<SessionOutputs<'s, 's>>
+	where
+		T: PrimitiveTensorElementType + fmt::Debug + Clone + 'static,
+		D: ndarray::Dimension + 'static
+	{
+		let inputs = inputs
+			.into_iter()
+			.map(|(name, array)| {
+				if !array.is_standard_layout() {
+					return Err(Error::new_with_code(
+						ErrorCode::InvalidArgument,
+						format!("Input `{name}` is not in standard layout; use `.as_standard_layout()` to get a contiguous array")
+					));
+				}
+				Ok((name, SessionInputValue::from(TensorRef::from_array_view(array)?)))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		self.run(inputs)
+	}
+
+	fn run_with_selected_outputs<'s, 'i, 'v: 'i, const N: usize>(
+		&'s self,
+		input_values: impl Into<SessionInputs<'i, 'v, N>>,
+		selected_outputs: Option<(SmallVec<&'s str, { STACK_SESSION_OUTPUTS }>, SmallVec<Option<DynValue>, { STACK_SESSION_OUTPUTS }>)>
+	) -> Result<SessionOutputs<'s, 's>> {
+		self.run_inputs_with_selected_outputs(&input_values.into(), selected_outputs)
+	}
+
+	fn run_inputs_with_selected_outputs<'s, 'v, const N: usize>(
+		&'s self,
+		input_values: &SessionInputs<'_, 'v, N>,
+		selected_outputs: Option<(SmallVec<&'s str, { STACK_SESSION_OUTPUTS }>, SmallVec<Option<DynValue>, { STACK_SESSION_OUTPUTS }>)>
+	) -> Result<SessionOutputs<'s, 's>> {
+		match input_values {
+			SessionInputs::ValueSlice(input_values) => {
+				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), None, selected_outputs)
 			}
 			SessionInputs::ValueArray(input_values) => {
-				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), Some(&run_options.inner))
+				self.run_inner(self.inputs.iter().map(|input| input.name.as_str()).collect(), input_values.iter().collect(), None, selected_outputs)
 			}
 			SessionInputs::ValueMap(input_values) => {
-				self.run_inner(input_values.iter().map(|(k, _)| k.as_ref()).collect(), input_values.iter().map(|(_, v)| v).collect(), Some(&run_options.inner))
+				self.run_inner(input_values.iter().map(|(k, _)| k.as_ref()).collect(), input_values.iter().map(|(_, v)| v).collect(), None, selected_outputs)
 			}
 		}
 	}
@@ -267,7 +722,8 @@ impl Session {
 		&'s self,
 		input_names: SmallVec<&str, { STACK_SESSION_INPUTS }>,
 		input_values: SmallVec<&'i SessionInputValue<'v>, { STACK_SESSION_INPUTS }>,
-		run_options: Option<&'r UntypedRunOptions>
+		run_options: Option<&'r UntypedRunOptions>,
+		selected_outputs: Option<(SmallVec<&'s str, { STACK_SESSION_OUTPUTS }>, SmallVec<Option<DynValue>, { STACK_SESSION_OUTPUTS }>)>
 	) -> Result<SessionOutputs<'r, 's>> {
 		if input_values.len() > input_names.len() {
 			// If we provide more inputs than the model expects with `ort::inputs![a, b, c]`, then we get an `input_names` shorter
@@ -279,10 +735,15 @@ impl Session {
 				format!("{} inputs were provided, but the model only accepts {}.", input_values.len(), input_names.len())
 			));
 		}
+		self.check_required_inputs(&input_names[..input_values.len()])?;
+		if self.validate_inputs {
+			self.check_input_types(&input_names, &input_values)?;
+		}
 
-		let (output_names, mut output_tensors) = match run_options {
-			Some(r) => r.outputs.resolve_outputs(&self.outputs),
-			None => (self.outputs.iter().map(|o| o.name.as_str()).collect(), iter::repeat_with(|| None).take(self.outputs.len()).collect())
+		let (output_names, mut output_tensors) = match (selected_outputs, run_options) {
+			(Some(selected_outputs), _) => selected_outputs,
+			(None, Some(r)) => r.outputs.resolve_outputs(&self.outputs)?,
+			(None, None) => (self.outputs.iter().map(|o| o.name.as_str()).collect(), iter::repeat_with(|| None).take(self.outputs.len()).collect())
 		};
 		let output_value_ptrs: SmallVec<*mut ort_sys::OrtValue, { STACK_SESSION_OUTPUTS }> = output_tensors
 			.iter_mut()
@@ -330,6 +791,14 @@ impl Session {
 		Ok(SessionOutputs::new(output_names, outputs))
 	}
 
+	/// Runs the session with the inputs & outputs bound in `binding`. See [`IoBinding`] for more details.
+	///
+	/// Outputs bound with [`IoBinding::bind_output`] are returned as the same value that was bound. Outputs bound
+	/// with [`IoBinding::bind_output_to_device`] are allocated by ONNX Runtime on the requested device and returned
+	/// without being copied to the host; their [`Tensor::memory_info`](crate::value::Tensor::memory_info) describes
+	/// that device, and [`Tensor::data_ptr`](crate::value::Tensor::data_ptr) returns a device pointer. Each returned
+	/// value owns its buffer and keeps the session alive, so device memory remains valid for as long as the value
+	/// exists, even after the [`SessionOutputs`] or the session are dropped.
 	pub fn run_binding<'b, 's: 'b>(&'s mut self, binding: &'b IoBinding) -> Result<SessionOutputs<'b, 's>> {
 		self.run_binding_inner(binding, None)
 	}
@@ -343,13 +812,20 @@ impl Session {
 	}
 
 	fn run_binding_inner<'r, 'b, 's: 'b>(
-		&'s self,
+		&'s mut self,
 		binding: &'b IoBinding,
 		run_options: Option<&'r RunOptions<NoSelectedOutputs>>
 	) -> Result<SessionOutputs<'b, 's>> {
+		let graph_id = run_options.map_or(0, |run_options| run_options.inner.gpu_graph_id);
+		let uncaptured_shapes = self.check_captured_graph_shapes(binding, graph_id)?;
+
 		let run_options_ptr = if let Some(run_options) = run_options { run_options.ptr() } else { ptr::null() };
 		ortsys![unsafe RunWithBinding(self.inner.ptr().cast_mut(), run_options_ptr, binding.ptr())?];
 
+		if let (Some(captured_graph_shapes), Some(shapes)) = (self.captured_graph_shapes.as_mut(), uncaptured_shapes) {
+			captured_graph_shapes.insert(graph_id, shapes);
+		}
+
 		let mut count = binding.output_values.len();
 		if count > 0 {
 			let mut output_values_ptr: *mut *mut ort_sys::OrtValue = ptr::null_mut();
@@ -360,6 +836,9 @@ impl Session {
 				.zip(binding.output_values.iter())
 				.map(|(ptr, (_, value))| unsafe {
 					if let Some(value) = value {
+						// `GetBoundOutputValues` returns a new reference to the bound value, which we don't need since we
+						// already hold the value.
+						ortsys![ReleaseValue](ptr);
 						DynValue::clone_of(value)
 					} else {
 						DynValue::from_ptr(NonNull::new(ptr).expect("OrtValue ptrs returned by GetBoundOutputValues should not be null"), Some(self.inner()))
@@ -380,6 +859,31 @@ impl Session {
 		}
 	}
 
+	/// If GPU graph capture is enabled, checks that the shapes bound to `binding` match those the graph `graph_id` was
+	/// captured with. Returns the shapes to remember if this run will capture the graph.
+	fn check_captured_graph_shapes(&self, binding: &IoBinding, graph_id: i32) -> Result<Option<Vec<(String, Shape)>>> {
+		// -1 disables capture & replay for a run.
+		let Some(captured_graph_shapes) = self.captured_graph_shapes.as_ref().filter(|_| graph_id != -1) else {
+			return Ok(None);
+		};
+		let Some(captured) = captured_graph_shapes.get(&graph_id) else {
+			return Ok(Some(binding.bound_shapes().map(|(name, shape)| (String::from(name), shape.clone())).collect()));
+		};
+		for (name, shape) in binding.bound_shapes() {
+			if let Some((_, captured_shape)) = captured.iter().find(|(captured_name, _)| captured_name == name) {
+				if captured_shape != shape {
+					return Err(Error::new_with_code(
+						ErrorCode::InvalidArgument,
+						format!(
+							"`{name}` is bound to a tensor of shape {shape}, but GPU graph {graph_id} was captured with shape {captured_shape}; captured graphs must be replayed with the same shapes, or be captured under a different graph ID"
+						)
+					));
+				}
+			}
+		}
+		Ok(None)
+	}
+
 	/// Asynchronously run input data through the ONNX graph, performing inference.
 	///
 	/// Inference will be performed on a thread in the session's thread pool. **Thus, the session must have been
@@ -427,6 +931,18 @@ impl Session {
 		input_values: SmallVec<&SessionInputValue<'v>, { STACK_SESSION_INPUTS }>,
 		run_options: &'r UntypedRunOptions
 	) -> Result<InferenceFut<'s, 'r, 'v>> {
+		if input_values.len() > input_names.len() {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("{} inputs were provided, but the model only accepts {}.", input_values.len(), input_names.len())
+			));
+		}
+		self.check_required_inputs(&input_names[..input_values.len()])?;
+		if self.validate_inputs {
+			self.check_input_types(&input_names, &input_values)?;
+		}
+		let (output_names, mut output_tensors) = run_options.outputs.resolve_outputs(&self.outputs)?;
+
 		let input_name_ptrs = input_names
 			.into_iter()
 			.map(|name| CString::new(name.as_bytes()).map(|s| s.into_raw().cast_const()))
@@ -443,7 +959,6 @@ impl Session {
 			}));
 		}
 
-		let (output_names, mut output_tensors) = run_options.outputs.resolve_outputs(&self.outputs);
 		let output_name_ptrs = output_names
 			.iter()
 			.map(|n| CString::new(*n).unwrap_or_else(|_| unreachable!()))
@@ -492,6 +1007,106 @@ impl Session {
 		Ok(InferenceFut::new(async_inner, run_options))
 	}
 
+	/// Ensures all required (i.e. non-[optional](ValueType::Optional)) graph inputs are among `bound_names`. Inputs
+	/// that are left unbound are handled by ONNX Runtime's default behavior for optional inputs.
+	fn check_required_inputs(&self, bound_names: &[&str]) -> Result<()> {
+		let missing: Vec<&str> = self
+			.inputs
+			.iter()
+			.filter(|input| !matches!(input.input_type, ValueType::Optional(_)))
+			.map(|input| input.name.as_str())
+			.filter(|name| !bound_names.contains(name))
+			.collect();
+		if !missing.is_empty() {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Required input(s) were not provided: `{}`", missing.join("`, `"))));
+		}
+		Ok(())
+	}
+
+	/// Checks that the element type & shape of each tensor input matches what the graph expects. Enabled via
+	/// [`SessionBuilder::with_input_validation`].
+	fn check_input_types(&self, names: &[&str], values: &[&SessionInputValue<'_>]) -> Result<()> {
+		for (name, value) in names.iter().zip(values) {
+			let Some(input) = self.inputs.iter().find(|input| input.name == *name) else {
+				continue;
+			};
+			let (
+				ValueType::Tensor {
+					ty: expected_ty,
+					shape: expected_shape,
+					dimension_symbols
+				},
+				ValueType::Tensor { ty, shape, .. }
+			) = (&input.input_type, value.dtype())
+			else {
+				continue;
+			};
+
+			let shape_matches = shape.len() == expected_shape.len()
+				&& shape
+					.iter()
+					.zip(expected_shape.iter())
+					.all(|(dim, expected)| *expected < 0 || dim == expected);
+			if ty != expected_ty || !shape_matches {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!(
+						"Invalid input `{name}`: expected Tensor<{expected_ty}> with shape {}, got Tensor<{ty}> with shape {shape}",
+						format_declared_shape(expected_shape, dimension_symbols)
+					)
+				));
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks that this session's inputs & outputs match an expected signature, returning an error describing the first
+	/// mismatch. This is useful to verify that a model loaded at runtime is the model you expect.
+	///
+	/// Each input/output is given as `(name, element type, shape)`. A `None` dimension matches any dimension, including
+	/// symbolic dimensions; a `Some` dimension must match the model's declared dimension exactly. Inputs & outputs of
+	/// the session not mentioned in the signature are ignored.
+	///
+	/// ```
+	/// # use ort::{session::Session, tensor::TensorElementType};
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	let (input, output) = (session.inputs[0].name.as_str(), session.outputs[0].name.as_str());
+	/// session.assert_signature(
+	/// 	&[(input, TensorElementType::Float32, &[None, None, None, Some(3)])],
+	/// 	&[(output, TensorElementType::Float32, &[None, None, None, Some(3)])]
+	/// )?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn assert_signature(&self, inputs: &[(&str, TensorElementType, &[Option<i64>])], outputs: &[(&str, TensorElementType, &[Option<i64>])]) -> Result<()> {
+		let declared_inputs = self.inputs.iter().map(|input| (input.name.as_str(), &input.input_type));
+		let declared_outputs = self.outputs.iter().map(|output| (output.name.as_str(), &output.output_type));
+		check_signature("input", inputs, declared_inputs)?;
+		check_signature("output", outputs, declared_outputs)
+	}
+
+	/// Returns a fingerprint of this session's signature: the names, types, and shapes (including symbolic dimensions)
+	/// of its inputs & outputs, in order. This is useful as part of the key of a cache of inference results, so the
+	/// cache is invalidated when the model's interface changes.
+	///
+	/// The hash is stable across processes, platforms, and `ort` versions for the same model. Note that it does not
+	/// cover the model's weights; models with the same signature but different weights will have the same hash. To
+	/// also invalidate on a new version of the model, combine it with the [metadata](Session::metadata)'s
+	/// [`version`](ModelMetadata::version).
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let cache_key = (session.signature_hash(), session.metadata()?.version()?);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn signature_hash(&self) -> u64 {
+		signature_hash(&self.inputs, &self.outputs)
+	}
+
 	/// Gets the session model metadata. See [`ModelMetadata`] for more info.
 	pub fn metadata(&self) -> Result<ModelMetadata<'_>> {
 		let mut metadata_ptr: *mut ort_sys::OrtModelMetadata = ptr::null_mut();
@@ -500,6 +1115,11 @@ impl Session {
 	}
 
 	/// Returns the time that profiling was started, in nanoseconds.
+	///
+	/// The timestamp is taken from the C++ `high_resolution_clock`, which on most platforms is the system clock, i.e.
+	/// nanoseconds since the Unix epoch. The `ts` field of each event in the profiling file is an offset in
+	/// *microseconds* from this time, so adding `ts * 1000` to this value places an event on an absolute timeline that
+	/// can be merged with traces from other sources.
 	pub fn profiling_start_ns(&self) -> Result<u64> {
 		let mut out = 0;
 		ortsys![unsafe SessionGetProfilingStartTimeNs(self.inner.session_ptr.as_ptr(), &mut out)?];
@@ -509,14 +1129,161 @@ impl Session {
 	/// Ends profiling for this session.
 	///
 	/// Note that this must be explicitly called at the end of profiling, otherwise the profiling file will be empty.
+	/// [`Session::profile`] can be used to end profiling automatically instead.
 	pub fn end_profiling(&mut self) -> Result<String> {
 		let mut profiling_name: *mut c_char = ptr::null_mut();
 
 		ortsys![unsafe SessionEndProfiling(self.inner.session_ptr.as_ptr(), self.inner.allocator.ptr().cast_mut(), &mut profiling_name)?; nonNull(profiling_name)];
+		self.profiling = false;
 
 		dangerous::raw_pointer_to_string(&self.inner.allocator, profiling_name)
 	}
 
+	/// Returns a [`Profiler`] guard which ends profiling when it is dropped, so the profiling file is always written,
+	/// even if an error occurs while the session is being profiled. The guard dereferences to the session, so the
+	/// session can be run through it as usual.
+	///
+	/// ONNX Runtime can only enable profiling when a session is created, so the session must have been created with
+	/// [`SessionBuilder::with_profiling`]; otherwise, or if profiling has already ended, this returns an error with
+	/// [`ErrorCode::InvalidArgument`].
+	///
+	/// ```no_run
+	/// # use ort::{session::Session, value::TensorRef};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?
+	/// 	.with_profiling("trace")?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	///
+	/// let mut profiler = session.profile()?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// profiler.run(ort::inputs![TensorRef::from_array_view(&input)?])?;
+	/// let trace_path = profiler.finish()?;
+	/// println!("profile written to {trace_path}");
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[cfg(feature = "std")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+	pub fn profile(&mut self) -> Result<Profiler<'_>> {
+		if !self.profiling {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Profiling is not enabled for this session; use `SessionBuilder::with_profiling`"));
+		}
+		Ok(Profiler::new(self))
+	}
+
+	/// Runs the session like [`Session::run`], measuring the run's latency if `sampler` selects it for sampling. The
+	/// latency of sampled runs is aggregated in [`ProfilingSampler::stats`].
+	///
+	/// Since ONNX Runtime's profiler can only profile every run of a session, this does not produce node-level
+	/// profiles; see [`ProfilingSampler`] for how to profile only the sampled runs.
+	///
+	/// ```no_run
+	/// # use ort::{session::{ProfilingSampler, Session}, value::TensorRef};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let sampler = ProfilingSampler::new(0.01)?;
+	///
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// for _ in 0..1000 {
+	/// 	let outputs = session.run_sampled(ort::inputs![TensorRef::from_array_view(&input)?], &sampler)?;
+	/// }
+	/// println!("mean latency of sampled runs: {:?}", sampler.stats().mean);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[cfg(feature = "std")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+	pub fn run_sampled<'s, 'i, 'v: 'i, const N: usize>(
+		&'s mut self,
+		input_values: impl Into<SessionInputs<'i, 'v, N>>,
+		sampler: &ProfilingSampler
+	) -> Result<SessionOutputs<'s, 's>> {
+		if !sampler.should_sample() {
+			return self.run(input_values);
+		}
+		let start = std::time::Instant::now();
+		let outputs = self.run(input_values)?;
+		sampler.record(start.elapsed());
+		Ok(outputs)
+	}
+
+	/// Returns the execution provider assigned to each node which was executed while profiling, as `(node name,
+	/// execution provider)` pairs in the order the nodes first ran, e.g. `("conv_1", "CUDAExecutionProvider")`. This is
+	/// useful to find out why part of a model is running on the CPU instead of a registered execution provider.
+	///
+	/// ONNX Runtime does not expose node placements directly, so they are read from the session's profiling output.
+	/// The session must have been created with [`SessionBuilder::with_profiling`], and must have been run at least once
+	/// before calling this; nodes which were not executed (e.g. in untaken branches of an `If` node) will not be
+	/// listed. Nodes fused into a compiled subgraph by an execution provider like TensorRT are reported under the name
+	/// of the fused node.
+	///
+	/// **This ends profiling** (see [`Session::end_profiling`]); subsequent runs will not be profiled.
+	///
+	/// ```no_run
+	/// # use ort::{execution_providers::CUDAExecutionProvider, session::Session, value::TensorRef};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?
+	/// 	.with_execution_providers([CUDAExecutionProvider::default().build()])?
+	/// 	.with_profiling("profile")?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// session.run(ort::inputs![TensorRef::from_array_view(&input)?])?;
+	///
+	/// for (node, provider) in session.node_placements()? {
+	/// 	println!("{node}: {provider}");
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[cfg(feature = "std")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+	pub fn node_placements(&mut self) -> Result<Vec<(String, String)>> {
+		let profile_path = self.end_profiling()?;
+		let profile = std::fs::read_to_string(&profile_path)
+			.map_err(|e| Error::new(format!("Failed to read profiling file `{profile_path}`; was profiling enabled for this session? {e}")))?;
+		Ok(placement::parse_node_placements(&profile))
+	}
+
+	/// Returns a summary of the nodes in this session's optimized graph, including their operator types & how they are
+	/// connected. This is useful to see what ONNX Runtime's graph optimizations did to a model, e.g. which nodes were
+	/// fused or removed.
+	///
+	/// ONNX Runtime does not expose the loaded graph directly, so it is read from the optimized model written by
+	/// ONNX Runtime when the session was created. The session must have been created with
+	/// [`SessionBuilder::with_optimized_model_path`], and the model must not have been saved in the ORT format.
+	///
+	/// ```no_run
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_optimized_model_path("upsample.optimized.onnx")?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	///
+	/// let graph = session.graph_summary()?;
+	/// println!("{graph}");
+	/// for (op_type, count) in graph.op_type_counts() {
+	/// 	println!("{op_type}: {count}");
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if the session was not created with an optimized model
+	/// path, or if the optimized model could not be parsed.
+	#[cfg(feature = "std")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+	pub fn graph_summary(&self) -> Result<GraphSummary> {
+		let Some(path) = self.optimized_model_path.as_ref() else {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				"Session was not created with an optimized model path; use `SessionBuilder::with_optimized_model_path`"
+			));
+		};
+		let model = std::fs::read(path).map_err(|e| Error::new(format!("Failed to read optimized model `{}`: {e}", path.display())))?;
+		GraphSummary::from_model(&model)
+	}
+
 	/// Sets this session's [workload type][`WorkloadType`] to instruct execution providers to prioritize performance or
 	/// efficiency.
 	///
@@ -540,6 +1307,48 @@ impl Session {
 		}
 	}
 
+	/// Updates execution provider options on an already-created session, without having to rebuild it. Each option
+	/// is given as a `(key, value)` pair, and is forwarded to every execution provider registered with the session.
+	///
+	/// Support for dynamic options is EP-specific; execution providers ignore keys they don't recognize. As of ONNX
+	/// Runtime 1.21, the supported keys are:
+	/// - `ep.dynamic.workload_type` (`Default` or `Efficient`) - supported by the QNN EP; see
+	///   [`Session::set_workload_type`].
+	/// - `ep.dynamic.qnn_htp_performance_mode` - supported by the QNN EP; accepts the same values as
+	///   [`QNNPerformanceMode::as_str`](crate::execution_providers::qnn::QNNPerformanceMode::as_str).
+	///
+	/// Other EPs, including CUDA & TensorRT, do not currently support changing any options after session creation.
+	///
+	/// ```no_run
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("model.onnx")?;
+	/// session.set_ep_dynamic_options(&[("ep.dynamic.qnn_htp_performance_mode", "burst")])?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if any key or value is empty, or if an execution provider rejects an option.
+	pub fn set_ep_dynamic_options(&mut self, options: &[(&str, &str)]) -> Result<()> {
+		if let Some((key, value)) = options.iter().find(|(key, value)| key.is_empty() || value.is_empty()) {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Dynamic EP option keys & values must not be empty (got key `{key}` with value `{value}`)")
+			));
+		}
+
+		let keys: SmallVec<&str, 4> = options.iter().map(|(key, _)| *key).collect();
+		let values: SmallVec<&str, 4> = options.iter().map(|(_, value)| *value).collect();
+		let session_ptr = self.inner.session_ptr.as_ptr();
+		with_cstr_ptr_array(&keys, &|keys| {
+			with_cstr_ptr_array(&values, &|values| {
+				ortsys![unsafe SetEpDynamicOptions(session_ptr, keys.as_ptr(), values.as_ptr(), keys.len())?];
+				Ok(())
+			})
+		})
+	}
+
 	pub(crate) fn set_dynamic_option(&mut self, key: *const c_char, value: *const c_char) -> Result<()> {
 		ortsys![unsafe SetEpDynamicOptions(self.inner.session_ptr.as_ptr(), &key, &value, 1)?];
 		Ok(())
@@ -558,6 +1367,115 @@ pub enum WorkloadType {
 	Efficient
 }
 
+fn format_declared_shape(shape: &[i64], dimension_symbols: &[String]) -> String {
+	let dims: Vec<String> = shape
+		.iter()
+		.zip(dimension_symbols.iter())
+		.map(|(dim, symbol)| match *dim {
+			dim if dim >= 0 => dim.to_string(),
+			_ if !symbol.is_empty() => symbol.clone(),
+			_ => "?".to_string()
+		})
+		.collect();
+	format!("[{}]", dims.join(", "))
+}
+
+fn check_signature<'d>(
+	kind: &str,
+	expected: &[(&str, TensorElementType, &[Option<i64>])],
+	declared: impl Iterator<Item = (&'d str, &'d ValueType)> + Clone
+) -> Result<()> {
+	for (name, expected_ty, expected_shape) in expected {
+		let Some((_, value_type)) = declared.clone().find(|(declared_name, _)| declared_name == name) else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Model has no {kind} named `{name}`")));
+		};
+		let ValueType::Tensor { ty, shape, dimension_symbols } = value_type else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Expected {kind} `{name}` to be a tensor, but it is {value_type}")));
+		};
+
+		let shape_matches = shape.len() == expected_shape.len()
+			&& shape
+				.iter()
+				.zip(expected_shape.iter())
+				.all(|(dim, expected)| expected.map_or(true, |expected| *dim == expected));
+		if ty != expected_ty || !shape_matches {
+			let expected_dims: Vec<String> = expected_shape
+				.iter()
+				.map(|dim| dim.map_or_else(|| "?".to_string(), |dim| dim.to_string()))
+				.collect();
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!(
+					"Expected {kind} `{name}` to be Tensor<{expected_ty}> with shape [{}], but the model declares Tensor<{ty}> with shape {}",
+					expected_dims.join(", "),
+					format_declared_shape(shape, dimension_symbols)
+				)
+			));
+		}
+	}
+	Ok(())
+}
+
+/// Hashes the names & types of `inputs` and `outputs` with 64-bit FNV-1a, which, unlike the standard library's
+/// `DefaultHasher`, is guaranteed to produce the same hash across Rust versions.
+fn signature_hash(inputs: &[Input], outputs: &[Output]) -> u64 {
+	struct Fnv1a(u64);
+	impl Fnv1a {
+		fn write(&mut self, bytes: &[u8]) {
+			for byte in bytes {
+				self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+			}
+		}
+
+		fn write_len(&mut self, len: usize) {
+			self.write(&(len as u64).to_le_bytes());
+		}
+
+		fn write_str(&mut self, s: &str) {
+			self.write_len(s.len());
+			self.write(s.as_bytes());
+		}
+
+		fn write_value_type(&mut self, ty: &ValueType) {
+			let element_type = |ty: TensorElementType| ort_sys::ONNXTensorElementDataType::from(ty) as u8;
+			match ty {
+				ValueType::Tensor { ty, shape, dimension_symbols } => {
+					self.write(&[0, element_type(*ty)]);
+					self.write_len(shape.len());
+					for dim in shape.iter() {
+						self.write(&dim.to_le_bytes());
+					}
+					for symbol in dimension_symbols.iter() {
+						self.write_str(symbol);
+					}
+				}
+				ValueType::Sequence(ty) => {
+					self.write(&[1]);
+					self.write_value_type(ty);
+				}
+				ValueType::Map { key, value } => self.write(&[2, element_type(*key), element_type(*value)]),
+				ValueType::Optional(ty) => {
+					self.write(&[3]);
+					self.write_value_type(ty);
+				}
+			}
+		}
+	}
+
+	let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+	hasher.write_len(inputs.len());
+	for input in inputs {
+		hasher.write_str(&input.name);
+		hasher.write_value_type(&input.input_type);
+	}
+	hasher.write_len(outputs.len());
+	for output in outputs {
+		hasher.write_str(&output.name);
+		hasher.write_value_type(&output.output_type);
+	}
+	hasher.0
+}
+
 // https://github.com/microsoft/onnxruntime/issues/114
 unsafe impl Send for Session {}
 // Allowing `Sync` segfaults with CUDA, DirectML, and seemingly any EP other than the CPU EP. I'm not certain if it's a
@@ -621,6 +1539,12 @@ mod dangerous {
 		extract_io_name(f, session_ptr, allocator, i)
 	}
 
+	pub(super) fn extract_overridable_initializer_names(session_ptr: NonNull<ort_sys::OrtSession>, allocator: &Allocator) -> Result<Vec<String>> {
+		let count = extract_io_count(ortsys![SessionGetOverridableInitializerCount], session_ptr)?;
+		let f = ortsys![SessionGetOverridableInitializerName];
+		(0..count).map(|i| extract_io_name(f, session_ptr, allocator, i)).collect()
+	}
+
 	pub(crate) fn raw_pointer_to_string(allocator: &Allocator, c_str: *mut c_char) -> Result<String> {
 		let name = match char_p_to_string(c_str) {
 			Ok(name) => name,
@@ -682,3 +1606,39 @@ mod dangerous {
 		Ok(ValueType::from_type_info(typeinfo_ptr))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{Input, Output, signature_hash};
+	use crate::{
+		tensor::{Shape, SymbolicDimensions, TensorElementType},
+		value::ValueType
+	};
+
+	fn tensor(ty: TensorElementType, symbol: &str) -> ValueType {
+		ValueType::Tensor {
+			ty,
+			shape: Shape::new([-1, 3]),
+			dimension_symbols: SymbolicDimensions::new([symbol.to_string(), String::new()])
+		}
+	}
+
+	#[test]
+	fn test_signature_hash() {
+		let hash = |input_type: ValueType, output_name: &str| {
+			let inputs = [Input { name: "x".to_string(), input_type }];
+			let outputs = [Output {
+				name: output_name.to_string(),
+				output_type: tensor(TensorElementType::Int64, "")
+			}];
+			signature_hash(&inputs, &outputs)
+		};
+
+		let base = hash(tensor(TensorElementType::Float32, "batch"), "y");
+		assert_eq!(base, hash(tensor(TensorElementType::Float32, "batch"), "y"));
+		assert_ne!(base, hash(tensor(TensorElementType::Float16, "batch"), "y"));
+		assert_ne!(base, hash(tensor(TensorElementType::Float32, "n"), "y"));
+		assert_ne!(base, hash(tensor(TensorElementType::Float32, "batch"), "z"));
+		assert_ne!(base, hash(ValueType::Sequence(Box::new(tensor(TensorElementType::Float32, "batch"))), "y"));
+	}
+}
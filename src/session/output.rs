@@ -113,6 +113,25 @@ impl<'r, 's> SessionOutputs<'r, 's> {
 		None
 	}
 
+	/// Removes the output named `key`, returning ownership of its value, or `None` if there is no such output (or it
+	/// was already removed).
+	///
+	/// The value is moved out rather than copied, so it can be kept after the rest of the outputs are dropped without
+	/// cloning its data.
+	///
+	/// ```
+	/// # use ort::{value::TensorRef, session::Session};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let mut outputs = session.run(ort::inputs![TensorRef::from_array_view(&input)?])?;
+	///
+	/// let output = outputs.remove("Identity:0").expect("model has an output named `Identity:0`");
+	/// assert!(!outputs.contains_key("Identity:0"));
+	/// assert!(outputs.remove("Identity:0").is_none());
+	/// # 	Ok(())
+	/// # }
+	/// ```
 	pub fn remove(&mut self, key: impl AsRef<str>) -> Option<DynValue> {
 		let key = key.as_ref();
 		assert!(!key.is_empty(), "output name cannot be empty");
@@ -126,6 +145,23 @@ impl<'r, 's> SessionOutputs<'r, 's> {
 		None
 	}
 
+	/// Consumes the outputs, returning ownership of only the value of the output named `key` and dropping the rest.
+	/// Returns `None` if there is no such output.
+	///
+	/// ```
+	/// # use ort::{value::TensorRef, session::Session};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let output = session.run(ort::inputs![TensorRef::from_array_view(&input)?])?.take("Identity:0");
+	/// assert!(output.is_some());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn take(mut self, key: impl AsRef<str>) -> Option<DynValue> {
+		self.remove(key)
+	}
+
 	#[inline(always)]
 	#[allow(clippy::len_without_is_empty)]
 	pub fn len(&self) -> usize {
@@ -0,0 +1,276 @@
+use alloc::{string::String, sync::Arc, vec::Vec};
+
+use crate::{
+	tensor::{Shape, TensorElementType},
+	value::{DynValue, ValueType}
+};
+
+/// A pool of output buffers which [`Session::run_pooled`] recycles across runs, so that once a server reaches a steady
+/// state, ONNX Runtime writes outputs into existing tensors instead of allocating new ones for each request.
+///
+/// Each tensor output returned by [`Session::run_pooled`] is kept in the pool, keyed by the output's name & shape. A
+/// pooled tensor is only reused once the pool holds the last handle to it, i.e. once the [`SessionOutputs`] it was
+/// returned in, any value removed from them, and any view of those values have all been dropped. Until then, runs
+/// allocate new buffers instead.
+///
+/// Before each run, a free buffer is bound to an output only if it is known to fit: its element type must match the
+/// output's, as must every dimension the model declares as fixed. If the output also has dynamic dimensions (e.g. a
+/// batch dimension), the buffer must additionally have been produced by a run whose inputs had the same names &
+/// shapes, since the output's shape usually follows from the input shapes.
+///
+/// ```
+/// # use ort::{session::{OutputPool, Session}, value::TensorRef};
+/// # fn main() -> ort::Result<()> {
+/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+/// let mut pool = OutputPool::new();
+///
+/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+/// for _ in 0..4 {
+/// 	let outputs = session.run_pooled(ort::inputs![TensorRef::from_array_view(&input)?], &mut pool)?;
+/// 	// ... use outputs, then drop them so their buffers can be reused
+/// }
+/// // the first run allocates; the others reuse its outputs
+/// assert_eq!(pool.hits(), 3);
+/// # 	Ok(())
+/// # }
+/// ```
+///
+/// [`Session::run_pooled`]: super::Session::run_pooled
+/// [`SessionOutputs`]: super::SessionOutputs
+#[derive(Debug)]
+pub struct OutputPool {
+	/// Buffers ordered from least to most recently used.
+	buffers: Vec<PooledBuffer>,
+	max_buffers_per_output: usize,
+	hits: u64,
+	misses: u64
+}
+
+#[derive(Debug)]
+struct PooledBuffer {
+	output: String,
+	value: DynValue,
+	/// The names & shapes of the inputs of the run that produced this buffer.
+	inputs: Vec<(String, Shape)>
+}
+
+impl PooledBuffer {
+	fn is_free(&self) -> bool {
+		Arc::strong_count(&self.value.inner) == 1 && Arc::weak_count(&self.value.inner) == 0
+	}
+
+	fn has_inputs(&self, inputs: &[(&str, &Shape)]) -> bool {
+		self.inputs.len() == inputs.len()
+			&& self
+				.inputs
+				.iter()
+				.zip(inputs)
+				.all(|((name, shape), (other_name, other_shape))| name == other_name && shape == *other_shape)
+	}
+}
+
+impl Default for OutputPool {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl OutputPool {
+	/// Creates an empty pool which keeps up to 2 buffers per output.
+	pub fn new() -> Self {
+		Self {
+			buffers: Vec::new(),
+			max_buffers_per_output: 2,
+			hits: 0,
+			misses: 0
+		}
+	}
+
+	/// Configures the maximum number of buffers kept for each output; the least recently used buffers are released
+	/// once this is exceeded. Keeping more than one buffer per output allows reuse even when the outputs of the
+	/// previous run are still alive, e.g. in a pipeline which processes one request while the next is running.
+	pub fn with_max_buffers_per_output(mut self, max: usize) -> Self {
+		self.max_buffers_per_output = max;
+		self
+	}
+
+	/// Returns the number of outputs which were written into a recycled buffer.
+	pub fn hits(&self) -> u64 {
+		self.hits
+	}
+
+	/// Returns the number of outputs for which no buffer could be recycled, and ONNX Runtime allocated a new one.
+	pub fn misses(&self) -> u64 {
+		self.misses
+	}
+
+	/// Returns the fraction of outputs which were written into a recycled buffer, from `0.0` to `1.0`, or `0.0` if
+	/// the pool has not been used yet.
+	pub fn hit_rate(&self) -> f64 {
+		let total = self.hits + self.misses;
+		if total == 0 { 0.0 } else { self.hits as f64 / total as f64 }
+	}
+
+	/// Returns the number of buffers in the pool, including those still in use.
+	pub fn len(&self) -> usize {
+		self.buffers.len()
+	}
+
+	/// Returns `true` if the pool holds no buffers.
+	pub fn is_empty(&self) -> bool {
+		self.buffers.is_empty()
+	}
+
+	/// Releases all buffers held by the pool & resets its statistics.
+	pub fn clear(&mut self) {
+		self.buffers.clear();
+		self.hits = 0;
+		self.misses = 0;
+	}
+
+	/// Takes the most recently used free buffer which fits the output `name` of type `output_type` in a run with the
+	/// given `inputs`, if there is one. `inputs` is `None` if the run's input shapes are unknown, e.g. because an input
+	/// is not a tensor.
+	pub(crate) fn acquire(&mut self, name: &str, output_type: &ValueType, inputs: Option<&[(&str, &Shape)]>) -> Option<DynValue> {
+		let ValueType::Tensor { ty, shape: declared, .. } = output_type else {
+			return None;
+		};
+		let is_static = declared.iter().all(|&dim| dim >= 0);
+		let index = self.buffers.iter().rposition(|buffer| {
+			buffer.output == name
+				&& buffer.is_free()
+				&& fits(&buffer.value, *ty, declared)
+				&& (is_static || inputs.is_some_and(|inputs| buffer.has_inputs(inputs)))
+		})?;
+		let buffer = self.buffers.remove(index);
+		let value = DynValue::clone_of(&buffer.value);
+		self.buffers.push(buffer);
+		Some(value)
+	}
+
+	/// Adds the output `value` of a run with the given `inputs` to the pool, evicting the least recently used buffers
+	/// for `name` if there are too many.
+	pub(crate) fn release(&mut self, name: &str, value: &DynValue, inputs: Option<&[(&str, &Shape)]>) {
+		if tensor_shape(value).is_none() {
+			return;
+		}
+		// The value is already pooled if it was written into a recycled buffer; `acquire` moved it to the back.
+		if !self.buffers.iter().any(|buffer| Arc::ptr_eq(&buffer.value.inner, &value.inner)) {
+			self.buffers.push(PooledBuffer {
+				output: String::from(name),
+				value: DynValue::clone_of(value),
+				// Without known input shapes, this buffer can only be reused for outputs with a fully static shape.
+				inputs: inputs
+					.map(|inputs| inputs.iter().map(|(name, shape)| (String::from(*name), (*shape).clone())).collect())
+					.unwrap_or_default()
+			});
+		}
+		let mut excess = self
+			.buffers
+			.iter()
+			.filter(|buffer| buffer.output == name)
+			.count()
+			.saturating_sub(self.max_buffers_per_output);
+		self.buffers.retain(|buffer| {
+			if excess > 0 && buffer.output == name {
+				excess -= 1;
+				return false;
+			}
+			true
+		});
+	}
+
+	/// Removes `value` from the pool, e.g. because ONNX Runtime rejected it as an output buffer.
+	pub(crate) fn discard(&mut self, value: &DynValue) {
+		self.buffers.retain(|buffer| !Arc::ptr_eq(&buffer.value.inner, &value.inner));
+	}
+
+	pub(crate) fn record(&mut self, hits: usize, misses: usize) {
+		self.hits += hits as u64;
+		self.misses += misses as u64;
+	}
+}
+
+fn tensor_shape(value: &DynValue) -> Option<&Shape> {
+	match value.dtype() {
+		ValueType::Tensor { shape, .. } => Some(shape),
+		_ => None
+	}
+}
+
+/// Returns `true` if `value` is a tensor of type `ty` whose shape matches every fixed dimension of `declared`.
+fn fits(value: &DynValue, ty: TensorElementType, declared: &Shape) -> bool {
+	match value.dtype() {
+		ValueType::Tensor { ty: value_ty, shape, .. } => {
+			*value_ty == ty && shape.len() == declared.len() && shape.iter().zip(declared.iter()).all(|(&dim, &declared)| declared < 0 || dim == declared)
+		}
+		_ => false
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::OutputPool;
+	use crate::{
+		tensor::{Shape, SymbolicDimensions, TensorElementType},
+		value::{DynValue, Tensor, ValueType}
+	};
+
+	fn output_type(shape: impl Into<Shape>) -> ValueType {
+		let shape = shape.into();
+		ValueType::Tensor {
+			ty: TensorElementType::Float32,
+			dimension_symbols: SymbolicDimensions::empty(shape.len()),
+			shape
+		}
+	}
+
+	#[test]
+	fn test_output_pool() -> crate::Result<()> {
+		let dynamic = output_type([-1_i64]);
+		let x = Shape::new([2]);
+		let inputs = [("x", &x)];
+
+		let mut pool = OutputPool::new().with_max_buffers_per_output(1);
+		assert!(pool.acquire("y", &dynamic, Some(&inputs)).is_none());
+
+		let output = Tensor::from_array(([2usize], vec![1.0_f32, 2.0]))?.into_dyn();
+		pool.release("y", &output, Some(&inputs));
+		assert_eq!(pool.len(), 1);
+		// still in use by `output`, or by another handle to it, e.g. one removed from `SessionOutputs`
+		assert!(pool.acquire("y", &dynamic, Some(&inputs)).is_none());
+		let removed = DynValue::clone_of(&output);
+		drop(output);
+		assert!(pool.acquire("y", &dynamic, Some(&inputs)).is_none());
+		drop(removed);
+
+		// the output's shape can't be predicted if the inputs changed shape or aren't known
+		let other_x = Shape::new([3]);
+		assert!(pool.acquire("y", &dynamic, Some(&[("x", &other_x)])).is_none());
+		assert!(pool.acquire("y", &dynamic, None).is_none());
+		// ...and the buffer must match the type & fixed dimensions of the output
+		assert!(pool.acquire("y", &output_type([3_i64]), None).is_none());
+		assert!(pool.acquire("z", &dynamic, Some(&inputs)).is_none());
+
+		let buffer = pool.acquire("y", &dynamic, Some(&inputs)).expect("buffer should be free");
+		pool.release("y", &buffer, Some(&inputs));
+		assert_eq!(pool.len(), 1);
+		drop(buffer);
+		// outputs with a static shape can reuse buffers regardless of the inputs
+		let buffer = pool.acquire("y", &output_type([2_i64]), None).expect("buffer should fit");
+		pool.discard(&buffer);
+		assert!(pool.is_empty());
+
+		let output = Tensor::from_array(([3usize], vec![1.0_f32, 2.0, 3.0]))?.into_dyn();
+		pool.release("y", &buffer, Some(&inputs));
+		pool.release("y", &output, Some(&[("x", &other_x)]));
+		// the old buffer is evicted in favor of the latest one
+		assert_eq!(pool.len(), 1);
+
+		pool.record(3, 1);
+		assert_eq!(pool.hit_rate(), 0.75);
+		pool.clear();
+		assert!(pool.is_empty());
+		Ok(())
+	}
+}
@@ -0,0 +1,106 @@
+//! Extracts node placements from ONNX Runtime's profiling output. See [`Session::node_placements`].
+//!
+//! [`Session::node_placements`]: super::Session::node_placements
+
+use alloc::{string::String, vec::Vec};
+
+/// Suffix ONNX Runtime appends to a node's name for the event recording the node's kernel execution.
+const KERNEL_TIME_SUFFIX: &str = "_kernel_time";
+
+/// Parses a profiling file written by ONNX Runtime, returning `(node name, execution provider)` pairs in order of
+/// first execution.
+///
+/// ONNX Runtime writes each event on its own line as a JSON object; node kernel events have the category `Node`, are
+/// named `<node name>_kernel_time`, and record the execution provider which ran them in `args.provider`.
+pub(crate) fn parse_node_placements(profile: &str) -> Vec<(String, String)> {
+	let mut placements: Vec<(String, String)> = Vec::new();
+	for line in profile.lines() {
+		if string_field(line, "cat").as_deref() != Some("Node") {
+			continue;
+		}
+		let (Some(name), Some(provider)) = (string_field(line, "name"), string_field(line, "provider")) else {
+			continue;
+		};
+		let Some(node) = name.strip_suffix(KERNEL_TIME_SUFFIX) else {
+			continue;
+		};
+		if !placements.iter().any(|(n, _)| n == node) {
+			placements.push((String::from(node), provider));
+		}
+	}
+	placements
+}
+
+/// Finds the first `"key": "value"` pair in `event` and returns the unescaped value.
+fn string_field(event: &str, key: &str) -> Option<String> {
+	let mut rest = event;
+	loop {
+		let start = rest.find('"')?;
+		let (token, after) = read_string(&rest[start + 1..])?;
+		let after = after.trim_start();
+		match after.strip_prefix(':') {
+			Some(value) if token == key => {
+				let value = value.trim_start().strip_prefix('"')?;
+				return read_string(value).map(|(value, _)| value);
+			}
+			// Skip over the value of a non-matching key so it isn't mistaken for a key itself.
+			Some(value) => match value.trim_start().strip_prefix('"') {
+				Some(value) => rest = read_string(value)?.1,
+				None => rest = value
+			},
+			None => rest = after
+		}
+	}
+}
+
+/// Reads a JSON string up to its closing quote (the opening quote must already be consumed), returning the unescaped
+/// string and the remaining input.
+fn read_string(input: &str) -> Option<(String, &str)> {
+	let mut out = String::new();
+	let mut chars = input.char_indices();
+	while let Some((i, c)) = chars.next() {
+		match c {
+			'"' => return Some((out, &input[i + 1..])),
+			'\\' => match chars.next()?.1 {
+				'n' => out.push('\n'),
+				't' => out.push('\t'),
+				'r' => out.push('\r'),
+				'u' => {
+					let hex: String = (0..4).filter_map(|_| chars.next().map(|(_, c)| c)).collect();
+					out.push(
+						u32::from_str_radix(&hex, 16)
+							.ok()
+							.and_then(char::from_u32)
+							.unwrap_or(char::REPLACEMENT_CHARACTER)
+					);
+				}
+				c => out.push(c)
+			},
+			c => out.push(c)
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::parse_node_placements;
+
+	#[test]
+	fn test_parse_node_placements() {
+		let profile = r#"[
+{"cat" : "Session","pid" :1,"tid" :1,"dur" :120,"ts" :4,"ph" : "X","name" :"model_loading_uri","args" : {}},
+{"cat" : "Node","pid" :1,"tid" :1,"dur" :0,"ts" :10,"ph" : "X","name" :"conv_1_fence_before","args" : {"op_name" : "Conv"}},
+{"cat" : "Node","pid" :1,"tid" :1,"dur" :32,"ts" :11,"ph" : "X","name" :"conv_1_kernel_time","args" : {"op_name" : "Conv","provider" : "CUDAExecutionProvider"}},
+{"cat" : "Node","pid" :1,"tid" :1,"dur" :5,"ts" :44,"ph" : "X","name" :"resize \"a\"_kernel_time","args" : {"op_name" : "Resize","provider" : "CPUExecutionProvider"}},
+{"cat" : "Node","pid" :1,"tid" :1,"dur" :30,"ts" :90,"ph" : "X","name" :"conv_1_kernel_time","args" : {"op_name" : "Conv","provider" : "CUDAExecutionProvider"}}
+]"#;
+		assert_eq!(
+			parse_node_placements(profile),
+			vec![
+				("conv_1".to_string(), "CUDAExecutionProvider".to_string()),
+				("resize \"a\"".to_string(), "CPUExecutionProvider".to_string())
+			]
+		);
+	}
+}
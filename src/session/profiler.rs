@@ -0,0 +1,200 @@
+use alloc::{format, string::String};
+use core::{
+	ops::{Deref, DerefMut},
+	sync::atomic::{AtomicU64, Ordering},
+	time::Duration
+};
+
+use super::Session;
+use crate::error::{Error, ErrorCode, Result};
+
+/// A guard which ends profiling of a [`Session`] when dropped, created by [`Session::profile`].
+///
+/// Profiling output is only written once profiling is ended; the guard ensures this happens even if it is never
+/// explicitly [finished](Profiler::finish), e.g. if an error is propagated with `?` while profiling. Since it
+/// mutably borrows the session, the guard cannot outlive it.
+///
+/// `Profiler` dereferences to the profiled [`Session`], so the session can be run through it as usual.
+#[derive(Debug)]
+pub struct Profiler<'s> {
+	session: &'s mut Session,
+	finished: bool
+}
+
+impl<'s> Profiler<'s> {
+	pub(crate) fn new(session: &'s mut Session) -> Self {
+		Self { session, finished: false }
+	}
+
+	/// Ends profiling, returning the path of the profiling file. See [`Session::end_profiling`].
+	///
+	/// Unlike dropping the guard, this reports any error which occurs while writing the profiling file.
+	pub fn finish(mut self) -> Result<String> {
+		self.finished = true;
+		self.session.end_profiling()
+	}
+}
+
+impl Deref for Profiler<'_> {
+	type Target = Session;
+	fn deref(&self) -> &Self::Target {
+		self.session
+	}
+}
+impl DerefMut for Profiler<'_> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.session
+	}
+}
+
+impl Drop for Profiler<'_> {
+	fn drop(&mut self) {
+		if !self.finished {
+			if let Err(e) = self.session.end_profiling() {
+				crate::warn!("Failed to end profiling: {e}");
+				let _ = e;
+			}
+		}
+	}
+}
+
+/// Selects a fraction of a session's runs to be sampled, and aggregates the latency of the sampled runs; used with
+/// [`Session::run_sampled`].
+///
+/// ONNX Runtime's profiler is scoped to a whole session: it can only be enabled when the session is created, records
+/// every run until profiling ends, and cannot be enabled again afterwards. Profiling only some runs of one session is
+/// therefore not possible. The sampler instead provides two cheaper forms of observability:
+/// - [`ProfilingSampler::stats`] aggregates the wall-clock latency of the sampled runs, which is enough to catch most
+///   regressions at almost no cost.
+/// - For node-level profiles, create a second session with [`SessionBuilder::with_profiling`] (e.g. with
+///   [`SessionBuilder::commit_from_session_model`]), and route the runs chosen by [`ProfilingSampler::should_sample`]
+///   to it. Its profile then aggregates only the sampled runs, at the cost of loading the model twice.
+///
+/// Runs are sampled deterministically rather than randomly: with a rate of `0.01`, every 100th run is sampled. The
+/// sampler can be shared between threads.
+///
+/// ```no_run
+/// # use ort::{session::{ProfilingSampler, Session}, value::TensorRef};
+/// # fn main() -> ort::Result<()> {
+/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+/// let mut profiled_session = Session::builder()?.with_profiling("sampled")?.commit_from_session_model(&session)?;
+///
+/// let sampler = ProfilingSampler::new(0.01)?;
+/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+/// for _ in 0..1000 {
+/// 	let session = if sampler.should_sample() { &mut profiled_session } else { &mut session };
+/// 	let outputs = session.run(ort::inputs![TensorRef::from_array_view(&input)?])?;
+/// }
+/// let profile_path = profiled_session.end_profiling()?;
+/// # 	Ok(())
+/// # }
+/// ```
+///
+/// [`SessionBuilder::with_profiling`]: crate::session::builder::SessionBuilder::with_profiling
+/// [`SessionBuilder::commit_from_session_model`]: crate::session::builder::SessionBuilder::commit_from_session_model
+#[derive(Debug)]
+pub struct ProfilingSampler {
+	rate: f64,
+	runs: AtomicU64,
+	samples: AtomicU64,
+	total_nanos: AtomicU64,
+	min_nanos: AtomicU64,
+	max_nanos: AtomicU64
+}
+
+impl ProfilingSampler {
+	/// Creates a sampler which samples the given fraction of runs, from `0.0` (none) to `1.0` (all).
+	///
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if `rate` is not in this range.
+	pub fn new(rate: f64) -> Result<Self> {
+		if !(0.0..=1.0).contains(&rate) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Sampling rate must be between 0 and 1, got {rate}")));
+		}
+		Ok(Self {
+			rate,
+			runs: AtomicU64::new(0),
+			samples: AtomicU64::new(0),
+			total_nanos: AtomicU64::new(0),
+			min_nanos: AtomicU64::new(u64::MAX),
+			max_nanos: AtomicU64::new(0)
+		})
+	}
+
+	/// Returns the fraction of runs this sampler samples.
+	pub fn rate(&self) -> f64 {
+		self.rate
+	}
+
+	/// Counts a run, returning `true` if it should be sampled.
+	pub fn should_sample(&self) -> bool {
+		let run = self.runs.fetch_add(1, Ordering::Relaxed);
+		// sample a run whenever the expected number of samples crosses an integer
+		(((run + 1) as f64) * self.rate).floor() > ((run as f64) * self.rate).floor()
+	}
+
+	/// Records the latency of a sampled run. [`Session::run_sampled`] calls this automatically.
+	pub fn record(&self, elapsed: Duration) {
+		let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+		self.samples.fetch_add(1, Ordering::Relaxed);
+		self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+		self.min_nanos.fetch_min(nanos, Ordering::Relaxed);
+		self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+	}
+
+	/// Returns the number of runs & samples so far, and latency statistics of the sampled runs.
+	pub fn stats(&self) -> SampledRunStats {
+		let samples = self.samples.load(Ordering::Relaxed);
+		let min_nanos = if samples == 0 { 0 } else { self.min_nanos.load(Ordering::Relaxed) };
+		SampledRunStats {
+			runs: self.runs.load(Ordering::Relaxed),
+			samples,
+			mean: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed).checked_div(samples).unwrap_or(0)),
+			min: Duration::from_nanos(min_nanos),
+			max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed))
+		}
+	}
+}
+
+/// Statistics of the runs sampled by a [`ProfilingSampler`], returned by [`ProfilingSampler::stats`]. All durations
+/// are zero if no runs were sampled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampledRunStats {
+	/// The number of runs counted by the sampler.
+	pub runs: u64,
+	/// The number of runs which were sampled.
+	pub samples: u64,
+	/// The mean latency of the sampled runs.
+	pub mean: Duration,
+	/// The lowest latency of the sampled runs.
+	pub min: Duration,
+	/// The highest latency of the sampled runs.
+	pub max: Duration
+}
+
+#[cfg(test)]
+mod tests {
+	use core::time::Duration;
+
+	use super::ProfilingSampler;
+
+	#[test]
+	fn test_profiling_sampler() -> crate::Result<()> {
+		let sampler = ProfilingSampler::new(0.25)?;
+		let sampled = (0..100).filter(|_| sampler.should_sample()).count();
+		assert_eq!(sampled, 25);
+
+		sampler.record(Duration::from_millis(2));
+		sampler.record(Duration::from_millis(4));
+		let stats = sampler.stats();
+		assert_eq!((stats.runs, stats.samples), (100, 2));
+		assert_eq!(stats.mean, Duration::from_millis(3));
+		assert_eq!(stats.min, Duration::from_millis(2));
+		assert_eq!(stats.max, Duration::from_millis(4));
+
+		assert!(!ProfilingSampler::new(0.0)?.should_sample());
+		assert!(ProfilingSampler::new(1.0)?.should_sample());
+		assert!(ProfilingSampler::new(1.5).is_err());
+		assert!(ProfilingSampler::new(f64::NAN).is_err());
+		Ok(())
+	}
+}
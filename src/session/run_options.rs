@@ -1,4 +1,4 @@
-use alloc::{string::String, sync::Arc, vec::Vec};
+use alloc::{format, string::String, sync::Arc, vec::Vec};
 use core::{
 	ffi::{CStr, c_char},
 	marker::PhantomData,
@@ -11,7 +11,7 @@ use smallvec::SmallVec;
 use crate::{
 	AsPointer,
 	adapter::{Adapter, AdapterInner},
-	error::Result,
+	error::{Error, ErrorCode, Result},
 	ortsys,
 	session::Output,
 	util::{MiniMap, STACK_SESSION_OUTPUTS, with_cstr},
@@ -121,16 +121,41 @@ impl OutputSelector {
 		self
 	}
 
+	/// Resolves the names of the outputs to compute, along with their preallocated values (if any).
+	///
+	/// Returns an error if any output named by this selector is not an output of the model, or if no outputs would be
+	/// computed.
 	pub(crate) fn resolve_outputs<'a, 's: 'a>(
 		&'a self,
 		outputs: &'s [Output]
-	) -> (SmallVec<&'a str, { STACK_SESSION_OUTPUTS }>, SmallVec<Option<DynValue>, { STACK_SESSION_OUTPUTS }>) {
-		if self.use_defaults { outputs.iter() } else { [].iter() }
-			.map(|o| &o.name)
-			.filter(|n| !self.default_blocklist.contains(n))
-			.chain(self.allowlist.iter())
-			.map(|n| (n.as_str(), self.preallocated_outputs.get(n).map(DynValue::clone_of)))
-			.unzip()
+	) -> Result<(SmallVec<&'a str, { STACK_SESSION_OUTPUTS }>, SmallVec<Option<DynValue>, { STACK_SESSION_OUTPUTS }>)> {
+		if let Some(name) = self
+			.allowlist
+			.iter()
+			.chain(self.default_blocklist.iter())
+			.chain(self.preallocated_outputs.iter().map(|(name, _)| name))
+			.find(|name| !outputs.iter().any(|o| &o.name == *name))
+		{
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!(
+					"Output `{name}` was selected, but is not an output of this model; available outputs are: {}",
+					outputs.iter().map(|o| format!("`{}`", o.name)).collect::<Vec<_>>().join(", ")
+				)
+			));
+		}
+
+		let resolved: (SmallVec<&'a str, { STACK_SESSION_OUTPUTS }>, SmallVec<Option<DynValue>, { STACK_SESSION_OUTPUTS }>) =
+			if self.use_defaults { outputs.iter() } else { [].iter() }
+				.map(|o| &o.name)
+				.filter(|n| !self.default_blocklist.contains(n))
+				.chain(self.allowlist.iter())
+				.map(|n| (n.as_str(), self.preallocated_outputs.get(n).map(DynValue::clone_of)))
+				.unzip();
+		if resolved.0.is_empty() {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "No outputs were selected to be computed by this run"));
+		}
+		Ok(resolved)
 	}
 }
 
@@ -147,6 +172,7 @@ impl SelectedOutputMarker for HasSelectedOutputs {}
 pub(crate) struct UntypedRunOptions {
 	pub(crate) ptr: NonNull<ort_sys::OrtRunOptions>,
 	pub(crate) outputs: OutputSelector,
+	pub(crate) gpu_graph_id: i32,
 	adapters: Vec<Arc<AdapterInner>>
 }
 
@@ -180,6 +206,37 @@ unsafe impl Send for UntypedRunOptions {}
 /// [`OutputSelector`], hence [`RunOptions`] contains an additional type parameter that marks whether or not outputs
 /// have been selected.
 ///
+/// ## Output selection states
+/// - [`RunOptions::new`] creates a `RunOptions<NoSelectedOutputs>`, which computes every output of the model. It can be
+///   used anywhere `RunOptions` are accepted, and is `Sync`, so it can be shared between threads, e.g. to
+///   [terminate](RunOptions::terminate) a run from another thread.
+/// - [`RunOptions::select_outputs`] or [`RunOptions::with_outputs`] consume the options and return a
+///   `RunOptions<HasSelectedOutputs>`. This can only be passed to [`Session::run_with_options`] and
+///   [`Session::run_async`], since [`IoBinding`](crate::io_binding::IoBinding) determines outputs itself. Because the
+///   selection may contain pre-allocated values which are written to by each run, options in this state are not `Sync`.
+///
+/// When running with selected outputs, the selection is validated against the session: the run fails with
+/// [`ErrorCode::InvalidArgument`] if any selected output is not an output of the model, or if the selection would not
+/// compute any outputs. The returned [`SessionOutputs`](crate::session::SessionOutputs) only contain the selected
+/// outputs, and ONNX Runtime may skip parts of the graph that only contribute to outputs which were not selected.
+///
+/// ```
+/// # use ort::{session::{Session, run_options::{RunOptions, HasSelectedOutputs}}, memory::Allocator, value::Tensor};
+/// # fn main() -> ort::Result<()> {
+/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+/// let input = Tensor::<f32>::new(&Allocator::default(), [1_usize, 64, 64, 3])?;
+///
+/// let output0 = session.outputs[0].name.clone();
+/// let options: RunOptions<HasSelectedOutputs> = RunOptions::new()?.with_tag("upsample")?.select_outputs(&[&output0]);
+/// assert_eq!(session.run_with_options(ort::inputs![&input], &options)?.len(), 1);
+///
+/// // Selecting an output the model does not have is an error.
+/// let options = RunOptions::new()?.select_outputs(&["not_an_output"]);
+/// assert!(session.run_with_options(ort::inputs![&input], &options).is_err());
+/// # 	Ok(())
+/// # }
+/// ```
+///
 /// [`Session::run_async`]: crate::session::Session::run_async
 /// [`Session::run_with_options`]: crate::session::Session::run_with_options
 /// [`Session::run_binding_with_options`]: crate::session::Session::run_binding_with_options
@@ -203,6 +260,7 @@ impl RunOptions {
 			inner: UntypedRunOptions {
 				ptr: unsafe { NonNull::new_unchecked(run_options_ptr) },
 				outputs: OutputSelector::default(),
+				gpu_graph_id: 0,
 				adapters: Vec::new()
 			},
 			_marker: PhantomData
@@ -242,6 +300,29 @@ impl<O: SelectedOutputMarker> RunOptions<O> {
 		unsafe { mem::transmute(self) }
 	}
 
+	/// Selects only the outputs named in `names` to be computed by runs with these options; all other outputs are
+	/// disabled. The outputs are returned in the order given in `names`.
+	///
+	/// This is shorthand for [`RunOptions::with_outputs`] with an [`OutputSelector::no_default`] selector. Selected
+	/// names are validated against the session when running; see [the output selection
+	/// states](RunOptions#output-selection-states).
+	///
+	/// ```
+	/// # use ort::{session::{Session, run_options::RunOptions}, memory::Allocator, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = Tensor::<f32>::new(&Allocator::default(), [1_usize, 64, 64, 3])?;
+	///
+	/// let output0 = session.outputs[0].name.clone();
+	/// let options = RunOptions::new()?.select_outputs(&[&output0]);
+	/// let outputs = session.run_with_options(ort::inputs![input], &options)?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn select_outputs(self, names: &[&str]) -> RunOptions<HasSelectedOutputs> {
+		self.with_outputs(names.iter().fold(OutputSelector::no_default(), |selector, name| selector.with(*name)))
+	}
+
 	/// Sets a tag to identify this run in logs.
 	pub fn with_tag(mut self, tag: impl AsRef<str>) -> Result<Self> {
 		self.set_tag(tag).map(|_| self)
@@ -261,6 +342,65 @@ impl<O: SelectedOutputMarker> RunOptions<O> {
 		Ok(unsafe { CStr::from_ptr(tag_ptr) }.to_str()?)
 	}
 
+	/// Enables/disables synchronizing execution providers with the CPU at the end of each run. Enabled by default.
+	///
+	/// Execution providers like CUDA execute asynchronously on a device stream; by default, ONNX Runtime waits for the
+	/// stream to finish before returning from a run, so the outputs are ready to be read. When chaining several runs on
+	/// the same stream, this wait is unnecessary, and disabling it can reduce latency in pipelined GPU inference.
+	///
+	/// **Danger:** with synchronization disabled, the run may return before its outputs have been written. Reading an
+	/// output (including by copying it to the CPU) before synchronizing the device stream yourself, e.g. with
+	/// [`IoBinding::synchronize_outputs`](crate::io_binding::IoBinding::synchronize_outputs) or the device API's own
+	/// synchronization function, will observe incomplete data. Outputs must also not be dropped or overwritten while
+	/// the device may still be writing to them.
+	///
+	/// This sets the `disable_synchronize_execution_providers` config entry.
+	///
+	/// ```no_run
+	/// # use ort::session::run_options::RunOptions;
+	/// # fn main() -> ort::Result<()> {
+	/// let run_options = RunOptions::new()?.with_ep_synchronization(false)?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_ep_synchronization(mut self, enable: bool) -> Result<Self> {
+		self.set_ep_synchronization(enable).map(|_| self)
+	}
+
+	/// Enables/disables synchronizing execution providers with the CPU at the end of each run. See
+	/// [`RunOptions::with_ep_synchronization`] for more details.
+	pub fn set_ep_synchronization(&mut self, enable: bool) -> Result<()> {
+		self.add_config_entry("disable_synchronize_execution_providers", if enable { "0" } else { "1" })
+	}
+
+	/// Selects which GPU graph runs with these options capture & replay, when the session uses an execution provider
+	/// with graph capture enabled, like [`CUDAExecutionProvider::with_cuda_graph`]. Graphs are captured by the first
+	/// run with each ID, and replayed by later runs with the same ID, so one session can serve several input shapes by
+	/// capturing a graph for each. Runs without a graph ID use graph `0`, and `-1` disables capture & replay for runs
+	/// with these options.
+	///
+	/// This sets the `gpu_graph_id` config entry.
+	///
+	/// ```no_run
+	/// # use ort::session::run_options::RunOptions;
+	/// # fn main() -> ort::Result<()> {
+	/// let batch_1 = RunOptions::new()?.with_gpu_graph_id(1)?;
+	/// let batch_8 = RunOptions::new()?.with_gpu_graph_id(8)?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// [`CUDAExecutionProvider::with_cuda_graph`]: crate::execution_providers::CUDAExecutionProvider::with_cuda_graph
+	pub fn with_gpu_graph_id(mut self, id: i32) -> Result<Self> {
+		self.set_gpu_graph_id(id).map(|_| self)
+	}
+
+	/// Selects which GPU graph runs with these options capture & replay. See [`RunOptions::with_gpu_graph_id`] for more
+	/// details.
+	pub fn set_gpu_graph_id(&mut self, id: i32) -> Result<()> {
+		self.add_config_entry("gpu_graph_id", format!("{id}"))
+	}
+
 	/// Sets the termination flag for the runs associated with this [`RunOptions`].
 	///
 	/// This function returns immediately (it does not wait for the session run to terminate). The run will terminate as
@@ -322,6 +462,15 @@ impl<O: SelectedOutputMarker> RunOptions<O> {
 
 	/// Adds a custom configuration option to the `RunOptions`.
 	///
+	/// Entries accumulate; each call applies its entry to the underlying run options immediately, and all entries take
+	/// effect for every run this [`RunOptions`] is passed to. Some useful keys include:
+	/// - `memory.enable_memory_arena_shrinkage`: shrinks the given devices' memory arenas at the end of the run, e.g.
+	///   `"cpu:0;gpu:0"`.
+	/// - `disable_synchronize_execution_providers`: set to `"1"` to skip synchronizing execution providers with the CPU
+	///   at the end of the run; see [`RunOptions::with_ep_synchronization`].
+	/// - `gpu_graph_id`: selects which captured graph to replay when using CUDA graphs; see
+	///   [`RunOptions::with_gpu_graph_id`].
+	///
 	/// This can be used to, for example, configure the graph ID when using compute graphs with an execution provider
 	/// like CUDA:
 	/// ```no_run
@@ -339,7 +488,24 @@ impl<O: SelectedOutputMarker> RunOptions<O> {
 				ortsys![unsafe AddRunConfigEntry(self.inner.ptr.as_ptr(), key.as_ptr(), value.as_ptr())?];
 				Ok(())
 			})
-		})
+		})?;
+		if key.as_ref() == "gpu_graph_id" {
+			self.inner.gpu_graph_id = value.as_ref().parse().unwrap_or(0);
+		}
+		Ok(())
+	}
+
+	/// Adds a custom configuration option to the `RunOptions`. See [`RunOptions::add_config_entry`] for more details.
+	///
+	/// ```no_run
+	/// # use ort::session::run_options::RunOptions;
+	/// # fn main() -> ort::Result<()> {
+	/// let run_options = RunOptions::new()?.with_config_entry("memory.enable_memory_arena_shrinkage", "cpu:0")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_config_entry(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Result<Self> {
+		self.add_config_entry(key, value).map(|_| self)
 	}
 
 	pub fn add_adapter(&mut self, adapter: &Adapter) -> Result<()> {
@@ -362,3 +528,58 @@ impl<O: SelectedOutputMarker> Drop for RunOptions<O> {
 		ortsys![unsafe ReleaseRunOptions(self.inner.ptr.as_ptr())];
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{OutputSelector, RunOptions};
+	use crate::{
+		ErrorCode,
+		session::Output,
+		tensor::{Shape, SymbolicDimensions, TensorElementType},
+		value::ValueType
+	};
+
+	fn output(name: &str) -> Output {
+		Output {
+			name: name.to_string(),
+			output_type: ValueType::Tensor {
+				ty: TensorElementType::Float32,
+				shape: Shape::new([-1]),
+				dimension_symbols: SymbolicDimensions::empty(1)
+			}
+		}
+	}
+
+	#[test]
+	fn test_resolve_outputs() -> crate::Result<()> {
+		let outputs = [output("logits"), output("hidden_states")];
+
+		let resolve = |selector: OutputSelector| {
+			selector
+				.resolve_outputs(&outputs)
+				.map(|(names, _)| names.iter().map(|n| n.to_string()).collect::<Vec<_>>())
+		};
+
+		assert_eq!(resolve(OutputSelector::default())?, ["logits", "hidden_states"]);
+		assert_eq!(resolve(OutputSelector::default().without("hidden_states"))?, ["logits"]);
+		assert_eq!(resolve(OutputSelector::no_default().with("hidden_states"))?, ["hidden_states"]);
+
+		let err = resolve(OutputSelector::no_default().with("attentions")).expect_err("unknown output");
+		assert_eq!(err.code(), ErrorCode::InvalidArgument);
+		let err = resolve(OutputSelector::no_default()).expect_err("empty selection");
+		assert_eq!(err.code(), ErrorCode::InvalidArgument);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_gpu_graph_id() -> crate::Result<()> {
+		let mut options = RunOptions::new()?;
+		assert_eq!(options.inner.gpu_graph_id, 0);
+		options.set_gpu_graph_id(3)?;
+		assert_eq!(options.inner.gpu_graph_id, 3);
+		options.add_config_entry("gpu_graph_id", "-1")?;
+		assert_eq!(options.inner.gpu_graph_id, -1);
+		Ok(())
+	}
+}
@@ -14,7 +14,8 @@ use smallvec::{SmallVec, smallvec};
 
 #[cfg(feature = "ndarray")]
 pub use self::ndarray::ArrayExtensions;
-pub use self::types::{IntoTensorElementType, PrimitiveTensorElementType, TensorElementType, Utf8Data};
+pub(crate) use self::types::check_bool_data;
+pub use self::types::{CastElement, IntoTensorElementType, PrimitiveTensorElementType, TensorElementType, Utf8Data};
 
 #[derive(Default, Clone, PartialEq, Eq)]
 pub struct Shape {
@@ -30,6 +31,9 @@ impl Shape {
 		Self { inner: smallvec![0; rank] }
 	}
 
+	/// Returns the total number of elements in a tensor of this shape, or `0` if any dimension is dynamic (`-1`).
+	///
+	/// This does not check for overflow; use [`Shape::checked_num_elements`] for shapes from untrusted sources.
 	#[doc(alias = "numel")]
 	pub fn num_elements(&self) -> usize {
 		let mut size = 1usize;
@@ -42,6 +46,26 @@ impl Shape {
 		size
 	}
 
+	/// Returns the total number of elements in a tensor of this shape, or `0` if any dimension is dynamic (`-1`).
+	///
+	/// Returns `None` if the number of elements would overflow `usize`.
+	///
+	/// ```
+	/// # use ort::tensor::Shape;
+	/// assert_eq!(Shape::new([2, 3, 4]).checked_num_elements(), Some(24));
+	/// assert_eq!(Shape::new([i64::MAX, i64::MAX]).checked_num_elements(), None);
+	/// ```
+	pub fn checked_num_elements(&self) -> Option<usize> {
+		let mut size = 1usize;
+		for dim in &self.inner {
+			if *dim < 0 {
+				return Some(0);
+			}
+			size = size.checked_mul(usize::try_from(*dim).ok()?)?;
+		}
+		Some(size)
+	}
+
 	#[cfg(feature = "ndarray")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
 	pub fn to_ixdyn(&self) -> ::ndarray::IxDyn {
@@ -150,3 +174,31 @@ impl Deref for SymbolicDimensions {
 		&self.0
 	}
 }
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Shape {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_seq(self.iter())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Shape {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		<Vec<i64> as serde::Deserialize>::deserialize(deserializer).map(Shape::from)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SymbolicDimensions {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_seq(self.iter())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SymbolicDimensions {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		<Vec<String> as serde::Deserialize>::deserialize(deserializer).map(SymbolicDimensions::new)
+	}
+}
@@ -1,8 +1,11 @@
-use alloc::string::String;
+use alloc::{format, string::String};
 use core::fmt;
 
+use crate::error::{Error, ErrorCode};
+
 /// Enum mapping ONNX Runtime's supported tensor data types.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TensorElementType {
 	/// 32-bit floating point number, equivalent to Rust's `f32`.
 	Float32,
@@ -20,7 +23,11 @@ pub enum TensorElementType {
 	Int64,
 	/// String, equivalent to Rust's `String`.
 	String,
-	/// Boolean, equivalent to Rust's `bool`.
+	/// Boolean, equivalent to Rust's `bool`. Stored as one byte per element, where `0` is `false` and `1` is `true`,
+	/// so `bool` tensors can be created from and extracted to `&[bool]` without conversion.
+	///
+	/// Since any other byte value would be an invalid `bool`, functions which create `bool` tensors from raw bytes,
+	/// like [`DynTensor::from_bytes`](crate::value::DynTensor::from_bytes), reject bytes other than `0` or `1`.
 	Bool,
 	/// 16-bit floating point number, equivalent to [`half::f16`] (with the `half` feature).
 	Float16,
@@ -32,7 +39,14 @@ pub enum TensorElementType {
 	Uint64,
 	/// Brain 16-bit floating point number, equivalent to [`half::bf16`] (with the `half` feature).
 	Bfloat16,
+	/// Complex number with 32-bit floating point real & imaginary parts, equivalent to [`num_complex::Complex32`]
+	/// (with the `num-complex` feature). Stored as the real part followed by the imaginary part, 8 bytes per element.
+	///
+	/// Few of ONNX Runtime's operators accept complex tensors, but they can always be passed through a model as inputs
+	/// & outputs, or created & extracted for use with custom operators.
 	Complex64,
+	/// Complex number with 64-bit floating point real & imaginary parts, equivalent to [`num_complex::Complex64`]
+	/// (with the `num-complex` feature). Stored as the real part followed by the imaginary part, 16 bytes per element.
 	Complex128,
 	/// 8-bit floating point number with 4 exponent bits and 3 mantissa bits, with only NaN values and no infinite
 	/// values.
@@ -71,6 +85,97 @@ impl TensorElementType {
 			TensorElementType::Complex128 => container_capacity * 16
 		}
 	}
+
+	/// Parses a NumPy dtype string, like `"<f4"`, `"int64"`, or `"?"`, as used in `.npy` headers & by
+	/// `numpy.dtype(...).str`.
+	///
+	/// Byte order prefixes `<` (little-endian), `=` (native), and `|` (not applicable) are accepted; since ONNX Runtime
+	/// stores tensors in little-endian order, big-endian (`>`) dtypes are only accepted for single-byte types. Types
+	/// NumPy lacks natively are recognized by their [`ml_dtypes`](https://github.com/jax-ml/ml_dtypes) names, e.g.
+	/// `"bfloat16"` or `"float8_e4m3fn"`, and the object dtype `"O"` is parsed as [`TensorElementType::String`].
+	///
+	/// ```
+	/// # use ort::tensor::TensorElementType;
+	/// assert_eq!(TensorElementType::from_numpy_dtype("<f4"), Some(TensorElementType::Float32));
+	/// assert_eq!(TensorElementType::from_numpy_dtype("i8"), Some(TensorElementType::Int64));
+	/// assert_eq!(TensorElementType::from_numpy_dtype("bool"), Some(TensorElementType::Bool));
+	/// assert_eq!(TensorElementType::from_numpy_dtype(">f4"), None);
+	/// ```
+	pub fn from_numpy_dtype(dtype: &str) -> Option<Self> {
+		let (big_endian, dtype) = match dtype.as_bytes().first() {
+			Some(b'<' | b'=' | b'|') => (false, &dtype[1..]),
+			Some(b'>') => (true, &dtype[1..]),
+			_ => (false, dtype)
+		};
+		let ty = match dtype {
+			"b1" | "?" | "bool" => TensorElementType::Bool,
+			"u1" | "B" | "uint8" => TensorElementType::Uint8,
+			"i1" | "b" | "int8" => TensorElementType::Int8,
+			"u2" | "H" | "uint16" => TensorElementType::Uint16,
+			"i2" | "h" | "int16" => TensorElementType::Int16,
+			"u4" | "I" | "uint32" => TensorElementType::Uint32,
+			"i4" | "i" | "int32" => TensorElementType::Int32,
+			"u8" | "Q" | "uint64" => TensorElementType::Uint64,
+			"i8" | "q" | "int64" => TensorElementType::Int64,
+			"f2" | "e" | "float16" => TensorElementType::Float16,
+			"f4" | "f" | "float32" => TensorElementType::Float32,
+			"f8" | "d" | "float64" => TensorElementType::Float64,
+			"c8" | "F" | "complex64" => TensorElementType::Complex64,
+			"c16" | "D" | "complex128" => TensorElementType::Complex128,
+			"O" | "object" => TensorElementType::String,
+			"bfloat16" => TensorElementType::Bfloat16,
+			"float8_e4m3fn" => TensorElementType::Float8E4M3FN,
+			"float8_e4m3fnuz" => TensorElementType::Float8E4M3FNUZ,
+			"float8_e5m2" => TensorElementType::Float8E5M2,
+			"float8_e5m2fnuz" => TensorElementType::Float8E5M2FNUZ,
+			"uint4" => TensorElementType::Uint4,
+			"int4" => TensorElementType::Int4,
+			_ => return None
+		};
+		if big_endian && !matches!(ty, TensorElementType::Bool | TensorElementType::Uint8 | TensorElementType::Int8) {
+			return None;
+		}
+		Some(ty)
+	}
+
+	/// Returns the NumPy dtype string for this type, in the form returned by `numpy.dtype(...).str`, e.g. `"<f4"` for
+	/// [`TensorElementType::Float32`].
+	///
+	/// Types NumPy lacks natively are named as in [`ml_dtypes`](https://github.com/jax-ml/ml_dtypes), e.g. `"bfloat16"`,
+	/// and [`TensorElementType::String`] maps to the object dtype `"|O"`. The result can be parsed back with
+	/// [`TensorElementType::from_numpy_dtype`].
+	///
+	/// ```
+	/// # use ort::tensor::TensorElementType;
+	/// assert_eq!(TensorElementType::Float32.to_numpy_dtype(), "<f4");
+	/// assert_eq!(TensorElementType::Bool.to_numpy_dtype(), "|b1");
+	/// ```
+	pub fn to_numpy_dtype(&self) -> &'static str {
+		match self {
+			TensorElementType::Bool => "|b1",
+			TensorElementType::Uint8 => "|u1",
+			TensorElementType::Int8 => "|i1",
+			TensorElementType::Uint16 => "<u2",
+			TensorElementType::Int16 => "<i2",
+			TensorElementType::Uint32 => "<u4",
+			TensorElementType::Int32 => "<i4",
+			TensorElementType::Uint64 => "<u8",
+			TensorElementType::Int64 => "<i8",
+			TensorElementType::Float16 => "<f2",
+			TensorElementType::Float32 => "<f4",
+			TensorElementType::Float64 => "<f8",
+			TensorElementType::Complex64 => "<c8",
+			TensorElementType::Complex128 => "<c16",
+			TensorElementType::String => "|O",
+			TensorElementType::Bfloat16 => "bfloat16",
+			TensorElementType::Float8E4M3FN => "float8_e4m3fn",
+			TensorElementType::Float8E4M3FNUZ => "float8_e4m3fnuz",
+			TensorElementType::Float8E5M2 => "float8_e5m2",
+			TensorElementType::Float8E5M2FNUZ => "float8_e5m2fnuz",
+			TensorElementType::Uint4 => "uint4",
+			TensorElementType::Int4 => "int4"
+		}
+	}
 }
 
 impl fmt::Display for TensorElementType {
@@ -131,10 +236,25 @@ impl From<TensorElementType> for ort_sys::ONNXTensorElementDataType {
 		}
 	}
 }
+
+/// # Panics
+/// Panics for `ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED`; use [`TensorElementType::try_from_sys`] to handle it instead.
 impl From<ort_sys::ONNXTensorElementDataType> for TensorElementType {
 	fn from(val: ort_sys::ONNXTensorElementDataType) -> Self {
-		match val {
-			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED => panic!("Invalid ONNX tensor element data type"),
+		TensorElementType::try_from_sys(val).expect("Invalid ONNX tensor element data type")
+	}
+}
+
+impl TensorElementType {
+	/// Converts a raw element type, e.g. one obtained from a direct [`ort_sys`] call, to a [`TensorElementType`].
+	///
+	/// Unlike the [`From`] conversion, which panics, this returns an error with [`ErrorCode::InvalidArgument`] for
+	/// `ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED`.
+	pub fn try_from_sys(val: ort_sys::ONNXTensorElementDataType) -> Result<Self, Error> {
+		Ok(match val {
+			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED => {
+				return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Undefined tensor element type"));
+			}
 			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT => TensorElementType::Float32,
 			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT8 => TensorElementType::Uint8,
 			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT8 => TensorElementType::Int8,
@@ -157,7 +277,7 @@ impl From<ort_sys::ONNXTensorElementDataType> for TensorElementType {
 			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E4M3FNUZ => TensorElementType::Float8E4M3FNUZ,
 			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E5M2 => TensorElementType::Float8E5M2,
 			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E5M2FNUZ => TensorElementType::Float8E5M2FNUZ
-		}
+		})
 	}
 }
 
@@ -215,6 +335,39 @@ impl_type_trait!(num_complex::Complex32, Complex64);
 #[cfg_attr(docsrs, doc(cfg(feature = "num-complex")))]
 impl_type_trait!(num_complex::Complex64, Complex128);
 
+/// Conversion from the numeric tensor element type `T`, used by [`Tensor::cast`](crate::value::Tensor::cast).
+///
+/// Conversions follow the semantics of Rust's `as` operator:
+/// - Casting between integers of different widths truncates (when narrowing) or sign-/zero-extends (when widening).
+/// - Casting a float to an integer rounds towards zero and saturates at the integer's bounds; `NaN` becomes `0`.
+/// - Casting an integer or `f64` to a float rounds to the nearest representable value.
+pub trait CastElement<T>: PrimitiveTensorElementType {
+	/// Converts `value` to `Self`.
+	fn cast_from(value: T) -> Self;
+
+	private_trait!();
+}
+
+macro_rules! impl_cast_element {
+	($($from:ty),+) => {
+		$(impl_cast_element!(@from $from => f32, f64, i8, i16, i32, i64, u8, u16, u32, u64);)+
+	};
+	(@from $from:ty => $($to:ty),+) => {
+		$(
+			impl CastElement<$from> for $to {
+				#[inline]
+				fn cast_from(value: $from) -> Self {
+					value as $to
+				}
+
+				private_impl!();
+			}
+		)+
+	};
+}
+
+impl_cast_element!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64);
+
 impl IntoTensorElementType for String {
 	fn into_tensor_element_type() -> TensorElementType {
 		TensorElementType::String
@@ -240,3 +393,62 @@ impl Utf8Data for &str {
 		self.as_bytes()
 	}
 }
+
+/// Checks that every byte of the data of a [`TensorElementType::Bool`] tensor is a valid `bool`, i.e. `0` or `1`.
+pub(crate) fn check_bool_data(data: &[u8]) -> crate::Result<()> {
+	match data.iter().position(|b| *b > 1) {
+		Some(i) => Err(Error::new_with_code(
+			ErrorCode::InvalidArgument,
+			format!("Invalid `bool` tensor element {:#04x} at index {i}; booleans must be stored as 0 or 1", data[i])
+		)),
+		None => Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{TensorElementType, check_bool_data};
+
+	#[test]
+	fn test_element_type_sys_roundtrip() {
+		use ort_sys::ONNXTensorElementDataType::*;
+		for ty in [
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT8,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_INT8,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT16,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_INT16,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_INT32,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_INT64,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_STRING,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_BOOL,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT16,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_DOUBLE,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT32,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX64,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_COMPLEX128,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E4M3FN,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E4M3FNUZ,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E5M2,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E5M2FNUZ,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT4,
+			ONNX_TENSOR_ELEMENT_DATA_TYPE_INT4
+		] {
+			let element_type = TensorElementType::try_from_sys(ty).expect("type should be defined");
+			assert_eq!(TensorElementType::from(ty), element_type);
+			assert_eq!(ort_sys::ONNXTensorElementDataType::from(element_type), ty);
+		}
+		assert!(TensorElementType::try_from_sys(ONNX_TENSOR_ELEMENT_DATA_TYPE_UNDEFINED).is_err());
+	}
+
+	#[test]
+	fn test_check_bool_data() {
+		assert!(check_bool_data(&[]).is_ok());
+		assert!(check_bool_data(&[0, 1, 1, 0]).is_ok());
+		let err = check_bool_data(&[0, 1, 2]).expect_err("2 is not a valid bool");
+		assert_eq!(err.code(), crate::ErrorCode::InvalidArgument);
+		assert!(check_bool_data(&[0xff]).is_err());
+	}
+}
@@ -0,0 +1,102 @@
+use alloc::{format, vec::Vec};
+use core::slice;
+use std::path::Path;
+
+use safetensors::{Dtype, tensor::TensorView};
+
+use super::Checkpoint;
+use crate::{Error, ErrorCode, Result, memory::Allocator, tensor::TensorElementType, value::DynTensor};
+
+fn safetensors_dtype(ty: TensorElementType) -> Option<Dtype> {
+	Some(match ty {
+		TensorElementType::Bool => Dtype::BOOL,
+		TensorElementType::Uint8 => Dtype::U8,
+		TensorElementType::Int8 => Dtype::I8,
+		TensorElementType::Uint16 => Dtype::U16,
+		TensorElementType::Int16 => Dtype::I16,
+		TensorElementType::Uint32 => Dtype::U32,
+		TensorElementType::Int32 => Dtype::I32,
+		TensorElementType::Uint64 => Dtype::U64,
+		TensorElementType::Int64 => Dtype::I64,
+		TensorElementType::Float16 => Dtype::F16,
+		TensorElementType::Bfloat16 => Dtype::BF16,
+		TensorElementType::Float32 => Dtype::F32,
+		TensorElementType::Float64 => Dtype::F64,
+		TensorElementType::Float8E4M3FN => Dtype::F8_E4M3,
+		TensorElementType::Float8E5M2 => Dtype::F8_E5M2,
+		_ => return None
+	})
+}
+
+impl Checkpoint {
+	/// Exports the parameters named in `names` to a [safetensors](https://huggingface.co/docs/safetensors) file at
+	/// `path`, allowing weights fine-tuned with ONNX Runtime to be loaded by other tools.
+	///
+	/// ONNX Runtime does not provide a way to list the parameters stored in a checkpoint, so the names to export must
+	/// be given explicitly. Each parameter is copied into `allocator`, which must allocate CPU-accessible memory, and
+	/// is written under its checkpoint name with its original shape & data type. Returns an error with
+	/// [`ErrorCode::InvalidArgument`] if a parameter's data type cannot be represented in safetensors, e.g. strings or
+	/// complex numbers.
+	///
+	/// ```no_run
+	/// # use ort::{memory::Allocator, training::Checkpoint};
+	/// # fn main() -> ort::Result<()> {
+	/// let checkpoint = Checkpoint::load("training_artifacts/checkpoint")?;
+	/// checkpoint.export_safetensors("model.safetensors", &["fc1.weight", "fc1.bias"], &Allocator::default())?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[cfg_attr(docsrs, doc(cfg(feature = "safetensors")))]
+	pub fn export_safetensors(&self, path: impl AsRef<Path>, names: &[impl AsRef<str>], allocator: &Allocator) -> Result<()> {
+		let mut parameters: Vec<(&str, Dtype, DynTensor)> = Vec::with_capacity(names.len());
+		for name in names {
+			let name = name.as_ref();
+			let tensor = self.get_parameter(name, allocator)?;
+			if !tensor.memory_info().is_cpu_accessible() {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Parameter `{name}` was not allocated in CPU-accessible memory; use an allocator for a CPU device")
+				));
+			}
+			let ty = tensor.dtype().tensor_type().expect("parameter should be a tensor");
+			let dtype = safetensors_dtype(ty).ok_or_else(|| {
+				Error::new_with_code(ErrorCode::InvalidArgument, format!("Parameter `{name}` has data type `{ty}`, which is not supported by safetensors"))
+			})?;
+			parameters.push((name, dtype, tensor));
+		}
+
+		let views = parameters
+			.iter()
+			.map(|(name, dtype, tensor)| {
+				let shape: Vec<usize> = tensor.shape().iter().map(|&d| d as usize).collect();
+				let len = shape.iter().product::<usize>() * dtype.size();
+				let data = if len == 0 {
+					&[][..]
+				} else {
+					// SAFETY: the tensor is CPU-accessible, and holds `len` bytes of data for its shape & type.
+					unsafe { slice::from_raw_parts(tensor.data_ptr()?.cast::<u8>(), len) }
+				};
+				let view = TensorView::new(*dtype, shape, data).map_err(Error::wrap)?;
+				Ok((*name, view))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		safetensors::serialize_to_file(views, &None, path.as_ref()).map_err(Error::wrap)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use safetensors::Dtype;
+
+	use super::safetensors_dtype;
+	use crate::tensor::TensorElementType;
+
+	#[test]
+	fn test_safetensors_dtype() {
+		assert_eq!(safetensors_dtype(TensorElementType::Float32), Some(Dtype::F32));
+		assert_eq!(safetensors_dtype(TensorElementType::Bfloat16), Some(Dtype::BF16));
+		assert_eq!(safetensors_dtype(TensorElementType::Bool), Some(Dtype::BOOL));
+		assert_eq!(safetensors_dtype(TensorElementType::String), None);
+		assert_eq!(safetensors_dtype(TensorElementType::Complex64), None);
+	}
+}
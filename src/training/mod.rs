@@ -1,15 +1,22 @@
 //! Provides [`Trainer`], a simple interface for on-device training/fine-tuning.
 
-use alloc::string::{String, ToString};
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec
+};
 use core::{
+	cell::RefCell,
 	ffi::{CStr, c_char},
-	marker::PhantomData,
 	ptr::{self, NonNull}
 };
-use std::{path::Path, sync::OnceLock};
+use std::{
+	path::{Path, PathBuf},
+	sync::{Mutex, OnceLock}
+};
 
 use crate::{
-	AsPointer, Error, Result,
+	AsPointer, Error, ErrorCode, Result,
 	memory::Allocator,
 	ortsys,
 	session::{NoSelectedOutputs, RunOptions},
@@ -17,13 +24,15 @@ use crate::{
 	value::{DynTensor, Value, ValueType, ValueTypeMarker, r#type::extract_data_type_from_tensor_info}
 };
 
+#[cfg(feature = "safetensors")]
+mod export;
 mod simple;
 mod trainer;
 
 pub use self::{
 	simple::{
-		CheckpointStrategy, DataLoader, EvaluationStrategy, IterableDataLoader, TrainerCallbacks, TrainerControl, TrainerState, TrainingArguments,
-		iterable_data_loader
+		CheckpointStrategy, DataLoader, EvaluationStrategy, IterableDataLoader, MetricMode, ProgressLogger, TrainerCallbacks, TrainerControl, TrainerState,
+		TrainingArguments, iterable_data_loader
 	},
 	trainer::Trainer
 };
@@ -54,12 +63,19 @@ pub fn training_api() -> Result<&'static ort_sys::OrtTrainingApi> {
 	Ok(unsafe { ptr.as_ref() })
 }
 
-/// Sets the seed used for RNG when training.
+/// Sets the seed used for RNG when training, e.g. by dropout.
+///
+/// ONNX Runtime only has a single, process-global seed, so this affects all [`Trainer`]s in the process. To seed a
+/// single training run, use [`TrainingArguments::with_seed`], which also prevents other seeded training runs from
+/// changing the seed while it is in progress.
 pub fn set_seed(seed: i64) -> Result<()> {
 	trainsys![unsafe SetSeed(seed)?];
 	Ok(())
 }
 
+/// Held for the duration of a [`Trainer::train`] call which was given a seed via [`TrainingArguments::with_seed`].
+pub(crate) static SEED_LOCK: Mutex<()> = Mutex::new(());
+
 macro_rules! trainsys {
 	($method:ident) => {
 		($crate::training::training_api().unwrap().$method)
@@ -120,16 +136,65 @@ impl Checkpoint {
 		})
 	}
 
+	/// Saves this checkpoint to `path`.
+	///
+	/// If `include_optimizer_state` is `true`, the optimizer's state (e.g. AdamW's moment estimates) is saved as well,
+	/// so that a [`Trainer`] created from the saved checkpoint resumes training exactly where it left off; otherwise,
+	/// only the parameters are saved, which is sufficient for exporting or fine-tuning from scratch. See
+	/// [`Optimizer`](Optimizer#optimizer-state).
 	pub fn save(&self, path: impl AsRef<Path>, include_optimizer_state: bool) -> Result<()> {
 		let path = crate::util::path_to_os_char(path);
 		trainsys![unsafe SaveCheckpoint(self.ptr.as_ptr(), path.as_ptr(), include_optimizer_state)?];
 		Ok(())
 	}
 
+	/// Serializes this checkpoint into memory, e.g. to upload it to object storage. The returned buffer can be loaded
+	/// with [`Checkpoint::load_from_buffer`]. See [`Checkpoint::save`] for the meaning of `include_optimizer_state`.
+	///
+	/// ONNX Runtime's training API can only save checkpoints to a file, so this saves the checkpoint into a newly
+	/// created, randomly named directory in [`std::env::temp_dir`], reads it back, and then deletes the directory. On
+	/// Unix, the directory is only accessible by the current user, so other users cannot read or replace the
+	/// checkpoint. The temporary directory must be writable, and have enough space to hold the checkpoint.
+	pub fn save_to_buffer(&self, include_optimizer_state: bool) -> Result<Vec<u8>> {
+		/// Deletes the temporary directory & its contents when dropped, so they don't linger if saving or reading
+		/// fails.
+		struct TempDir(PathBuf);
+		impl Drop for TempDir {
+			fn drop(&mut self) {
+				let _ = std::fs::remove_dir_all(&self.0);
+			}
+		}
+
+		let mut builder = std::fs::DirBuilder::new();
+		#[cfg(unix)]
+		std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+		// `DirBuilder::create` fails if anything, including a symlink, already exists at the path, so the directory is
+		// always freshly created by us; retry with a new name in the unlikely event of a collision.
+		let mut attempts = 0;
+		let dir = loop {
+			let path = std::env::temp_dir().join(format!("ort-checkpoint-{}", ort_sys::internal::random_identifier()));
+			match builder.create(&path) {
+				Ok(()) => break TempDir(path),
+				Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && attempts < 16 => attempts += 1,
+				Err(e) => return Err(Error::new(format!("Failed to create temporary checkpoint directory `{}`: {e}", path.display())))
+			}
+		};
+
+		let path = dir.0.join("checkpoint");
+		self.save(&path, include_optimizer_state)?;
+		std::fs::read(&path).map_err(|e| Error::new(format!("Failed to read temporary checkpoint file `{}`: {e}", path.display())))
+	}
+
 	pub fn add_property(&mut self, name: impl AsRef<str>, property: impl Into<Property>) -> Result<()> {
-		let property = property.into();
-		with_cstr(name.as_ref().as_bytes(), &|name| {
-			match &property {
+		self.set_property(name.as_ref(), &property.into())
+	}
+
+	/// Like [`Checkpoint::add_property`], but usable through a shared reference to the checkpoint owned by a
+	/// [`Trainer`]; ONNX Runtime stores properties separately from the parameters used by the training session.
+	pub(crate) fn set_property(&self, name: &str, property: &Property) -> Result<()> {
+		with_cstr(name.as_bytes(), &|name| {
+			match property {
 				Property::Int(value) => {
 					trainsys![unsafe AddProperty(self.ptr.as_ptr(), name.as_ptr(), ort_sys::OrtPropertyType::OrtIntProperty, (value as *const i64).cast())?];
 				}
@@ -199,6 +264,138 @@ impl Checkpoint {
 		ortsys![unsafe ReleaseTensorTypeAndShapeInfo(shape_info)];
 		Ok(value_type)
 	}
+
+	/// Compares the `f32` parameters named in `names` between this checkpoint and `other`, which is useful for
+	/// debugging diverging training runs.
+	///
+	/// ONNX Runtime does not provide a way to list the parameters stored in a checkpoint, so the names to compare must
+	/// be given explicitly. Names present in only one of the checkpoints are reported in
+	/// [`CheckpointDiff::only_in_self`] and [`CheckpointDiff::only_in_other`]. Parameters are copied into `allocator`,
+	/// which must allocate CPU-accessible memory.
+	///
+	/// ```no_run
+	/// # use ort::{memory::Allocator, training::Checkpoint};
+	/// # fn main() -> ort::Result<()> {
+	/// let a = Checkpoint::load("run_a/checkpoint")?;
+	/// let b = Checkpoint::load("run_b/checkpoint")?;
+	/// let diff = a.diff(&b, &["fc1.weight", "fc1.bias"], &Allocator::default())?;
+	/// for param in diff.parameters {
+	/// 	println!("{}: max {}, mean {}", param.name, param.max_abs_diff, param.mean_abs_diff);
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn diff(&self, other: &Checkpoint, names: &[impl AsRef<str>], allocator: &Allocator) -> Result<CheckpointDiff> {
+		let mut diff = CheckpointDiff::default();
+		for name in names {
+			let name = name.as_ref();
+			let (ours, theirs) = match (self.get_parameter_type(name), other.get_parameter_type(name)) {
+				(Ok(_), Ok(_)) => (self.get_parameter(name, allocator)?, other.get_parameter(name, allocator)?),
+				(Ok(_), Err(_)) => {
+					diff.only_in_self.push(name.to_string());
+					continue;
+				}
+				(Err(_), Ok(_)) => {
+					diff.only_in_other.push(name.to_string());
+					continue;
+				}
+				(Err(e), Err(_)) => return Err(e)
+			};
+
+			let (our_shape, our_data) = ours.try_extract_tensor::<f32>()?;
+			let (their_shape, their_data) = theirs.try_extract_tensor::<f32>()?;
+			if our_shape != their_shape {
+				return Err(Error::new(format!("Parameter `{name}` has shape {our_shape} in one checkpoint, but {their_shape} in the other")));
+			}
+
+			let (mut max_abs_diff, mut total_abs_diff) = (0.0_f32, 0.0_f64);
+			for (a, b) in our_data.iter().zip(their_data) {
+				let abs_diff = (a - b).abs();
+				max_abs_diff = max_abs_diff.max(abs_diff);
+				total_abs_diff += abs_diff as f64;
+			}
+			diff.parameters.push(ParameterDiff {
+				name: name.to_string(),
+				max_abs_diff,
+				mean_abs_diff: if our_data.is_empty() { 0.0 } else { (total_abs_diff / our_data.len() as f64) as f32 }
+			});
+		}
+		Ok(diff)
+	}
+
+	/// Sums the element counts & sizes of the parameters named in `names`, e.g. for logging the size of a model after
+	/// loading its checkpoint.
+	///
+	/// ONNX Runtime does not provide a way to list the parameters stored in a checkpoint, nor whether a parameter is
+	/// trainable, so the names must be given explicitly and [`ParameterStats::trainable`] is always `None`. Use
+	/// [`Trainer::parameter_stats`] to also count trainable parameters. Parameters are not copied; only their types are
+	/// queried.
+	///
+	/// ```no_run
+	/// # use ort::training::Checkpoint;
+	/// # fn main() -> ort::Result<()> {
+	/// let checkpoint = Checkpoint::load("training_artifacts/checkpoint")?;
+	/// let stats = checkpoint.parameter_stats(&["fc1.weight", "fc1.bias"])?;
+	/// println!("{} parameters ({} bytes)", stats.count, stats.bytes);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn parameter_stats(&self, names: &[impl AsRef<str>]) -> Result<ParameterStats> {
+		let mut stats = ParameterStats::default();
+		for name in names {
+			let name = name.as_ref();
+			let ty = self.get_parameter_type(name)?;
+			let (Some(shape), Some(element_type)) = (ty.tensor_shape(), ty.tensor_type()) else {
+				return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Parameter `{name}` is not a tensor")));
+			};
+			let overflow = || Error::new_with_code(ErrorCode::InvalidArgument, format!("Size of parameter `{name}` overflows"));
+			let count = shape.checked_num_elements().ok_or_else(overflow)?;
+			stats.count = stats.count.checked_add(count as u64).ok_or_else(overflow)?;
+			stats.bytes = stats.bytes.checked_add(element_type.byte_size(count) as u64).ok_or_else(overflow)?;
+		}
+		Ok(stats)
+	}
+}
+
+/// Parameter counts & sizes, returned by [`Checkpoint::parameter_stats`] and [`Trainer::parameter_stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ParameterStats {
+	/// The total number of parameter elements.
+	pub count: u64,
+	/// The number of trainable parameter elements, or `None` if it is not known.
+	pub trainable: Option<u64>,
+	/// The total size of all parameters in bytes.
+	pub bytes: u64
+}
+
+impl ParameterStats {
+	/// Returns the number of frozen (non-trainable) parameter elements, or `None` if it is not known.
+	///
+	/// See [Freezing parameters](Trainer#freezing-parameters) for how parameters are frozen.
+	pub fn frozen(&self) -> Option<u64> {
+		self.trainable.map(|trainable| self.count.saturating_sub(trainable))
+	}
+}
+
+/// The result of comparing two checkpoints with [`Checkpoint::diff`].
+#[derive(Debug, Default, Clone)]
+pub struct CheckpointDiff {
+	/// Statistics for each parameter present in both checkpoints.
+	pub parameters: Vec<ParameterDiff>,
+	/// Names of parameters present only in the checkpoint `diff` was called on.
+	pub only_in_self: Vec<String>,
+	/// Names of parameters present only in the other checkpoint.
+	pub only_in_other: Vec<String>
+}
+
+/// The difference of a single parameter between two checkpoints. See [`Checkpoint::diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterDiff {
+	pub name: String,
+	/// The largest absolute difference between any two corresponding elements.
+	pub max_abs_diff: f32,
+	/// The mean absolute difference over all elements.
+	pub mean_abs_diff: f32
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -244,24 +441,104 @@ impl Drop for Checkpoint {
 	}
 }
 
+/// A learning rate schedule, registered with [`Optimizer::register_scheduler`] and advanced with
+/// [`Optimizer::step_scheduler`].
+///
+/// [`LearningRateScheduler::Linear`] is implemented natively by ONNX Runtime; the other schedules are implemented by
+/// `ort`, which computes the new learning rate on each [`Optimizer::step_scheduler`] call and applies it with
+/// [`Optimizer::set_lr`].
 #[derive(Debug, Clone)]
 pub enum LearningRateScheduler {
+	/// Linearly increases the learning rate from 0 to `initial_lr` over `warmup_step_count` steps, then linearly
+	/// decreases it to 0 at `total_step_count` steps.
 	Linear {
 		warmup_step_count: i64,
 		total_step_count: i64,
 		initial_lr: f32
+	},
+	/// Keeps the learning rate fixed at `lr`.
+	Constant { lr: f32 },
+	/// Starts at `initial_lr`, multiplying the learning rate by `gamma` every `step_size` steps, i.e. the learning rate
+	/// after `n` scheduler steps is `initial_lr * gamma.powi(n / step_size)`.
+	Step { step_size: i64, gamma: f32, initial_lr: f32 },
+	/// Linearly increases the learning rate from 0 to `lr` over `warmup_step_count` steps, then keeps it fixed at `lr`.
+	/// Unlike [`LearningRateScheduler::Linear`], the learning rate never decays. If `warmup_step_count` is 0, the
+	/// learning rate starts at `lr`.
+	WarmupConstant { warmup_step_count: i64, lr: f32 }
+}
+
+/// State for the learning rate schedule registered with [`Optimizer::register_scheduler`]. For
+/// [`LearningRateScheduler::Linear`], the schedule itself lives in ONNX Runtime; only the number of steps is tracked.
+#[derive(Debug)]
+pub(crate) struct SchedulerState {
+	scheduler: LearningRateScheduler,
+	steps: i64
+}
+
+impl SchedulerState {
+	fn is_native(&self) -> bool {
+		matches!(self.scheduler, LearningRateScheduler::Linear { .. })
+	}
+
+	/// Moves the schedule forward to `steps` steps, returning how many steps it was advanced by. Schedules never move
+	/// backwards.
+	fn fast_forward(&mut self, steps: i64) -> i64 {
+		let advanced = (steps - self.steps).max(0);
+		self.steps += advanced;
+		advanced
+	}
+
+	fn lr(&self) -> f32 {
+		match self.scheduler {
+			LearningRateScheduler::Constant { lr } => lr,
+			LearningRateScheduler::Step { step_size, gamma, initial_lr } => initial_lr * gamma.powi((self.steps / step_size) as i32),
+			LearningRateScheduler::WarmupConstant { warmup_step_count, lr } => {
+				if self.steps >= warmup_step_count {
+					lr
+				} else {
+					lr * self.steps as f32 / warmup_step_count as f32
+				}
+			}
+			LearningRateScheduler::Linear { .. } => unreachable!("linear schedules are implemented by ONNX Runtime")
+		}
 	}
 }
 
+/// Controls the optimizer & learning rate of a [`Trainer`].
+///
+/// Note that ONNX Runtime's training API does not expose the gradient buffers. Gradients are accumulated internally by
+/// [`Trainer::step`] and consumed by [`Optimizer::step`]; neither individual parameter gradients
+/// nor the global gradient norm can be read out between the two. Only the parameters themselves are accessible, via
+/// [`Checkpoint::get_parameter`].
+///
+/// ## Optimizer state
+/// The optimizer's internal state, e.g. the first & second moment estimates of AdamW, is likewise not exposed by the
+/// training API, so it cannot be inspected or modified. It is stored in the trainer's [`Checkpoint`] alongside the
+/// parameters, and can be carried over to a later training run by saving the checkpoint with
+/// `include_optimizer_state` set (see [`Checkpoint::save`]), then creating the new [`Trainer`] from that checkpoint:
+/// ```no_run
+/// # use ort::{memory::Allocator, session::Session, training::{Checkpoint, Trainer}};
+/// # fn main() -> ort::Result<()> {
+/// # let trainer: Trainer = unimplemented!();
+/// // ...train for a while, then save the parameters *and* optimizer state
+/// trainer.checkpoint().save("run/checkpoint", true)?;
+///
+/// // later, resume training where we left off
+/// let checkpoint = Checkpoint::load("run/checkpoint")?;
+/// let trainer =
+/// 	Trainer::new_from_artifacts(Session::builder()?, Allocator::default(), "training_artifacts", Some(checkpoint))?;
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Optimizer<'s> {
 	session: NonNull<ort_sys::OrtTrainingSession>,
-	_p: PhantomData<&'s ()>
+	scheduler: &'s RefCell<Option<SchedulerState>>
 }
 
-impl Optimizer<'_> {
-	pub(crate) fn new(session: NonNull<ort_sys::OrtTrainingSession>) -> Self {
-		Self { session, _p: PhantomData }
+impl<'s> Optimizer<'s> {
+	pub(crate) fn new(session: NonNull<ort_sys::OrtTrainingSession>, scheduler: &'s RefCell<Option<SchedulerState>>) -> Self {
+		Self { session, scheduler }
 	}
 
 	pub fn reset_grad(&mut self) -> Result<()> {
@@ -280,6 +557,35 @@ impl Optimizer<'_> {
 		Ok(())
 	}
 
+	/// Registers a learning rate schedule, replacing any previously registered schedule. The learning rate is set to
+	/// the schedule's initial value immediately.
+	///
+	/// Call [`Optimizer::step_scheduler`] once after each [`Optimizer::step`] to advance the schedule; the learning
+	/// rate used by the `n`th optimizer step (counting from 0) is thus the schedule's value after `n` scheduler steps.
+	///
+	/// ```no_run
+	/// # use ort::training::{LearningRateScheduler, Trainer};
+	/// # fn main() -> ort::Result<()> {
+	/// # let trainer: Trainer = unimplemented!();
+	/// let mut optimizer = trainer.optimizer();
+	/// // Halve the learning rate every 1000 steps.
+	/// optimizer.register_scheduler(LearningRateScheduler::Step {
+	/// 	step_size: 1000,
+	/// 	gamma: 0.5,
+	/// 	initial_lr: 1e-3
+	/// })?;
+	/// loop {
+	/// 	// ...call `trainer.step(...)`...
+	/// 	optimizer.step()?;
+	/// 	optimizer.reset_grad()?;
+	/// 	optimizer.step_scheduler()?;
+	/// }
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if [`LearningRateScheduler::Step::step_size`] is not positive, or if
+	/// [`LearningRateScheduler::WarmupConstant::warmup_step_count`] is negative.
 	pub fn register_scheduler(&mut self, scheduler: LearningRateScheduler) -> Result<()> {
 		match scheduler {
 			LearningRateScheduler::Linear {
@@ -288,11 +594,32 @@ impl Optimizer<'_> {
 				initial_lr
 			} => {
 				trainsys![unsafe RegisterLinearLRScheduler(self.session.as_ptr(), warmup_step_count, total_step_count, initial_lr)?];
+				*self.scheduler.borrow_mut() = Some(SchedulerState { scheduler, steps: 0 });
+			}
+			LearningRateScheduler::Step { step_size, .. } if step_size <= 0 => {
+				return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Step scheduler `step_size` must be positive, got {step_size}")));
+			}
+			LearningRateScheduler::WarmupConstant { warmup_step_count, .. } if warmup_step_count < 0 => {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Warmup scheduler `warmup_step_count` must not be negative, got {warmup_step_count}")
+				));
+			}
+			scheduler => {
+				let state = SchedulerState { scheduler, steps: 0 };
+				self.set_lr(state.lr())?;
+				*self.scheduler.borrow_mut() = Some(state);
 			}
 		}
 		Ok(())
 	}
 
+	/// Updates the model's parameters using the gradients accumulated since the last call to
+	/// [`Optimizer::reset_grad`].
+	///
+	/// ONNX Runtime does not report whether the update was actually applied (e.g. if it was skipped due to non-finite
+	/// gradients) or the gradient norm, so this only signals whether the step failed. To detect skipped steps, check
+	/// the loss returned by [`Trainer::step`] for non-finite values before stepping the optimizer.
 	pub fn step(&mut self) -> Result<()> {
 		trainsys![unsafe OptimizerStep(self.session.as_ptr(), ptr::null_mut())?];
 		Ok(())
@@ -303,8 +630,120 @@ impl Optimizer<'_> {
 		Ok(())
 	}
 
+	/// Advances the learning rate schedule registered with [`Optimizer::register_scheduler`] by one step. See
+	/// [`Optimizer::register_scheduler`] for the intended order of operations.
 	pub fn step_scheduler(&mut self) -> Result<()> {
-		trainsys![unsafe SchedulerStep(self.session.as_ptr())?];
-		Ok(())
+		let lr = match self.scheduler.borrow_mut().as_mut() {
+			Some(state) if !state.is_native() => {
+				state.steps += 1;
+				state.lr()
+			}
+			state => {
+				trainsys![unsafe SchedulerStep(self.session.as_ptr())?];
+				if let Some(state) = state {
+					state.steps += 1;
+				}
+				return Ok(());
+			}
+		};
+		self.set_lr(lr)
+	}
+
+	/// Advances the registered learning rate schedule (if any) to `steps` scheduler steps & applies its learning rate,
+	/// as if [`Optimizer::step_scheduler`] had been called `steps` times since it was registered. Used when resuming
+	/// training.
+	pub(crate) fn fast_forward_scheduler(&mut self, steps: i64) -> Result<()> {
+		let lr = match self.scheduler.borrow_mut().as_mut() {
+			// ONNX Runtime's linear schedule can only be advanced one step at a time.
+			Some(state) if state.is_native() => {
+				for _ in 0..state.fast_forward(steps) {
+					trainsys![unsafe SchedulerStep(self.session.as_ptr())?];
+				}
+				return Ok(());
+			}
+			Some(state) => {
+				state.fast_forward(steps);
+				state.lr()
+			}
+			None => return Ok(())
+		};
+		self.set_lr(lr)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{LearningRateScheduler, SchedulerState};
+
+	#[test]
+	fn test_step_scheduler_lr() {
+		let mut state = SchedulerState {
+			scheduler: LearningRateScheduler::Step {
+				step_size: 2,
+				gamma: 0.5,
+				initial_lr: 1.0
+			},
+			steps: 0
+		};
+		let mut lrs = Vec::new();
+		for _ in 0..5 {
+			lrs.push(state.lr());
+			state.steps += 1;
+		}
+		assert_eq!(lrs, [1.0, 1.0, 0.5, 0.5, 0.25]);
+	}
+
+	#[test]
+	fn test_warmup_constant_scheduler_lr() {
+		let lrs = |warmup_step_count| {
+			let mut state = SchedulerState {
+				scheduler: LearningRateScheduler::WarmupConstant { warmup_step_count, lr: 1.0 },
+				steps: 0
+			};
+			let mut lrs = Vec::new();
+			for _ in 0..6 {
+				lrs.push(state.lr());
+				state.steps += 1;
+			}
+			lrs
+		};
+		assert_eq!(lrs(4), [0.0, 0.25, 0.5, 0.75, 1.0, 1.0]);
+		assert_eq!(lrs(0), [1.0; 6]);
+	}
+
+	#[test]
+	fn test_fast_forward_scheduler_lr() {
+		let scheduler = LearningRateScheduler::Step {
+			step_size: 2,
+			gamma: 0.5,
+			initial_lr: 1.0
+		};
+		let mut stepped = SchedulerState {
+			scheduler: scheduler.clone(),
+			steps: 0
+		};
+		for _ in 0..5 {
+			stepped.steps += 1;
+		}
+		// A schedule fast-forwarded when resuming at step 5 must match one stepped after each of the first 5 steps.
+		let mut resumed = SchedulerState { scheduler, steps: 0 };
+		assert_eq!(resumed.fast_forward(5), 5);
+		assert_eq!(resumed.lr(), stepped.lr());
+		assert_eq!(resumed.lr(), 0.25);
+		// ...and never moves backwards.
+		assert_eq!(resumed.fast_forward(3), 0);
+		assert_eq!(resumed.steps, 5);
+
+		// ONNX Runtime's linear schedule is stepped natively once per step skipped.
+		let mut linear = SchedulerState {
+			scheduler: LearningRateScheduler::Linear {
+				warmup_step_count: 2,
+				total_step_count: 10,
+				initial_lr: 1.0
+			},
+			steps: 1
+		};
+		assert!(linear.is_native());
+		assert_eq!(linear.fast_forward(4), 3);
 	}
 }
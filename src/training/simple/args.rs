@@ -28,13 +28,23 @@ impl EvaluationStrategy {
 pub enum CheckpointStrategy {
 	None,
 	Steps(usize),
-	Epochs(usize)
+	Epochs(usize),
+	/// Saves a checkpoint after evaluation only if the metric `name` improved over the best value seen so far. The
+	/// first evaluation always saves a checkpoint. Non-finite values (NaN or infinity) never count as an improvement.
+	///
+	/// `eval_loss` is always reported; other metrics can be reported by callbacks via
+	/// [`TrainerControl::report_metric`](super::TrainerControl::report_metric). Combine with
+	/// [`TrainingArguments::with_max_saved_ckpts`] to control how many of the previous best checkpoints are kept.
+	BestMetric {
+		name: String,
+		mode: MetricMode
+	}
 }
 
 impl CheckpointStrategy {
 	pub(crate) fn should_fire(&self, _global_step: usize, iter_step: usize, dataloader_size: Option<usize>) -> bool {
 		match self {
-			Self::None => false,
+			Self::None | Self::BestMetric { .. } => false,
 			Self::Steps(steps) => iter_step > 0 && iter_step % steps == 0,
 			Self::Epochs(epochs) => {
 				if let Some(dataloader_size) = dataloader_size {
@@ -47,6 +57,28 @@ impl CheckpointStrategy {
 	}
 }
 
+/// Whether a lower or higher value of a metric is considered an improvement. See [`CheckpointStrategy::BestMetric`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricMode {
+	Min,
+	Max
+}
+
+impl MetricMode {
+	pub(crate) fn is_improvement(&self, best: Option<f32>, value: f32) -> bool {
+		if !value.is_finite() {
+			return false;
+		}
+		match best {
+			Some(best) if best.is_finite() => match self {
+				Self::Min => value < best,
+				Self::Max => value > best
+			},
+			_ => true
+		}
+	}
+}
+
 pub struct TrainingArguments<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static, 'static, NL>>, const NI: usize, const NL: usize> {
 	pub(crate) loader: Box<dyn DataLoader<I, L>>,
 	pub(crate) eval_loader: Option<Box<dyn DataLoader<I, L>>>,
@@ -58,6 +90,9 @@ pub struct TrainingArguments<I: Into<SessionInputs<'static, 'static, NI>>, L: In
 	pub(crate) gradient_accumulation_steps: usize,
 	pub(crate) max_steps: usize,
 	pub(crate) max_eval_steps: usize,
+	pub(crate) logging_steps: usize,
+	pub(crate) seed: Option<i64>,
+	pub(crate) resume: bool,
 	pub(crate) callbacks: Vec<Box<dyn TrainerCallbacks>>
 }
 
@@ -76,6 +111,9 @@ impl<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static
 			max_saved_ckpts: 1,
 			max_steps: usize::MAX,
 			max_eval_steps: usize::MAX,
+			logging_steps: 100,
+			seed: None,
+			resume: false,
 			callbacks: Vec::new()
 		}
 	}
@@ -130,6 +168,40 @@ impl<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static
 		self
 	}
 
+	/// Sets how often, in optimizer steps, training progress is logged by the default
+	/// [`ProgressLogger`](super::ProgressLogger). Defaults to every 100 steps; a value of `0` disables the default
+	/// logger.
+	///
+	/// The default logger is only used if no callbacks are added via [`TrainingArguments::with_callbacks`]. To log
+	/// progress alongside custom callbacks, add a [`ProgressLogger`](super::ProgressLogger) explicitly.
+	pub fn with_logging_steps(mut self, steps: usize) -> Self {
+		self.logging_steps = steps;
+		self
+	}
+
+	/// Seeds ONNX Runtime's random number generator (used by e.g. dropout) at the start of training, for reproducible
+	/// training runs.
+	///
+	/// ONNX Runtime only supports a single, process-global seed (see [`set_seed`](crate::training::set_seed)), so
+	/// per-trainer seeds cannot be isolated. Instead, a seeded training run holds a process-wide lock for its entire
+	/// duration, so that seeded training runs on other threads wait for it to finish instead of re-seeding the RNG
+	/// midway through. Unseeded training runs and direct calls to [`set_seed`](crate::training::set_seed) are not
+	/// synchronized, and will affect reproducibility if they run concurrently with a seeded run.
+	pub fn with_seed(mut self, seed: i64) -> Self {
+		self.seed = Some(seed);
+		self
+	}
+
+	/// Resumes training from the progress stored in the trainer's checkpoint, e.g. when the trainer was created from
+	/// a checkpoint saved by an earlier training run. See [`Trainer::train`](crate::training::Trainer::train) for
+	/// details.
+	///
+	/// Disabled by default, in which case training always starts from the first batch.
+	pub fn with_resume(mut self, resume: bool) -> Self {
+		self.resume = resume;
+		self
+	}
+
 	pub fn with_callbacks(mut self, callbacks: impl TrainerCallbacks + 'static) -> Self {
 		self.callbacks.push(Box::new(callbacks));
 		self
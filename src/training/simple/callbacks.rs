@@ -1,10 +1,10 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path, time::Instant};
 
 use super::TrainingArguments;
 use crate::{
 	error::Result,
 	session::input::SessionInputs,
-	training::{Checkpoint, Optimizer, Trainer}
+	training::{Checkpoint, Optimizer, Property, Trainer}
 };
 
 #[derive(Clone)]
@@ -17,9 +17,22 @@ pub struct TrainerState {
 	pub iter_step: usize,
 	pub gradient_accumulation_steps: usize,
 	pub max_steps: usize,
-	pub current_lr: f32
+	pub current_lr: f32,
+	/// Metrics from the most recent evaluation, including `eval_loss` and any metrics reported by callbacks via
+	/// [`TrainerControl::report_metric`].
+	pub metrics: HashMap<String, f32>,
+	/// The best value of the metric monitored by [`CheckpointStrategy::BestMetric`] seen so far.
+	///
+	/// [`CheckpointStrategy::BestMetric`]: super::CheckpointStrategy::BestMetric
+	pub best_metric: Option<f32>
 }
 
+/// Checkpoint property names under which [`TrainerState`] is persisted.
+const GLOBAL_STEP_PROPERTY: &str = "ort.trainer.global_step";
+const ITER_STEP_PROPERTY: &str = "ort.trainer.iter_step";
+const LR_PROPERTY: &str = "ort.trainer.lr";
+const BEST_METRIC_PROPERTY: &str = "ort.trainer.best_metric";
+
 impl TrainerState {
 	pub(crate) fn new<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static, 'static, NL>>, const NI: usize, const NL: usize>(
 		args: &TrainingArguments<I, L, NI, NL>
@@ -30,8 +43,41 @@ impl TrainerState {
 			iter_step: 0,
 			gradient_accumulation_steps: args.gradient_accumulation_steps,
 			max_steps: args.max_steps,
-			current_lr: args.lr
+			current_lr: args.lr,
+			metrics: HashMap::new(),
+			best_metric: None
+		}
+	}
+
+	/// Stores the progress of training in `checkpoint`'s properties, so it can be restored by
+	/// [`TrainerState::restore`].
+	pub(crate) fn persist(&self, checkpoint: &Checkpoint) -> Result<()> {
+		checkpoint.set_property(GLOBAL_STEP_PROPERTY, &Property::Int(self.global_step as i64))?;
+		checkpoint.set_property(ITER_STEP_PROPERTY, &Property::Int(self.iter_step as i64))?;
+		checkpoint.set_property(LR_PROPERTY, &Property::Float(self.current_lr))?;
+		if let Some(best_metric) = self.best_metric {
+			checkpoint.set_property(BEST_METRIC_PROPERTY, &Property::Float(best_metric))?;
 		}
+		Ok(())
+	}
+
+	/// Restores the progress of training from a checkpoint saved by [`Trainer::train`]. Returns the index of the next
+	/// batch to train on, or `None` if the checkpoint holds no training progress.
+	pub(crate) fn restore(&mut self, checkpoint: &Checkpoint) -> Option<usize> {
+		let (Some(Property::Int(global_step)), Some(Property::Int(iter_step))) =
+			(checkpoint.get_property(GLOBAL_STEP_PROPERTY), checkpoint.get_property(ITER_STEP_PROPERTY))
+		else {
+			return None;
+		};
+		self.global_step = global_step as usize;
+		self.iter_step = iter_step as usize;
+		if let Some(Property::Float(lr)) = checkpoint.get_property(LR_PROPERTY) {
+			self.current_lr = lr;
+		}
+		if let Some(Property::Float(best_metric)) = checkpoint.get_property(BEST_METRIC_PROPERTY) {
+			self.best_metric = Some(best_metric);
+		}
+		Some(self.iter_step + 1)
 	}
 }
 
@@ -40,12 +86,18 @@ impl TrainerState {
 pub struct TrainerControl<'t> {
 	pub(crate) halt: bool,
 	pub(crate) lr: Option<f32>,
+	pub(crate) metrics: Vec<(String, f32)>,
 	trainer: &'t Trainer
 }
 
 impl<'t> TrainerControl<'t> {
 	pub(crate) fn new(trainer: &'t Trainer) -> Self {
-		Self { halt: false, trainer, lr: None }
+		Self {
+			halt: false,
+			trainer,
+			lr: None,
+			metrics: Vec::new()
+		}
 	}
 
 	/// Halts training. Once all callbacks have been called, training will immediately end.
@@ -60,6 +112,13 @@ impl<'t> TrainerControl<'t> {
 		self.lr = Some(lr);
 	}
 
+	/// Reports the value of a metric computed by this callback, i.e. during [`TrainerCallbacks::eval_end`]. Reported
+	/// metrics are made available in [`TrainerState::metrics`] and can be monitored by
+	/// [`CheckpointStrategy::BestMetric`](super::CheckpointStrategy::BestMetric).
+	pub fn report_metric(&mut self, name: impl Into<String>, value: f32) {
+		self.metrics.push((name.into(), value));
+	}
+
 	/// Export the model as a complete ONNX graph.
 	pub fn export<O: AsRef<str>>(&self, out_path: impl AsRef<Path>, output_names: impl AsRef<[O]>) -> Result<()> {
 		self.trainer.export(out_path, output_names)
@@ -90,6 +149,14 @@ pub trait TrainerCallbacks: Send {
 	fn eval_end(&mut self, eval_loss: f32, state: &TrainerState, control: &mut TrainerControl<'_>) -> Result<()> {
 		Ok(())
 	}
+	/// Called once per evaluation, after [`TrainerCallbacks::eval_end`], with all metrics computed for this evaluation.
+	///
+	/// `metrics` contains `eval_loss`, the average of every other scalar `f32` output of the eval graph (keyed as
+	/// `eval_<output name>`), and any metrics reported by callbacks during `eval_end` via
+	/// [`TrainerControl::report_metric`]. This makes it a convenient place to forward evaluation results to a logger.
+	fn evaluate(&mut self, metrics: &HashMap<String, f32>, state: &TrainerState, control: &mut TrainerControl<'_>) -> Result<()> {
+		Ok(())
+	}
 
 	/// Called immediately after performing a single forward & backward pass. See also
 	/// [`TrainerCallbacks::optimizer_step`], which is called immediately after updating the optimizer.
@@ -109,3 +176,98 @@ pub trait TrainerCallbacks: Send {
 		Ok(())
 	}
 }
+
+/// A [`TrainerCallbacks`] implementation which logs training progress every `logging_steps` optimizer steps, including
+/// the average training loss since the last report, the current learning rate, and throughput. Evaluation metrics are
+/// logged after each evaluation.
+///
+/// Progress is logged at the `INFO` level via [`tracing`](https://crates.io/crates/tracing), so it is only visible
+/// with the `tracing` feature enabled and a subscriber installed.
+///
+/// This is used by default when no callbacks are added to the [`TrainingArguments`]; see
+/// [`TrainingArguments::with_logging_steps`].
+#[derive(Debug, Clone)]
+pub struct ProgressLogger {
+	logging_steps: usize,
+	batch_size: Option<usize>,
+	loss_sum: f32,
+	loss_count: usize,
+	window_start: Option<(Instant, usize)>
+}
+
+impl ProgressLogger {
+	/// Creates a logger that reports progress every `logging_steps` optimizer steps.
+	pub fn new(logging_steps: usize) -> Self {
+		Self {
+			logging_steps: logging_steps.max(1),
+			batch_size: None,
+			loss_sum: 0.0,
+			loss_count: 0,
+			window_start: None
+		}
+	}
+
+	/// Sets the number of samples per batch, so that throughput can be reported in samples per second instead of
+	/// batches per second.
+	pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+		self.batch_size = Some(batch_size);
+		self
+	}
+}
+
+impl TrainerCallbacks for ProgressLogger {
+	fn train_step(&mut self, train_loss: f32, state: &TrainerState, _: &mut TrainerControl<'_>) -> Result<()> {
+		self.loss_sum += train_loss;
+		self.loss_count += 1;
+		// Time is measured from the end of the first batch, so that batch is excluded from the throughput.
+		if self.window_start.is_none() {
+			self.window_start = Some((Instant::now(), state.iter_step));
+		}
+		Ok(())
+	}
+
+	fn optimizer_step(&mut self, _: f32, state: &TrainerState, control: &mut TrainerControl<'_>) -> Result<()> {
+		if state.global_step % self.logging_steps != 0 {
+			return Ok(());
+		}
+
+		let mut line = if state.max_steps == usize::MAX {
+			format!("step {}", state.global_step)
+		} else {
+			format!("step {}/{}", state.global_step, state.max_steps / state.gradient_accumulation_steps)
+		};
+		if let Some(epoch) = state.epoch {
+			line.push_str(&format!(" | epoch {epoch:.2}"));
+		}
+		line.push_str(&format!(" | loss {:.4} | lr {:.3e}", self.loss_sum / self.loss_count.max(1) as f32, control.optimizer().lr()?));
+		if let Some((start, start_step)) = self.window_start {
+			let elapsed = start.elapsed().as_secs_f32();
+			let batches = state.iter_step - start_step;
+			if batches > 0 && elapsed > 0.0 {
+				match self.batch_size {
+					Some(batch_size) => line.push_str(&format!(" | {:.2} samples/s", (batches * batch_size) as f32 / elapsed)),
+					None => line.push_str(&format!(" | {:.2} batches/s", batches as f32 / elapsed))
+				}
+			}
+		}
+		crate::info!("{line}");
+
+		self.loss_sum = 0.0;
+		self.loss_count = 0;
+		self.window_start = Some((Instant::now(), state.iter_step));
+		Ok(())
+	}
+
+	fn evaluate(&mut self, metrics: &HashMap<String, f32>, state: &TrainerState, _: &mut TrainerControl<'_>) -> Result<()> {
+		#[cfg(feature = "tracing")]
+		{
+			let mut metrics: Vec<_> = metrics.iter().collect();
+			metrics.sort_by(|a, b| a.0.cmp(b.0));
+			let metrics: Vec<_> = metrics.into_iter().map(|(name, value)| format!("{name} {value:.4}")).collect();
+			crate::info!("eval @ step {} | {}", state.global_step, metrics.join(" | "));
+		}
+		#[cfg(not(feature = "tracing"))]
+		let _ = (metrics, state);
+		Ok(())
+	}
+}
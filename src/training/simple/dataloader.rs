@@ -1,5 +1,24 @@
 use crate::error::Result;
 
+/// Loads batches of training data for [`Trainer::train`](crate::training::Trainer::train).
+///
+/// Each batch is a tuple of `(inputs, labels)`, where both elements can be anything convertible to
+/// [`SessionInputs`](crate::session::SessionInputs), so a batch can contain any number of input & label tensors. The
+/// inputs are fed to the training graph first, followed by the labels; positional inputs must therefore be given in the
+/// order the training graph declares them, while named inputs (i.e. a `Vec` of `(name, value)`) are matched by name.
+///
+/// ```no_run
+/// # use ort::value::Tensor;
+/// # fn main() -> ort::Result<()> {
+/// let loader = |_: usize| -> ort::Result<_> {
+/// 	let input_ids = Tensor::from_array(([1usize, 4], vec![101_i64, 7592, 2088, 102]))?;
+/// 	let attention_mask = Tensor::from_array(([1usize, 4], vec![1_i64; 4]))?;
+/// 	let labels = Tensor::from_array(([1usize], vec![1_i64]))?;
+/// 	Ok((ort::inputs![input_ids, attention_mask], ort::inputs![labels]))
+/// };
+/// # 	Ok(())
+/// # }
+/// ```
 #[allow(clippy::len_without_is_empty)]
 pub trait DataLoader<I, L> {
 	/// Synchronously loads the batch at index `idx`.
@@ -1,13 +1,22 @@
-use std::{collections::VecDeque, fs};
+use std::{
+	collections::{HashMap, VecDeque},
+	fs,
+	path::PathBuf,
+	sync::PoisonError
+};
 
-use crate::{error::Result, session::input::SessionInputs, training::Trainer};
+use crate::{
+	error::Result,
+	session::input::SessionInputs,
+	training::{SEED_LOCK, Trainer, set_seed}
+};
 
 mod dataloader;
 pub use self::dataloader::{DataLoader, IterableDataLoader, iterable_data_loader};
 mod args;
-pub use self::args::{CheckpointStrategy, EvaluationStrategy, TrainingArguments};
+pub use self::args::{CheckpointStrategy, EvaluationStrategy, MetricMode, TrainingArguments};
 mod callbacks;
-pub use self::callbacks::{TrainerCallbacks, TrainerControl, TrainerState};
+pub use self::callbacks::{ProgressLogger, TrainerCallbacks, TrainerControl, TrainerState};
 
 macro_rules! callback {
 	($which:ident($self:expr, $optimizer:expr, $args:expr, $state:expr)) => {
@@ -16,8 +25,10 @@ macro_rules! callback {
 			let mut control = TrainerControl::new($self);
 			cb.$which(&$state, &mut control)?;
 			halt = halt || control.halt;
+			$state.metrics.extend(control.metrics);
 			if let Some(lr) = control.lr {
 				$optimizer.set_lr(lr)?;
+				$state.current_lr = lr;
 			}
 		}
 		if halt {
@@ -30,8 +41,10 @@ macro_rules! callback {
 			let mut control = TrainerControl::new($self);
 			cb.$which($($addt,)* &$state, &mut control)?;
 			halt = halt || control.halt;
+			$state.metrics.extend(control.metrics);
 			if let Some(lr) = control.lr {
 				$optimizer.set_lr(lr)?;
+				$state.current_lr = lr;
 			}
 		}
 		if halt {
@@ -41,17 +54,74 @@ macro_rules! callback {
 }
 
 impl Trainer {
+	/// Trains the model according to `args`.
+	///
+	/// ## Resuming training
+	/// Each checkpoint saved during training (see [`TrainingArguments::with_ckpt_strategy`]) also stores the progress
+	/// of training as properties of the checkpoint:
+	/// - [`TrainerState::global_step`] & [`TrainerState::iter_step`], under `ort.trainer.global_step` &
+	///   `ort.trainer.iter_step`;
+	/// - [`TrainerState::current_lr`], under `ort.trainer.lr`;
+	/// - [`TrainerState::best_metric`], under `ort.trainer.best_metric`, if a metric has been monitored.
+	///
+	/// If resuming is enabled with [`TrainingArguments::with_resume`] and the trainer's checkpoint holds such progress,
+	/// i.e. this trainer was created from a saved checkpoint, training resumes from the batch after the one the
+	/// checkpoint was saved at, with the step counters, learning rate, and best metric restored;
+	/// [`TrainerState::epoch`] is derived from the restored step. Learning rate schedules implemented by callbacks via
+	/// [`TrainerControl::set_lr`] thus continue where they left off. A schedule registered with
+	/// [`Optimizer::register_scheduler`](crate::training::Optimizer::register_scheduler) (and stepped by a callback
+	/// after each optimizer step) is fast-forwarded to the restored [`TrainerState::global_step`], as if
+	/// [`Optimizer::step_scheduler`](crate::training::Optimizer::step_scheduler) had been called once per step, and
+	/// its learning rate takes precedence over the restored one. `max_steps` still counts from the start of the
+	/// original run. Evaluation metrics other than the best metric, and the list of previously saved checkpoints pruned
+	/// by [`TrainingArguments::with_max_saved_ckpts`], are not persisted.
+	///
+	/// Resuming is opt-in because saving a checkpoint also stores the progress in the trainer's own checkpoint, so
+	/// otherwise a second call to `train` on the same trainer would pick up where the first call left off.
+	///
+	/// ```no_run
+	/// # use ort::{memory::Allocator, session::Session, training::{Checkpoint, Trainer}};
+	/// # fn main() -> ort::Result<()> {
+	/// let checkpoint = Checkpoint::load("checkpoints/epoch=2,step=3000.ortckpt")?;
+	/// let trainer =
+	/// 	Trainer::new_from_artifacts(Session::builder()?, Allocator::default(), "training_artifacts", Some(checkpoint))?;
+	/// // trainer.train(args.with_resume(true))? continues from step 3000
+	/// # 	Ok(())
+	/// # }
+	/// ```
 	pub fn train<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static, 'static, NL>>, const NI: usize, const NL: usize>(
 		&self,
 		mut args: TrainingArguments<I, L, NI, NL>
 	) -> Result<()> {
-		let mut optimizer = self.optimizer();
-		optimizer.set_lr(args.lr)?;
+		let _seed_guard = match args.seed {
+			Some(seed) => {
+				let guard = SEED_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+				set_seed(seed)?;
+				Some(guard)
+			}
+			None => None
+		};
+
+		if args.callbacks.is_empty() && args.logging_steps > 0 {
+			args.callbacks.push(Box::new(ProgressLogger::new(args.logging_steps)));
+		}
 
 		let mut saved_ckpts = VecDeque::new();
 		let mut state = TrainerState::new(&args);
-		let mut last_epoch = -1.0;
-		for (iter_step, _) in (0..args.max_steps).enumerate() {
+		let start_step = start_step(args.resume, || state.restore(self.checkpoint()));
+
+		let mut optimizer = self.optimizer();
+		optimizer.set_lr(state.current_lr)?;
+		if start_step > 0 {
+			optimizer.fast_forward_scheduler(state.global_step as i64)?;
+			state.current_lr = optimizer.lr()?;
+		}
+
+		let mut last_epoch = match (start_step, args.loader.len()) {
+			(1.., Some(dl_len)) => (state.iter_step as f32 / dl_len as f32).trunc(),
+			_ => -1.0
+		};
+		for iter_step in start_step..args.max_steps {
 			state.iter_step = iter_step;
 			state.epoch = args.loader.len().map(|dl_len| iter_step as f32 / dl_len as f32);
 
@@ -78,36 +148,61 @@ impl Trainer {
 			}
 
 			if args.ckpt_strategy.should_fire(state.global_step, iter_step, args.loader.len()) {
-				if !args.ckpt_path.exists() {
-					let _ = fs::create_dir_all(&args.ckpt_path);
-				}
-
-				let ckpt_path =
-					args.ckpt_path
-						.join(format!("epoch={},step={}.ortckpt", state.epoch.map(f32::trunc).unwrap_or(0.0) as usize, state.global_step));
-				self.checkpoint().save(&ckpt_path, true)?;
-
-				saved_ckpts.push_front(ckpt_path.clone());
-				while saved_ckpts.len() > args.max_saved_ckpts {
-					let Some(old_ckpt) = saved_ckpts.pop_back() else {
-						break;
-					};
-					let _ = fs::remove_file(old_ckpt);
-				}
+				self.save_ckpt(&args, &state, &mut saved_ckpts)?;
 			}
 
 			if args
 				.eval_strategy
 				.should_fire(state.global_step, iter_step, args.eval_loader.as_ref().and_then(|d| d.len()))
 			{
+				state.metrics.clear();
 				callback!(eval_begin(self, optimizer, args, state));
-				let eval_loss = self.eval_inner(&mut args)?;
+				let eval_metrics = self.eval_inner(&mut args)?;
+				let eval_loss = eval_metrics.get("eval_loss").copied().unwrap_or(0.0);
+				state.metrics.extend(eval_metrics);
 				callback!(eval_end(self, optimizer, args, state), eval_loss);
+				let metrics = state.metrics.clone();
+				callback!(evaluate(self, optimizer, args, state), &metrics);
+
+				if let CheckpointStrategy::BestMetric { name, mode } = &args.ckpt_strategy {
+					if let Some(&value) = state.metrics.get(name) {
+						if mode.is_improvement(state.best_metric, value) {
+							state.best_metric = Some(value);
+							self.save_ckpt(&args, &state, &mut saved_ckpts)?;
+						}
+					}
+				}
 			}
 		}
 		Ok(())
 	}
 
+	fn save_ckpt<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static, 'static, NL>>, const NI: usize, const NL: usize>(
+		&self,
+		args: &TrainingArguments<I, L, NI, NL>,
+		state: &TrainerState,
+		saved_ckpts: &mut VecDeque<PathBuf>
+	) -> Result<()> {
+		if !args.ckpt_path.exists() {
+			let _ = fs::create_dir_all(&args.ckpt_path);
+		}
+
+		let ckpt_path = args
+			.ckpt_path
+			.join(format!("epoch={},step={}.ortckpt", state.epoch.map(f32::trunc).unwrap_or(0.0) as usize, state.global_step));
+		state.persist(self.checkpoint())?;
+		self.checkpoint().save(&ckpt_path, true)?;
+
+		saved_ckpts.push_front(ckpt_path);
+		while saved_ckpts.len() > args.max_saved_ckpts {
+			let Some(old_ckpt) = saved_ckpts.pop_back() else {
+				break;
+			};
+			let _ = fs::remove_file(old_ckpt);
+		}
+		Ok(())
+	}
+
 	fn handle_halt(&self, cbs: &mut Vec<Box<dyn TrainerCallbacks>>, state: &TrainerState) -> Result<()> {
 		for cb in cbs {
 			let mut control = TrainerControl::new(self);
@@ -119,21 +214,75 @@ impl Trainer {
 	pub(crate) fn eval_inner<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static, 'static, NL>>, const NI: usize, const NL: usize>(
 		&self,
 		args: &mut TrainingArguments<I, L, NI, NL>
-	) -> crate::Result<f32> {
+	) -> crate::Result<HashMap<String, f32>> {
+		let mut metrics = HashMap::new();
 		let Some(eval_loader) = &mut args.eval_loader else {
-			return Ok(0.0);
+			metrics.insert("eval_loss".to_string(), 0.0);
+			return Ok(metrics);
 		};
 
-		let mut total_loss = 0.0;
 		for step in 0..args.max_eval_steps.min(eval_loader.len().unwrap_or(usize::MAX)) {
 			let (inputs, labels) = eval_loader.load(step)?;
 			let (inputs, labels) = (inputs.into(), labels.into());
 
 			let outputs = self.eval_step(inputs, labels)?;
 			let loss = outputs[0].try_extract_scalar::<f32>()?;
-			total_loss = (total_loss * (step as f32) + loss) / (step as f32 + 1.);
+			let mut record = |name: String, value: f32| {
+				let mean = metrics.entry(name).or_insert(0.0);
+				*mean = (*mean * (step as f32) + value) / (step as f32 + 1.);
+			};
+			record("eval_loss".to_string(), loss);
+			// Any other scalar outputs of the eval graph are averaged and reported as metrics too.
+			for (name, value) in outputs.iter().skip(1) {
+				if let Ok(value) = value.try_extract_scalar::<f32>() {
+					record(format!("eval_{name}"), value);
+				}
+			}
 		}
 
-		Ok(total_loss)
+		Ok(metrics)
+	}
+}
+
+/// Returns the index of the batch to start training from: if `resume` is set, the batch after the one at which the
+/// progress returned by `restore` was saved, otherwise (or if there is no saved progress) the first batch.
+fn start_step(resume: bool, restore: impl FnOnce() -> Option<usize>) -> usize {
+	if resume { restore().unwrap_or(0) } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{MetricMode, start_step};
+
+	#[test]
+	fn test_start_step() {
+		// Simulates back-to-back calls to `train` on the same trainer, each of which saves its progress (the next batch,
+		// 5) into the trainer's own checkpoint. Later calls must only pick it up when resuming was requested.
+		let mut saved_progress = None;
+		let mut train = |resume| {
+			let start = start_step(resume, || saved_progress);
+			saved_progress = Some(5);
+			start
+		};
+		assert_eq!(train(false), 0);
+		assert_eq!(train(false), 0);
+		assert_eq!(train(true), 5);
+
+		assert_eq!(start_step(true, || None), 0);
+	}
+
+	#[test]
+	fn test_metric_improvement() {
+		assert!(MetricMode::Min.is_improvement(None, 1.0));
+		assert!(MetricMode::Min.is_improvement(Some(1.0), 0.5));
+		assert!(!MetricMode::Min.is_improvement(Some(1.0), 1.5));
+		assert!(MetricMode::Max.is_improvement(Some(1.0), 1.5));
+
+		// Non-finite values are never the best, and a non-finite best (e.g. from an older checkpoint) can always be
+		// improved upon.
+		assert!(!MetricMode::Min.is_improvement(None, f32::NAN));
+		assert!(!MetricMode::Max.is_improvement(Some(1.0), f32::INFINITY));
+		assert!(MetricMode::Min.is_improvement(Some(f32::NAN), 2.0));
+		assert!(MetricMode::Max.is_improvement(Some(f32::NAN), 2.0));
 	}
 }
@@ -1,5 +1,6 @@
 use alloc::borrow::Cow;
 use core::{
+	cell::RefCell,
 	fmt,
 	ptr::{self, NonNull}
 };
@@ -7,10 +8,10 @@ use std::path::Path;
 
 use ort_sys::c_char;
 
-use super::{Checkpoint, Optimizer, trainsys};
+use super::{Checkpoint, Optimizer, ParameterStats, SchedulerState, trainsys};
 use crate::{
 	AsPointer, char_p_to_string,
-	error::{Result, status_to_result},
+	error::{Error, ErrorCode, Result, status_to_result},
 	memory::Allocator,
 	session::{RunOptions, SessionInputValue, SessionInputs, SessionOutputs, builder::SessionBuilder},
 	tensor::IntoTensorElementType,
@@ -18,6 +19,45 @@ use crate::{
 	value::{Tensor, Value}
 };
 
+/// A training session, which runs the training, evaluation, and optimizer models generated from a model's training
+/// artifacts.
+///
+/// ## Freezing parameters
+/// Which parameters are trainable is fixed when the training artifacts are generated, and cannot be changed at
+/// runtime; ONNX Runtime's training API has no equivalent of PyTorch's `requires_grad_(False)`. To freeze part of a
+/// model (e.g. the backbone in transfer learning), list only the parameters to train in `requires_grad`, and the rest
+/// in `frozen_params`, when generating the artifacts:
+///
+/// ```python
+/// from onnxruntime.training import artifacts
+///
+/// artifacts.generate_artifacts(
+///     model,
+///     requires_grad=["head.weight", "head.bias"],
+///     frozen_params=["backbone.conv1.weight", "backbone.conv1.bias"],
+///     loss=artifacts.LossType.CrossEntropyLoss,
+///     optimizer=artifacts.OptimType.AdamW,
+///     artifact_directory="training_artifacts"
+/// )
+/// ```
+///
+/// Frozen parameters are still stored in the checkpoint, and can be read & updated with
+/// [`Checkpoint::get_parameter`] & [`Checkpoint::update_parameter`], but receive no gradients and are not updated by
+/// the optimizer. [`Trainer::parameter_stats`] reports how many parameters are trainable and frozen:
+///
+/// ```no_run
+/// # use ort::training::Trainer;
+/// # fn main() -> ort::Result<()> {
+/// # 	let trainer: Trainer = unimplemented!();
+/// let stats = trainer.parameter_stats()?;
+/// println!("{} of {} parameters frozen", stats.frozen().unwrap_or_default(), stats.count);
+/// # 	Ok(())
+/// # }
+/// ```
+///
+/// To train different sets of parameters in different phases (e.g. train the head first, then unfreeze the whole
+/// model), generate one set of artifacts per phase from the same base model, and copy the weights trained in one phase
+/// into the next phase's checkpoint with [`Checkpoint::get_parameter`] & [`Checkpoint::update_parameter`].
 #[derive(Debug)]
 pub struct Trainer {
 	ptr: NonNull<ort_sys::OrtTrainingSession>,
@@ -26,6 +66,7 @@ pub struct Trainer {
 	train_input_names: Vec<String>,
 	eval_input_names: Vec<String>,
 	ckpt: Checkpoint,
+	scheduler: RefCell<Option<SchedulerState>>,
 	_allocator: Allocator
 }
 
@@ -73,6 +114,12 @@ impl Trainer {
 		)
 	}
 
+	/// Creates a trainer from training, evaluation, and optimizer models held in memory, with an already loaded
+	/// checkpoint.
+	///
+	/// The evaluation and optimizer models are optional and may be passed as empty buffers if the trainer will not be
+	/// used for evaluation or optimizer steps. Returns an error with [`ErrorCode::InvalidArgument`] if the training
+	/// model buffer is empty.
 	pub fn new_from_memory(
 		session_options: SessionBuilder,
 		allocator: Allocator,
@@ -81,6 +128,8 @@ impl Trainer {
 		eval_model: &[u8],
 		optimizer_model: &[u8]
 	) -> Result<Self> {
+		check_artifact_buffer("training model", training_model)?;
+
 		let env = crate::environment::get_environment()?;
 
 		let mut ptr: *mut ort_sys::OrtTrainingSession = ptr::null_mut();
@@ -104,6 +153,44 @@ impl Trainer {
 		Self::new_inner(ptr, allocator, ckpt)
 	}
 
+	/// Creates a trainer entirely from in-memory training artifacts, without touching the filesystem; useful in
+	/// sandboxed environments where artifacts are received as bytes.
+	///
+	/// `checkpoint` is the contents of the artifacts' `checkpoint` file, loaded with [`Checkpoint::load_from_buffer`];
+	/// the models are the contents of `training_model.onnx`, `eval_model.onnx`, and `optimizer_model.onnx`. As with
+	/// [`Trainer::new_from_memory`], the evaluation and optimizer models are optional and may be empty. Returns an error
+	/// with [`ErrorCode::InvalidArgument`] naming the offending artifact if the checkpoint or training model buffer is
+	/// empty.
+	///
+	/// ```no_run
+	/// # use ort::{memory::Allocator, session::Session, training::Trainer};
+	/// # fn main() -> ort::Result<()> {
+	/// # 	let read = |_name: &str| -> Vec<u8> { unimplemented!() };
+	/// let trainer = Trainer::new_from_buffers(
+	/// 	Session::builder()?,
+	/// 	Allocator::default(),
+	/// 	&read("checkpoint"),
+	/// 	&read("training_model.onnx"),
+	/// 	&read("eval_model.onnx"),
+	/// 	&read("optimizer_model.onnx")
+	/// )?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[doc(alias = "from_buffers")]
+	pub fn new_from_buffers(
+		session_options: SessionBuilder,
+		allocator: Allocator,
+		checkpoint: &[u8],
+		training_model: &[u8],
+		eval_model: &[u8],
+		optimizer_model: &[u8]
+	) -> Result<Self> {
+		check_artifact_buffer("checkpoint", checkpoint)?;
+		let ckpt = Checkpoint::load_from_buffer(checkpoint)?;
+		Self::new_from_memory(session_options, allocator, ckpt, training_model, eval_model, optimizer_model)
+	}
+
 	fn new_inner(ptr: NonNull<ort_sys::OrtTrainingSession>, allocator: Allocator, ckpt: Checkpoint) -> Result<Self> {
 		let train_output_names =
 			extract_io_names(ptr, &allocator, trainsys![TrainingSessionGetTrainingModelOutputCount], trainsys![TrainingSessionGetTrainingModelOutputName])?;
@@ -122,7 +209,8 @@ impl Trainer {
 			train_input_names,
 			eval_output_names,
 			eval_input_names,
-			ckpt
+			ckpt,
+			scheduler: RefCell::new(None)
 		})
 	}
 
@@ -263,6 +351,28 @@ impl Trainer {
 		Ok(out)
 	}
 
+	/// Returns the number of parameters in the model, both in total and trainable, and their total size in bytes.
+	///
+	/// ```no_run
+	/// # use ort::training::Trainer;
+	/// # fn main() -> ort::Result<()> {
+	/// # 	let trainer: Trainer = unimplemented!();
+	/// let stats = trainer.parameter_stats()?;
+	/// println!("{} parameters ({} trainable)", stats.count, stats.trainable.unwrap_or_default());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn parameter_stats(&self) -> Result<ParameterStats> {
+		let count = self.num_params(false)? as u64;
+		let trainable = self.num_params(true)? as u64;
+		Ok(ParameterStats {
+			count,
+			trainable: Some(trainable),
+			// ONNX Runtime only supports training `f32` parameters.
+			bytes: count * size_of::<f32>() as u64
+		})
+	}
+
 	pub fn copy_parameters_to<T: IntoTensorElementType + fmt::Debug>(&self, value: &mut Tensor<T>, trainable_only: bool) -> Result<()> {
 		trainsys![unsafe CopyParametersToBuffer(self.ptr.as_ptr(), value.ptr_mut(), trainable_only)?];
 		Ok(())
@@ -274,7 +384,7 @@ impl Trainer {
 	}
 
 	pub fn optimizer(&self) -> Optimizer<'_> {
-		Optimizer::new(self.ptr)
+		Optimizer::new(self.ptr, &self.scheduler)
 	}
 
 	pub fn checkpoint(&self) -> &Checkpoint {
@@ -311,6 +421,13 @@ fn mapped_inputs<'v, 'a>(input_names: &[String], values: &'a [(Cow<'_, str>, Ses
 	out
 }
 
+fn check_artifact_buffer(artifact: &str, buffer: &[u8]) -> Result<()> {
+	if buffer.is_empty() {
+		return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("The {artifact} buffer is empty")));
+	}
+	Ok(())
+}
+
 fn extract_io_names(
 	ptr: NonNull<ort_sys::OrtTrainingSession>,
 	allocator: &Allocator,
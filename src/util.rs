@@ -303,6 +303,30 @@ impl<T> Drop for OnceLock<T> {
 #[doc(hidden)]
 pub fn cold() {}
 
+/// Runs `f`, a callback invoked by ONNX Runtime, converting any panic into an error so that it does not unwind into
+/// ONNX Runtime's C++ code. `context` describes the callback for the error message.
+///
+/// Without the `std` feature, panics can't be caught; since unwinding out of an `extern "system"` function aborts the
+/// process, this is still sound, just less graceful.
+pub(crate) fn catch_panic<T>(context: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+	#[cfg(feature = "std")]
+	{
+		std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+			let message = payload
+				.downcast_ref::<&str>()
+				.copied()
+				.or_else(|| payload.downcast_ref::<alloc::string::String>().map(|s| s.as_str()))
+				.unwrap_or("<non-string panic payload>");
+			Err(crate::Error::new(alloc::format!("{context} panicked: {message}")))
+		})
+	}
+	#[cfg(not(feature = "std"))]
+	{
+		let _ = context;
+		f()
+	}
+}
+
 #[inline]
 pub(crate) fn with_cstr<T>(bytes: &[u8], f: &dyn Fn(&CStr) -> Result<T>) -> Result<T> {
 	fn run_with_heap_cstr<T>(bytes: &[u8], f: &dyn Fn(&CStr) -> Result<T>) -> Result<T> {
@@ -376,3 +400,19 @@ where
 		run_with_heap_cstr_array(strings, f)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::catch_panic;
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn test_catch_panic() {
+		assert_eq!(catch_panic("test", || Ok(1)).expect("should not panic"), 1);
+
+		let err = catch_panic::<()>("Kernel::compute", || panic!("out of bounds")).expect_err("should have caught panic");
+		assert_eq!(err.to_string(), "Kernel::compute panicked: out of bounds");
+		let err = catch_panic::<()>("Kernel::compute", || panic!("index {}", 3)).expect_err("should have caught panic");
+		assert_eq!(err.to_string(), "Kernel::compute panicked: index 3");
+	}
+}
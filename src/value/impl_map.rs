@@ -85,19 +85,18 @@ pub type MapRef<'v, K, V> = ValueRef<'v, MapValueType<K, V>>;
 pub type MapRefMut<'v, K, V> = ValueRefMut<'v, MapValueType<K, V>>;
 
 impl<Type: MapValueTypeMarker + ?Sized> Value<Type> {
+	/// Extracts the key-value pairs of a map, in the order they are stored in the map.
+	///
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if this value is not a map, if its key or value type does
+	/// not match `K` or `V`, or if its keys & values are not 1-dimensional tensors of the same length. See
+	/// [`Value::try_extract_map`] for an example.
 	pub fn try_extract_key_values<K: IntoTensorElementType + Clone + Hash + Eq, V: PrimitiveTensorElementType + Clone>(&self) -> Result<Vec<(K, V)>> {
 		match self.dtype() {
 			ValueType::Map { key, value } => {
 				let k_type = K::into_tensor_element_type();
-				if k_type != *key {
-					return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot extract Map<{:?}, _> (value has K type {:?})", k_type, key)));
-				}
 				let v_type = V::into_tensor_element_type();
-				if v_type != *value {
-					return Err(Error::new_with_code(
-						ErrorCode::InvalidArgument,
-						format!("Cannot extract Map<{}, {}> from Map<{}, {}>", K::into_tensor_element_type(), V::into_tensor_element_type(), k_type, v_type)
-					));
+				if k_type != *key || v_type != *value {
+					return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot extract Map<{k_type}, {v_type}> from Map<{key}, {value}>")));
 				}
 
 				let allocator = Allocator::default();
@@ -127,13 +126,7 @@ impl<Type: MapValueTypeMarker + ?Sized> Value<Type> {
 							} else {
 								return Err(Error::new_with_code(
 									ErrorCode::InvalidArgument,
-									format!(
-										"Cannot extract Map<{}, {}> from Map<{}, {}>",
-										K::into_tensor_element_type(),
-										V::into_tensor_element_type(),
-										k_type,
-										v_type
-									)
+									format!("Cannot extract Map<{k_type}, {v_type}> from map with keys of type {ty}")
 								));
 							}
 						}
@@ -145,9 +138,7 @@ impl<Type: MapValueTypeMarker + ?Sized> Value<Type> {
 					let value_value: DynTensor = unsafe { Value::from_ptr(NonNull::new_unchecked(value_tensor_ptr), None) };
 					let (value_tensor_shape, value_tensor) = value_value.try_extract_tensor::<V>()?;
 
-					assert_eq!(key_tensor_shape.len(), 1);
-					assert_eq!(value_tensor_shape.len(), 1);
-					assert_eq!(key_tensor_shape[0], value_tensor_shape[0]);
+					check_key_value_shapes(key_tensor_shape, value_tensor_shape)?;
 
 					let mut vec = Vec::with_capacity(key_tensor_shape[0] as _);
 					for i in 0..key_tensor_shape[0] as usize {
@@ -166,9 +157,7 @@ impl<Type: MapValueTypeMarker + ?Sized> Value<Type> {
 					let value_value: DynTensor = unsafe { Value::from_ptr(NonNull::new_unchecked(value_tensor_ptr), None) };
 					let (value_tensor_shape, value_tensor) = value_value.try_extract_tensor::<V>()?;
 
-					assert_eq!(key_tensor_shape.len(), 1);
-					assert_eq!(value_tensor_shape.len(), 1);
-					assert_eq!(key_tensor_shape[0], value_tensor_shape[0]);
+					check_key_value_shapes(key_tensor_shape, value_tensor_shape)?;
 
 					let mut vec = Vec::with_capacity(key_tensor_shape[0] as _);
 					for i in 0..key_tensor_shape[0] as usize {
@@ -184,6 +173,26 @@ impl<Type: MapValueTypeMarker + ?Sized> Value<Type> {
 		}
 	}
 
+	/// Extracts the contents of a map into a [`HashMap`], e.g. the class probabilities output by the `ZipMap` operator
+	/// at the end of many scikit-learn classifiers converted to ONNX.
+	///
+	/// This can be called on a [`DynValue`], e.g. a session output, without downcasting it first.
+	///
+	/// ```
+	/// # use ort::value::{DynValue, Map};
+	/// # fn main() -> ort::Result<()> {
+	/// # let output: DynValue = Map::<i64, f32>::new([(0, 0.25), (1, 0.75)])?.into_dyn();
+	/// // let output = &outputs["output_probability"];
+	/// let probabilities = output.try_extract_map::<i64, f32>()?;
+	/// assert_eq!(probabilities[&1], 0.75);
+	///
+	/// assert!(output.try_extract_map::<String, f32>().is_err());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if this value is not a map, if its key or value type does
+	/// not match `K` or `V`, or if its keys & values are not 1-dimensional tensors of the same length.
 	#[cfg(feature = "std")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 	pub fn try_extract_map<K: IntoTensorElementType + Clone + Hash + Eq, V: PrimitiveTensorElementType + Clone>(&self) -> Result<HashMap<K, V>> {
@@ -191,6 +200,16 @@ impl<Type: MapValueTypeMarker + ?Sized> Value<Type> {
 	}
 }
 
+fn check_key_value_shapes(key_shape: &[i64], value_shape: &[i64]) -> Result<()> {
+	if key_shape.len() != 1 || value_shape.len() != 1 || key_shape[0] != value_shape[0] {
+		return Err(Error::new_with_code(
+			ErrorCode::InvalidArgument,
+			format!("Map has mismatched key & value tensors: keys have shape {key_shape:?}, but values have shape {value_shape:?}")
+		));
+	}
+	Ok(())
+}
+
 impl<K: PrimitiveTensorElementType + Debug + Clone + Hash + Eq + 'static, V: PrimitiveTensorElementType + Debug + Clone + 'static> Value<MapValueType<K, V>> {
 	/// Creates a [`Map`] from an iterable emitting `K` and `V`.
 	///
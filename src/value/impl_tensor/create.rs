@@ -4,12 +4,13 @@ use core::{
 	ffi::c_void,
 	fmt::Debug,
 	marker::PhantomData,
-	mem::size_of,
+	mem::{align_of, size_of},
 	ptr::{self, NonNull}
 };
 
 #[cfg(feature = "ndarray")]
 use ndarray::{ArcArray, Array, ArrayView, ArrayViewMut, CowArray, Dimension};
+use smallvec::SmallVec;
 
 use super::{DynTensor, Tensor, TensorRef, TensorRefMut};
 use crate::{
@@ -28,6 +29,7 @@ impl Tensor<String> {
 	/// - (with feature `ndarray`) a shared reference to a [`ndarray::CowArray`] (`&CowArray<'_, T, D>`) or
 	///   [`ndarray::Array`] (`&Array<T, D>`);
 	/// - (with feature `ndarray`) an [`ndarray::ArcArray`] or [`ndarray::ArrayView`];
+	/// - a reference to a fixed-size array (`&[T; N]`), creating a 1-dimensional tensor of `N` elements;
 	/// - a tuple of `(shape, data)` where:
 	///   * `shape` is one of `Vec<I>`, `[I; N]` or `&[I]`, where `I` is `i64` or `usize`, and
 	///   * `data` is one of `&[T]`, `Arc<[T]>`, or `Arc<Box<[T]>>`.
@@ -115,7 +117,8 @@ impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 	/// Construct an owned tensor from an array of data.
 	///
 	/// Owned tensors can be created from:
-	/// - (with feature `ndarray`) an owned [`ndarray::Array`], or
+	/// - (with feature `ndarray`) an owned [`ndarray::Array`],
+	/// - a fixed-size array `[T; N]`, creating a 1-dimensional tensor of `N` elements, or
 	/// - a tuple of `(shape, data)` where:
 	///   * `shape` is one of `Vec<I>`, `[I]` or `&[I]`, where `I` is `i64` or `usize`, and
 	///   * `data` is one of `Vec<T>` or `Box<[T]>`.
@@ -126,6 +129,9 @@ impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 	/// // Create a tensor from a raw data vector
 	/// let tensor = Tensor::from_array(([1usize, 2, 3], vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0].into_boxed_slice()))?;
 	///
+	/// // Create a 1-dimensional tensor of shape `[4]`
+	/// let bbox = Tensor::from_array([0.25_f32, 0.25, 0.75, 0.75])?;
+	///
 	/// // Create a tensor from an `ndarray::Array`
 	/// #[cfg(feature = "ndarray")]
 	/// let tensor = Tensor::from_array(ndarray::Array4::<f32>::zeros((1, 16, 16, 3)))?;
@@ -143,9 +149,62 @@ impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 		tensor_from_array(MemoryInfo::default(), shape, ptr.as_ptr().cast(), size_of::<T>(), T::into_tensor_element_type(), guard)
 			.map(|tensor| unsafe { tensor.transmute_type() })
 	}
+
+	/// Creates a view of this tensor's data with its elements reinterpreted as type `U`, without copying. The shape
+	/// of the view is the same as this tensor's. This can be used to, for example, inspect the bit patterns of an `f32`
+	/// tensor as `u32`s.
+	///
+	/// The view shares this tensor's buffer, which may be on a device other than the CPU.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2usize], vec![1.0_f32, -0.0]))?;
+	/// let bits = tensor.reinterpret::<u32>()?;
+	/// assert_eq!(bits.extract_tensor().1, &[0x3f80_0000, 0x8000_0000]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if:
+	/// - `T` and `U` differ in size;
+	/// - the tensor's data is not sufficiently aligned for `U`, which can happen for tensors created from user-provided
+	///   buffers when `U` has a stricter alignment than `T` (e.g. `Complex32` to `i64`);
+	/// - `U` is `bool` and `T` is not, since byte values other than `0` and `1` are not valid `bool`s.
+	pub fn reinterpret<U: PrimitiveTensorElementType + Debug>(&self) -> Result<TensorRef<'_, U>> {
+		let (from_type, to_type) = (T::into_tensor_element_type(), U::into_tensor_element_type());
+		if size_of::<T>() != size_of::<U>() {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!(
+					"Cannot reinterpret tensor of {from_type} ({} bytes) as {to_type} ({} bytes); element sizes must match",
+					size_of::<T>(),
+					size_of::<U>()
+				)
+			));
+		}
+		if to_type == TensorElementType::Bool && from_type != TensorElementType::Bool {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot reinterpret tensor of {from_type} as bool")));
+		}
+
+		let data = self.data_ptr()?.cast_mut();
+		if data as usize % align_of::<U>() != 0 {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot reinterpret tensor of {from_type} as {to_type}; data is not aligned to {} bytes", align_of::<U>())
+			));
+		}
+
+		tensor_from_array(self.memory_info().clone(), self.shape().clone(), data, size_of::<U>(), to_type, None).map(|tensor| {
+			let mut tensor: TensorRef<'_, U> = TensorRef::new(unsafe { tensor.transmute_type() });
+			tensor.upgradable = false;
+			tensor
+		})
+	}
 }
 
-fn tensor_from_array(
+pub(super) fn tensor_from_array(
 	memory_info: MemoryInfo,
 	shape: Shape,
 	data: *mut c_void,
@@ -153,13 +212,20 @@ fn tensor_from_array(
 	element_type: TensorElementType,
 	guard: Option<Box<dyn Any>>
 ) -> Result<DynTensor> {
+	let Some(data_len) = shape.checked_num_elements().and_then(|n| n.checked_mul(element_size)) else {
+		return Err(Error::new_with_code(
+			ErrorCode::InvalidArgument,
+			format!("Cannot create a tensor with shape {shape:?}; the size of its data in bytes overflows `usize`")
+		));
+	};
+
 	let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
 
 	ortsys![
 		unsafe CreateTensorWithDataAsOrtValue(
 			memory_info.ptr(),
 			data,
-			shape.num_elements() * element_size,
+			data_len,
 			shape.as_ptr(),
 			shape.len(),
 			element_type.into(),
@@ -191,6 +257,7 @@ impl<'a, T: PrimitiveTensorElementType + Debug> TensorRef<'a, T> {
 	/// - (with feature `ndarray`) a shared reference to a [`ndarray::CowArray`] (`&CowArray<'_, T, D>`) or
 	///   [`ndarray::Array`] (`&Array<T, D>`);
 	/// - (with feature `ndarray`) an [`ndarray::ArcArray`] or [`ndarray::ArrayView`];
+	/// - a reference to a fixed-size array (`&[T; N]`), creating a 1-dimensional tensor of `N` elements;
 	/// - a tuple of `(shape, data)` where:
 	///   * `shape` is one of `Vec<I>`, `[I; N]` or `&[I]`, where `I` is `i64` or `usize`, and
 	///   * `data` is one of `&[T]`, `Arc<[T]>`, or `Arc<Box<[T]>>`.
@@ -231,6 +298,7 @@ impl<'a, T: PrimitiveTensorElementType + Debug> TensorRefMut<'a, T> {
 	/// Mutably borrowed tensors can be created from:
 	/// - (with feature `ndarray`) an exclusive reference to an [`ndarray::Array`] (`&mut Array<T, D>`);
 	/// - (with feature `ndarray`) an [`ndarray::ArrayViewMut`];
+	/// - an exclusive reference to a fixed-size array (`&mut [T; N]`), creating a 1-dimensional tensor of `N` elements;
 	/// - a tuple of `(shape, &mut [T])`, where `shape` is one of `Vec<I>`, `[I; N]` or `&[I]`, where `I` is `i64` or
 	///   `usize`.
 	///
@@ -262,6 +330,41 @@ impl<'a, T: PrimitiveTensorElementType + Debug> TensorRefMut<'a, T> {
 		})
 	}
 
+	/// Construct a mutable tensor view from a CPU-accessible slice described by a custom [`MemoryInfo`], e.g. pinned
+	/// memory allocated by an execution provider's [`Allocator`].
+	///
+	/// This is a safe alternative to [`TensorRefMut::from_raw`] for memory the CPU can access directly.
+	///
+	/// ```
+	/// # use ort::{memory::{AllocationDevice, AllocatorType, MemoryInfo, MemoryType}, value::TensorRefMut};
+	/// # fn main() -> ort::Result<()> {
+	/// let mut data = vec![0.0_f32; 6];
+	/// let info = MemoryInfo::new(AllocationDevice::CUDA_PINNED, 0, AllocatorType::Device, MemoryType::CPUInput)?;
+	/// let tensor = TensorRefMut::from_slice_with_info(info, [2usize, 3], &mut data)?;
+	/// assert_eq!(**tensor.shape(), [2, 3]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if `info` does not describe CPU-accessible memory, or if the number of elements described by
+	/// `shape` does not match the length of `data`.
+	pub fn from_slice_with_info(info: MemoryInfo, shape: impl ToShape, data: &'a mut [T]) -> Result<TensorRefMut<'a, T>> {
+		if !info.is_cpu_accessible() {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot create a tensor from a slice with memory on device `{}`, which is not CPU accessible", info.allocation_device().as_str())
+			));
+		}
+
+		let shape = shape.to_shape(Some(data.len()))?;
+		tensor_from_array(info, shape, data.as_mut_ptr().cast(), size_of::<T>(), T::into_tensor_element_type(), None).map(|tensor| {
+			let mut tensor: TensorRefMut<'_, T> = TensorRefMut::new(unsafe { tensor.transmute_type() });
+			tensor.upgradable = false;
+			tensor
+		})
+	}
+
 	/// Create a mutable tensor view from a raw pointer and shape.
 	///
 	/// The length of data is determined by `T` and the given shape, so the given buffer must be at least
@@ -282,9 +385,13 @@ impl<'a, T: PrimitiveTensorElementType + Debug> TensorRefMut<'a, T> {
 	/// };
 	/// ```
 	///
+	/// See also [`DeviceTensorBuilder`](crate::value::DeviceTensorBuilder), which fills in the [`MemoryInfo`] for a
+	/// device and can create owned tensors for use with [`IoBinding`](crate::io_binding::IoBinding).
+	///
 	/// # Safety
 	/// - The pointer must be valid for the device description provided by `MemoryInfo`.
 	/// - The returned tensor must outlive the data described by the data pointer.
+	/// - If `T` is `bool`, every byte of the data must be `0` or `1`.
 	pub unsafe fn from_raw(info: MemoryInfo, data: *mut ort_sys::c_void, shape: Shape) -> Result<TensorRefMut<'a, T>> {
 		tensor_from_array(info, shape, data, size_of::<T>(), T::into_tensor_element_type(), None).map(|tensor| {
 			let mut tensor: TensorRefMut<'_, T> = TensorRefMut::new(unsafe { tensor.transmute_type() });
@@ -320,7 +427,27 @@ pub struct TensorArrayDataParts<I> {
 	pub guard: Option<Box<dyn Any>>
 }
 
+/// A type which can be used as the shape of a tensor created from raw data, i.e. via [`Tensor::from_array`].
+///
+/// This is implemented for [`Shape`], and for arrays, slices, [`Vec`]s, & [`SmallVec`]s of `usize`, `i32`, or `i64`.
+/// Shapes stored in other containers, like a `tinyvec::ArrayVec`, can be passed as a slice with `&shape[..]`, which
+/// does not allocate.
+///
+/// ```
+/// # use ort::value::Tensor;
+/// # fn main() -> ort::Result<()> {
+/// let dims: [i64; 4] = [2, 3, 0, 0];
+/// let rank = 2;
+/// let tensor = Tensor::from_array((&dims[..rank], vec![0.0_f32; 2 * 3]))?;
+/// assert_eq!(**tensor.shape(), [2, 3]);
+/// # 	Ok(())
+/// # }
+/// ```
+///
+/// [`SmallVec`]: smallvec::SmallVec
 pub trait ToShape {
+	/// Converts this value to a [`Shape`]. If `expected_size` is given, returns an error if the number of elements in
+	/// the shape does not match it.
 	fn to_shape(&self, expected_size: Option<usize>) -> Result<Shape>;
 }
 
@@ -332,7 +459,9 @@ macro_rules! impl_to_shape {
 				.enumerate()
 				.map(|(i, c)| {
 					if *c >= 1 {
-						Ok(*c as i64)
+						i64::try_from(*c).map_err(|_| {
+							Error::new_with_code(ErrorCode::InvalidArgument, format!("Invalid dimension #{}; dimension {} does not fit in an i64", i + 1, c))
+						})
 					} else {
 						Err(Error::new_with_code(
 							ErrorCode::InvalidArgument,
@@ -341,15 +470,19 @@ macro_rules! impl_to_shape {
 					}
 				})
 				.collect::<Result<Shape>>()?;
+			let Some(num_elements) = v.checked_num_elements() else {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Cannot create a tensor with shape {:?}; the number of elements overflows `usize`", v)
+				));
+			};
 			if let Some(expected_size) = expected_size {
-				if v.num_elements() != expected_size {
+				if num_elements != expected_size {
 					Err(Error::new_with_code(
 						ErrorCode::InvalidArgument,
 						format!(
 							"Cannot create a tensor from raw data; shape {:?} ({} elements) is larger than the length of the data provided ({} elements)",
-							v,
-							v.num_elements(),
-							expected_size
+							v, num_elements, expected_size
 						)
 					))
 				} else {
@@ -383,6 +516,7 @@ impl ToShape for () {
 
 impl_to_shape!(for Shape, for &[usize], for &[i32], for &[i64], for Vec<usize>, for Vec<i32>, for Vec<i64>);
 impl_to_shape!(<N> for [usize; N], for [i32; N], for [i64; N]);
+impl_to_shape!(<N> for SmallVec<usize, N>, for SmallVec<i32, N>, for SmallVec<i64, N>);
 
 #[cfg(feature = "ndarray")]
 #[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
@@ -526,6 +660,41 @@ impl<T: Clone + 'static, D: Dimension + 'static> TensorArrayDataMut<T> for &mut
 	private_impl!();
 }
 
+impl<T: Clone + 'static, const N: usize> TensorArrayData<T> for &[T; N] {
+	fn ref_parts(&self) -> Result<(Shape, &[T], Option<Box<dyn Any>>)> {
+		let shape = [N].to_shape(Some(N))?;
+		Ok((shape, *self, None))
+	}
+
+	private_impl!();
+}
+
+impl<T: Clone + 'static, const N: usize> TensorArrayData<T> for &mut [T; N] {
+	fn ref_parts(&self) -> Result<(Shape, &[T], Option<Box<dyn Any>>)> {
+		let shape = [N].to_shape(Some(N))?;
+		Ok((shape, *self, None))
+	}
+
+	private_impl!();
+}
+
+impl<T: Clone + 'static, const N: usize> TensorArrayDataMut<T> for &mut [T; N] {
+	fn ref_parts_mut(&mut self) -> Result<(Shape, &mut [T], Option<Box<dyn Any>>)> {
+		let shape = [N].to_shape(Some(N))?;
+		Ok((shape, *self, None))
+	}
+
+	private_impl!();
+}
+
+impl<T: Clone + 'static, const N: usize> OwnedTensorArrayData<T> for [T; N] {
+	fn into_parts(self) -> Result<TensorArrayDataParts<T>> {
+		([N], Box::<[T]>::from(self)).into_parts()
+	}
+
+	private_impl!();
+}
+
 impl<T: Clone + 'static, D: ToShape> TensorArrayData<T> for (D, &[T]) {
 	fn ref_parts(&self) -> Result<(Shape, &[T], Option<Box<dyn Any>>)> {
 		let shape = self.0.to_shape(Some(self.1.len()))?;
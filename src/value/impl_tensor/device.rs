@@ -0,0 +1,100 @@
+use core::{ffi::c_void, fmt::Debug};
+
+use super::{
+	DynTensor, Tensor,
+	create::{ToShape, tensor_from_array}
+};
+use crate::{
+	error::{Error, ErrorCode, Result},
+	memory::{AllocationDevice, AllocatorType, MemoryInfo, MemoryType},
+	tensor::{PrimitiveTensorElementType, TensorElementType}
+};
+
+/// Creates tensors which view memory already allocated on a device, like a CUDA buffer from
+/// [`cudarc`](https://docs.rs/cudarc), without copying it.
+///
+/// The builder holds the [`MemoryInfo`] describing the device, so it can be created once and reused to wrap every
+/// input & output buffer on that device. The resulting tensors can be passed directly to [`Session::run`], or bound
+/// with [`IoBinding::bind_input`] & [`IoBinding::bind_output`] to have ONNX Runtime write outputs into device memory
+/// you allocated yourself.
+///
+/// ```ignore
+/// let device = CudaDevice::new(0)?;
+/// let input = device.htod_sync_copy(&input_data)?;
+/// let mut output = device.alloc_zeros::<f32>(512 * 512)?;
+///
+/// let builder = DeviceTensorBuilder::cuda(0)?;
+/// let input_tensor = unsafe { builder.build::<f32>((*input.device_ptr() as usize as *mut ()).cast(), [1, 3, 512, 512])? };
+/// let output_tensor = unsafe { builder.build::<f32>((*output.device_ptr_mut() as usize as *mut ()).cast(), [1, 1, 512, 512])? };
+///
+/// let mut binding = session.create_binding()?;
+/// binding.bind_input("input", &input_tensor)?;
+/// binding.bind_output("output", output_tensor)?;
+/// session.run_binding(&binding)?;
+/// ```
+///
+/// [`Session::run`]: crate::session::Session::run
+/// [`IoBinding::bind_input`]: crate::io_binding::IoBinding::bind_input
+/// [`IoBinding::bind_output`]: crate::io_binding::IoBinding::bind_output
+#[derive(Debug, Clone)]
+pub struct DeviceTensorBuilder {
+	memory_info: MemoryInfo
+}
+
+impl DeviceTensorBuilder {
+	/// Creates a builder for tensors in the default memory of the device `device_id` of `device`.
+	pub fn new(device: AllocationDevice, device_id: i32) -> Result<Self> {
+		Ok(Self::with_memory_info(MemoryInfo::new(device, device_id, AllocatorType::Device, MemoryType::Default)?))
+	}
+
+	/// Creates a builder for tensors in the memory of the CUDA device `device_id`.
+	pub fn cuda(device_id: i32) -> Result<Self> {
+		Self::new(AllocationDevice::CUDA, device_id)
+	}
+
+	/// Creates a builder for tensors in the memory described by `memory_info`.
+	pub fn with_memory_info(memory_info: MemoryInfo) -> Self {
+		Self { memory_info }
+	}
+
+	/// Returns the [`MemoryInfo`] of the tensors created by this builder.
+	pub fn memory_info(&self) -> &MemoryInfo {
+		&self.memory_info
+	}
+
+	/// Creates a [`Tensor`] of type `T` with the given `shape`, viewing the device memory at `ptr`.
+	///
+	/// # Safety
+	/// - `ptr` must point to at least `shape.num_elements() * size_of::<T>()` bytes of memory on the device described
+	///   by this builder's [`MemoryInfo`].
+	/// - The memory must remain valid, and must not be freed, for as long as the returned tensor (or any
+	///   [`IoBinding`](crate::io_binding::IoBinding) it is bound to) is alive.
+	/// - If `T` is `bool`, every byte of the data must be `0` or `1`.
+	pub unsafe fn build<T: PrimitiveTensorElementType + Debug>(&self, ptr: *mut c_void, shape: impl ToShape) -> Result<Tensor<T>> {
+		let tensor = unsafe { self.build_dyn(ptr, T::into_tensor_element_type(), shape)? };
+		Ok(unsafe { tensor.transmute_type() })
+	}
+
+	/// Creates a [`DynTensor`] with the given element type & `shape`, viewing the device memory at `ptr`. This is
+	/// useful when the element type is only known at runtime.
+	///
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if `element_type` is [`TensorElementType::String`], since
+	/// string tensors cannot reside in device memory.
+	///
+	/// # Safety
+	/// - `ptr` must point to enough memory to hold a tensor of the given element type & shape on the device described
+	///   by this builder's [`MemoryInfo`].
+	/// - The memory must remain valid, and must not be freed, for as long as the returned tensor (or any
+	///   [`IoBinding`](crate::io_binding::IoBinding) it is bound to) is alive.
+	/// - If `element_type` is [`TensorElementType::Bool`], every byte of the data must be `0` or `1`.
+	pub unsafe fn build_dyn(&self, ptr: *mut c_void, element_type: TensorElementType, shape: impl ToShape) -> Result<DynTensor> {
+		if element_type == TensorElementType::String {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "String tensors cannot be created from device memory"));
+		}
+		if ptr.is_null() {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Cannot create a tensor from a null device pointer"));
+		}
+		let shape = shape.to_shape(None)?;
+		tensor_from_array(self.memory_info.clone(), shape, ptr, element_type.byte_size(1), element_type, None)
+	}
+}
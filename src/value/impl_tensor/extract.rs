@@ -4,7 +4,9 @@ use alloc::{
 	vec,
 	vec::Vec
 };
-use core::{ffi::c_void, fmt::Debug, ptr, slice};
+use core::{ffi::c_void, fmt::Debug, iter::FusedIterator, ops::Deref, ptr, slice};
+
+use smallvec::SmallVec;
 
 use super::{Tensor, TensorValueTypeMarker};
 use crate::{
@@ -19,10 +21,13 @@ use crate::{
 impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	/// Attempt to extract the underlying data of type `T` into a read-only [`ndarray::ArrayView`].
 	///
+	/// The view borrows the tensor's buffer directly, so no data is copied; its lifetime is tied to `&self`. Use
+	/// [`ArrayView::to_owned`](ndarray::ArrayBase::to_owned) if an owned copy of the data is needed.
+	///
 	/// See also:
 	/// - the mutable counterpart of this function, [`Tensor::try_extract_array_mut`].
 	/// - the infallible counterpart, [`Tensor::extract_array`], for typed [`Tensor<T>`]s.
-	/// - the alternative function for strings, [`Tensor::try_extract_string_tensor`].
+	/// - the alternative function for strings, [`Tensor::try_extract_strings`].
 	///
 	/// ```
 	/// # use std::sync::Arc;
@@ -47,11 +52,56 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	/// [`DynValue`]: crate::value::DynValue
 	#[cfg(feature = "ndarray")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	#[doc(alias = "try_extract_array_view")]
 	pub fn try_extract_array<T: PrimitiveTensorElementType>(&self) -> Result<ndarray::ArrayViewD<'_, T>> {
 		extract_tensor(self.ptr().cast_mut(), self.dtype(), self.memory_info(), T::into_tensor_element_type())
 			.and_then(|(ptr, shape)| Ok(unsafe { ndarray::ArrayView::from_shape_ptr(shape.to_ixdyn(), data_ptr(ptr)?.cast::<T>()) }))
 	}
 
+	/// Attempt to extract the underlying data of type `T` into a read-only [`ndarray::ArrayView`] with static
+	/// dimensionality `D`, e.g. [`ndarray::Ix4`](type@ndarray::Ix4) for an [`ndarray::ArrayView4`].
+	///
+	/// This behaves like [`Tensor::try_extract_array`] followed by
+	/// [`into_dimensionality`](ndarray::ArrayBase::into_dimensionality), allowing the view to be indexed with
+	/// fixed-size indices like `[n, c, h, w]` without a runtime rank check on every access.
+	///
+	/// ```
+	/// # use ort::value::TensorRef;
+	/// # fn main() -> ort::Result<()> {
+	/// let array = ndarray::Array4::<f32>::ones((1, 16, 16, 3));
+	/// let value = TensorRef::from_array_view(array.view())?.into_dyn();
+	///
+	/// let extracted = value.try_extract_array_d::<f32, ndarray::Ix4>()?;
+	/// assert_eq!(extracted[[0, 15, 15, 2]], 1.0);
+	///
+	/// assert!(value.try_extract_array_d::<f32, ndarray::Ix3>().is_err());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// May return an error if:
+	/// - The tensor's rank does not match `D`.
+	/// - This is a [`DynValue`], and the value is not actually a tensor.
+	/// - The provided type `T` does not match the tensor's element type.
+	/// - The tensor's data is not allocated in CPU memory.
+	///
+	/// [`DynValue`]: crate::value::DynValue
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	#[doc(alias = "view_as")]
+	pub fn try_extract_array_d<T: PrimitiveTensorElementType, D: ndarray::Dimension>(&self) -> Result<ndarray::ArrayView<'_, T, D>> {
+		let array = self.try_extract_array::<T>()?;
+		let rank = array.ndim();
+		// `into_dimensionality` can only fail if `D` has a static rank.
+		array.into_dimensionality::<D>().map_err(|_| {
+			Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot extract a tensor of rank {rank} into an array of rank {}", D::NDIM.unwrap_or(rank))
+			)
+		})
+	}
+
 	/// Attempt to extract the scalar from a tensor of type `T`.
 	///
 	/// ```
@@ -151,7 +201,7 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	/// [`DynValue`]: crate::value::DynValue
 	pub fn try_extract_tensor<T: PrimitiveTensorElementType>(&self) -> Result<(&Shape, &[T])> {
 		extract_tensor(self.ptr().cast_mut(), self.dtype(), self.memory_info(), T::into_tensor_element_type())
-			.and_then(|(ptr, shape)| Ok((shape, unsafe { slice::from_raw_parts(data_ptr(ptr)?.cast::<T>(), shape.num_elements()) })))
+			.and_then(|(ptr, shape)| Ok((shape, unsafe { slice::from_raw_parts(slice_data_ptr(ptr, shape.num_elements())?, shape.num_elements()) })))
 	}
 
 	/// Attempt to extract the underlying data into a view tuple, consisting of the tensor's shape and a
@@ -181,7 +231,7 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	/// [`DynValue`]: crate::value::DynValue
 	pub fn try_extract_tensor_mut<T: PrimitiveTensorElementType>(&mut self) -> Result<(&Shape, &mut [T])> {
 		extract_tensor(self.ptr_mut(), self.dtype(), self.memory_info(), T::into_tensor_element_type())
-			.and_then(|(ptr, shape)| Ok((shape, unsafe { slice::from_raw_parts_mut(data_ptr(ptr)?.cast::<T>(), shape.num_elements()) })))
+			.and_then(|(ptr, shape)| Ok((shape, unsafe { slice::from_raw_parts_mut(slice_data_ptr(ptr, shape.num_elements())?, shape.num_elements()) })))
 	}
 
 	/// Attempt to extract the underlying data into a Rust `ndarray`.
@@ -201,7 +251,7 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
 	pub fn try_extract_string_array(&self) -> Result<ndarray::ArrayD<String>> {
 		extract_tensor(self.ptr().cast_mut(), self.dtype(), self.memory_info(), TensorElementType::String).and_then(|(ptr, shape)| {
-			let strings = extract_strings(ptr, shape)?;
+			let strings = extract_strings(ptr, shape, false)?;
 			Ok(ndarray::Array::from_shape_vec(shape.to_ixdyn(), strings).expect("Shape extracted from tensor didn't match tensor contents"))
 		})
 	}
@@ -223,7 +273,28 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	/// ```
 	pub fn try_extract_strings(&self) -> Result<(&Shape, Vec<String>)> {
 		extract_tensor(self.ptr().cast_mut(), self.dtype(), self.memory_info(), TensorElementType::String).and_then(|(ptr, shape)| {
-			let strings = extract_strings(ptr, shape)?;
+			let strings = extract_strings(ptr, shape, false)?;
+			Ok((shape, strings))
+		})
+	}
+
+	/// Like [`Tensor::try_extract_strings`], but replaces any invalid UTF-8 sequences with
+	/// [`U+FFFD REPLACEMENT CHARACTER`](core::char::REPLACEMENT_CHARACTER) instead of returning an error.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_string_array(([2usize], &["hello", "world"][..]))?.into_dyn();
+	///
+	/// let (extracted_shape, extracted_data) = tensor.try_extract_strings_lossy()?;
+	/// assert_eq!(extracted_data, ["hello", "world"]);
+	/// assert_eq!(**extracted_shape, [2]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn try_extract_strings_lossy(&self) -> Result<(&Shape, Vec<String>)> {
+		extract_tensor(self.ptr().cast_mut(), self.dtype(), self.memory_info(), TensorElementType::String).and_then(|(ptr, shape)| {
+			let strings = extract_strings(ptr, shape, true)?;
 			Ok((shape, strings))
 		})
 	}
@@ -279,7 +350,16 @@ unsafe fn data_ptr(ptr: *mut ort_sys::OrtValue) -> Result<*mut c_void> {
 	Ok(output_array_ptr)
 }
 
-fn extract_strings(ptr: *mut ort_sys::OrtValue, shape: &Shape) -> Result<Vec<String>> {
+/// Like [`data_ptr`], but for creating a slice of `len` elements. ONNX Runtime does not allocate any memory for empty
+/// tensors, so their data pointer is null; a dangling pointer is returned instead so that the slice is valid.
+unsafe fn slice_data_ptr<T>(ptr: *mut ort_sys::OrtValue, len: usize) -> Result<*mut T> {
+	if len == 0 {
+		return Ok(ptr::NonNull::dangling().as_ptr());
+	}
+	Ok(unsafe { data_ptr(ptr) }?.cast())
+}
+
+fn extract_strings(ptr: *mut ort_sys::OrtValue, shape: &Shape, lossy: bool) -> Result<Vec<String>> {
 	let len = shape.num_elements();
 	// Total length of string data, not including \0 suffix
 	let mut total_length = 0;
@@ -306,7 +386,7 @@ fn extract_strings(ptr: *mut ort_sys::OrtValue, shape: &Shape) -> Result<Vec<Str
 		.windows(2)
 		.map(|w| {
 			let slice = &string_contents[w[0]..w[1]];
-			String::from_utf8(slice.into())
+			if lossy { Ok(String::from_utf8_lossy(slice).into_owned()) } else { String::from_utf8(slice.into()) }
 		})
 		.collect::<Result<Vec<String>, FromUtf8Error>>()
 		.map_err(Error::wrap)?;
@@ -314,7 +394,8 @@ fn extract_strings(ptr: *mut ort_sys::OrtValue, shape: &Shape) -> Result<Vec<Str
 }
 
 impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
-	/// Extracts the underlying data into a read-only [`ndarray::ArrayView`].
+	/// Extracts the underlying data into a read-only [`ndarray::ArrayView`] borrowing the tensor's buffer, without
+	/// copying.
 	///
 	/// ```
 	/// # use std::sync::Arc;
@@ -330,6 +411,7 @@ impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 	/// ```
 	#[cfg(feature = "ndarray")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	#[doc(alias = "as_ndarray_view")]
 	pub fn extract_array(&self) -> ndarray::ArrayViewD<'_, T> {
 		self.try_extract_array().expect("Failed to extract tensor")
 	}
@@ -395,4 +477,150 @@ impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 	pub fn extract_tensor_mut(&mut self) -> (&Shape, &mut [T]) {
 		self.try_extract_tensor_mut().expect("Failed to extract tensor")
 	}
+
+	/// Returns an iterator over the tensor's elements in row-major order, i.e. the order of
+	/// [`Tensor::extract_tensor`]'s data.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2usize, 2], vec![1_i64, 2, 3, 4]))?;
+	/// assert_eq!(tensor.try_iter()?.sum::<i64>(), 10);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the tensor's data is not accessible from the CPU.
+	pub fn try_iter(&self) -> Result<slice::Iter<'_, T>> {
+		Ok(self.try_extract_tensor()?.1.iter())
+	}
+
+	/// Returns an iterator over the tensor's elements in row-major order. This is the infallible counterpart to
+	/// [`Tensor::try_iter`].
+	///
+	/// # Panics
+	/// Panics if the tensor's data is not accessible from the CPU.
+	pub fn iter(&self) -> slice::Iter<'_, T> {
+		self.try_iter().expect("Failed to extract tensor")
+	}
+
+	/// Returns an iterator over mutable references to the tensor's elements in row-major order.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let mut tensor = Tensor::from_array(([3usize], vec![1.0_f32, 2.0, 3.0]))?;
+	/// tensor.try_iter_mut()?.for_each(|x| *x *= 2.0);
+	/// assert_eq!(tensor.extract_tensor().1, &[2.0, 4.0, 6.0]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the tensor's data is not accessible from the CPU.
+	pub fn try_iter_mut(&mut self) -> Result<slice::IterMut<'_, T>> {
+		Ok(self.try_extract_tensor_mut()?.1.iter_mut())
+	}
+
+	/// Returns an iterator over mutable references to the tensor's elements in row-major order. This is the infallible
+	/// counterpart to [`Tensor::try_iter_mut`].
+	///
+	/// # Panics
+	/// Panics if the tensor's data is not accessible from the CPU.
+	pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+		self.try_iter_mut().expect("Failed to extract tensor")
+	}
+
+	/// Returns an iterator over the tensor's elements in row-major order, along with the multi-dimensional index of
+	/// each element.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2usize, 2], vec![1_i64, 2, 3, 4]))?;
+	/// let (index, _) = tensor.try_indexed_iter()?.find(|(_, &x)| x == 3).unwrap();
+	/// assert_eq!(index, [1, 0]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the tensor's data is not accessible from the CPU.
+	pub fn try_indexed_iter(&self) -> Result<IndexedIter<'_, T>> {
+		let (shape, data) = self.try_extract_tensor()?;
+		Ok(IndexedIter {
+			dims: shape.iter().map(|&d| d as usize).collect(),
+			index: TensorIndex(SmallVec::from_elem(0, shape.len())),
+			data: data.iter()
+		})
+	}
+
+	/// Returns an iterator over the tensor's elements in row-major order, along with the multi-dimensional index of
+	/// each element. This is the infallible counterpart to [`Tensor::try_indexed_iter`].
+	///
+	/// # Panics
+	/// Panics if the tensor's data is not accessible from the CPU.
+	pub fn indexed_iter(&self) -> IndexedIter<'_, T> {
+		self.try_indexed_iter().expect("Failed to extract tensor")
+	}
+}
+
+/// The multi-dimensional index of an element of a tensor, yielded by [`IndexedIter`]. Dereferences to `[usize]`.
+///
+/// Indices of tensors with up to 4 dimensions are stored inline, so iterating over them does not allocate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TensorIndex(SmallVec<usize, 4>);
+
+impl Deref for TensorIndex {
+	type Target = [usize];
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
 }
+
+impl PartialEq<[usize]> for TensorIndex {
+	fn eq(&self, other: &[usize]) -> bool {
+		*self.0 == *other
+	}
+}
+
+impl<const N: usize> PartialEq<[usize; N]> for TensorIndex {
+	fn eq(&self, other: &[usize; N]) -> bool {
+		*self.0 == *other
+	}
+}
+
+/// An iterator over the elements of a [`Tensor`] and their multi-dimensional indices, returned by
+/// [`Tensor::indexed_iter`].
+#[derive(Debug, Clone)]
+pub struct IndexedIter<'t, T> {
+	dims: SmallVec<usize, 4>,
+	index: TensorIndex,
+	data: slice::Iter<'t, T>
+}
+
+impl<'t, T> Iterator for IndexedIter<'t, T> {
+	type Item = (TensorIndex, &'t T);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let value = self.data.next()?;
+		let index = self.index.clone();
+		// advance to the next index, carrying over from the last dimension
+		for (i, &dim) in self.index.0.iter_mut().zip(&self.dims).rev() {
+			*i += 1;
+			if *i < dim {
+				break;
+			}
+			*i = 0;
+		}
+		Some((index, value))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.data.size_hint()
+	}
+}
+
+impl<T> ExactSizeIterator for IndexedIter<'_, T> {}
+impl<T> FusedIterator for IndexedIter<'_, T> {}
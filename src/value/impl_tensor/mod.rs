@@ -1,5 +1,8 @@
 mod create;
+mod device;
 mod extract;
+mod ops;
+mod serialize;
 
 use alloc::sync::Arc;
 use core::{
@@ -9,7 +12,11 @@ use core::{
 	ptr::{self, NonNull}
 };
 
-pub use self::create::{OwnedTensorArrayData, TensorArrayData, TensorArrayDataMut, TensorArrayDataParts, ToShape};
+pub use self::{
+	create::{OwnedTensorArrayData, TensorArrayData, TensorArrayDataMut, TensorArrayDataParts, ToShape},
+	device::DeviceTensorBuilder,
+	extract::{IndexedIter, TensorIndex}
+};
 use super::{DowncastableTarget, DynValue, Value, ValueInner, ValueRef, ValueRefMut, ValueType, ValueTypeMarker};
 use crate::{
 	AsPointer,
@@ -149,6 +156,9 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	/// Use [`Tensor::memory_info`] & [`MemoryInfo::allocation_device`] to check which device the data resides on before
 	/// accessing it.
 	///
+	/// The pointer is only valid for as long as this tensor (or another [`Value`] sharing its data, e.g. via
+	/// [`Value::clone_of`]) is alive.
+	///
 	/// ```
 	/// # use ort::value::Tensor;
 	/// # fn main() -> ort::Result<()> {
@@ -177,6 +187,9 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	/// Use [`Tensor::memory_info`] & [`MemoryInfo::allocation_device`] to check which device the data resides on before
 	/// accessing it.
 	///
+	/// The pointer is only valid for as long as this tensor (or another [`Value`] sharing its data, e.g. via
+	/// [`Value::clone_of`]) is alive.
+	///
 	/// ```
 	/// # use ort::value::Tensor;
 	/// # fn main() -> ort::Result<()> {
@@ -336,12 +349,14 @@ mod tests {
 
 	#[cfg(feature = "ndarray")]
 	use ndarray::{ArcArray1, Array1, CowArray};
+	use smallvec::{SmallVec, smallvec};
 
-	use super::Tensor;
+	use super::{Tensor, ToShape};
 	use crate::{
+		ErrorCode,
 		memory::Allocator,
 		tensor::{Shape, SymbolicDimensions, TensorElementType},
-		value::{TensorRef, ValueType}
+		value::{DynTensor, TensorRef, TensorRefMut, ValueType}
 	};
 
 	#[test]
@@ -456,4 +471,152 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_tensor_reinterpret() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2usize, 2], vec![1_i32, -1, 0, i32::MIN]))?;
+		let view = tensor.reinterpret::<u32>()?;
+		let (shape, data) = view.extract_tensor();
+		assert_eq!(**shape, [2, 2]);
+		assert_eq!(data, &[1, u32::MAX, 0, 0x8000_0000]);
+		assert_eq!(view.dtype().tensor_type(), Some(TensorElementType::Uint32));
+
+		let tensor = Tensor::from_array(([1usize], vec![1_u8]))?;
+		assert!(tensor.reinterpret::<i16>().is_err());
+		assert!(tensor.reinterpret::<bool>().is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "ndarray")]
+	fn test_tensor_extract_array_is_zero_copy() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2usize, 3], vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]))?;
+		let view = tensor.try_extract_array::<f32>()?;
+		assert_eq!(view.shape(), [2, 3]);
+		assert_eq!(view.as_ptr(), tensor.data_ptr()?.cast::<f32>());
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_bool_roundtrip() -> crate::Result<()> {
+		let mask = vec![true, false, false, true, true, false];
+		let tensor = Tensor::from_array(([2usize, 3], mask.clone()))?;
+		assert_eq!(tensor.dtype().tensor_type(), Some(TensorElementType::Bool));
+		assert_eq!(tensor.extract_tensor().1, &mask[..]);
+
+		let bytes = unsafe { core::slice::from_raw_parts(tensor.data_ptr()?.cast::<u8>(), mask.len()) };
+		assert_eq!(bytes, [1, 0, 0, 1, 1, 0]);
+
+		let tensor = tensor.upcast();
+		assert_eq!(tensor.try_extract_tensor::<bool>()?.1, &mask[..]);
+		#[cfg(feature = "ndarray")]
+		assert_eq!(tensor.try_extract_array::<bool>()?.iter().copied().collect::<Vec<_>>(), mask);
+
+		// any byte other than 0 or 1 would be an invalid `bool`, so raw bool data containing one is rejected
+		let mut bytes = tensor.to_bytes()?;
+		*bytes.last_mut().expect("tensor has data") = 2;
+		assert_eq!(DynTensor::from_bytes(&bytes).expect_err("2 is not a valid bool").code(), ErrorCode::InvalidArgument);
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "num-complex")]
+	fn test_tensor_complex() -> crate::Result<()> {
+		use num_complex::{Complex32, Complex64};
+
+		let data = vec![Complex32::new(1.0, -1.0), Complex32::new(0.5, 2.0)];
+		let tensor = Tensor::from_array(([2usize], data.clone()))?;
+		assert_eq!(tensor.dtype().tensor_type(), Some(TensorElementType::Complex64));
+		assert_eq!(tensor.extract_tensor().1, &data[..]);
+
+		let tensor = Tensor::from_array(([1usize], vec![Complex64::new(3.0, 4.0)]))?.into_dyn();
+		assert_eq!(tensor.try_extract_tensor::<Complex64>()?.1, &[Complex64::new(3.0, 4.0)]);
+		assert!(tensor.try_extract_tensor::<Complex32>().is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_fixed_size_array() -> crate::Result<()> {
+		let tensor = Tensor::from_array([1.0_f32, 2.0, 3.0, 4.0])?;
+		assert_eq!(**tensor.shape(), [4]);
+		assert_eq!(tensor.extract_tensor().1, &[1.0, 2.0, 3.0, 4.0]);
+
+		let mut data = [1_i64, 2, 3];
+		let view = TensorRef::from_array_view(&data)?;
+		assert_eq!(**view.shape(), [3]);
+		assert_eq!(view.data_ptr()?.cast::<i64>(), data.as_ptr());
+		drop(view);
+
+		let mut view = TensorRefMut::from_array_view_mut(&mut data)?;
+		view.extract_tensor_mut().1[0] = 4;
+		drop(view);
+		assert_eq!(data, [4, 2, 3]);
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_squeeze_unsqueeze() -> crate::Result<()> {
+		let mut tensor = Tensor::from_array(([2usize, 3], vec![1_i64, 2, 3, 4, 5, 6]))?;
+		let data_ptr = tensor.data_ptr()?;
+		tensor.unsqueeze(2)?;
+		assert_eq!(**tensor.shape(), [2, 3, 1]);
+		assert_eq!(tensor.data_ptr()?, data_ptr);
+		assert_eq!(tensor.unsqueeze(4).expect_err("axis should be out of bounds").code(), ErrorCode::InvalidArgument);
+
+		assert_eq!(tensor.squeeze(Some(0)).expect_err("axis 0 has size 2").code(), ErrorCode::InvalidArgument);
+		assert_eq!(tensor.squeeze(Some(3)).expect_err("axis should be out of bounds").code(), ErrorCode::InvalidArgument);
+		tensor.squeeze(None)?;
+		assert_eq!(**tensor.shape(), [2, 3]);
+		assert_eq!(tensor.extract_tensor().1, &[1, 2, 3, 4, 5, 6]);
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_iter() -> crate::Result<()> {
+		let mut tensor = Tensor::from_array(([2usize, 3], vec![1_i64, 2, 3, 4, 5, 6]))?;
+		assert_eq!(tensor.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6]);
+
+		tensor.iter_mut().for_each(|x| *x *= 10);
+		let indexed = tensor.indexed_iter().map(|(index, &x)| (index, x)).collect::<Vec<_>>();
+		assert_eq!(indexed.len(), 6);
+		assert_eq!(indexed[0].0, [0, 0]);
+		assert_eq!(indexed[2].0, [0, 2]);
+		assert_eq!(indexed[2].1, 30);
+		assert_eq!(indexed[3].0, [1, 0]);
+		assert_eq!(indexed[5].0, [1, 2]);
+		assert_eq!(indexed[5].1, 60);
+
+		let scalar = Tensor::from_array(([0usize; 0], vec![7_i64]))?;
+		let indexed = scalar.try_indexed_iter()?.collect::<Vec<_>>();
+		assert_eq!(indexed.len(), 1);
+		assert!(indexed[0].0.is_empty());
+		assert_eq!(*indexed[0].1, 7);
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_shape_overflow() {
+		for shape in [vec![i64::MAX, 3], vec![1 << 32, 1 << 32, 1 << 32]] {
+			let err = shape.to_shape(None).expect_err("shape should overflow");
+			assert_eq!(err.code(), ErrorCode::InvalidArgument);
+		}
+		let err = [usize::MAX].to_shape(None).expect_err("dimension should not fit in an i64");
+		assert_eq!(err.code(), ErrorCode::InvalidArgument);
+
+		let err = Tensor::from_array(([i64::MAX, 3], vec![0.0_f32; 2])).expect_err("shape should overflow");
+		assert_eq!(err.code(), ErrorCode::InvalidArgument);
+		let err = TensorRef::from_array_view(([usize::MAX / 2, 2, 2], &[0_u8; 4][..])).expect_err("shape should overflow");
+		assert_eq!(err.code(), ErrorCode::InvalidArgument);
+	}
+
+	#[test]
+	fn test_smallvec_shape() -> crate::Result<()> {
+		let dims: SmallVec<i64, 4> = smallvec![2, 3];
+		assert_eq!(*dims.to_shape(Some(6))?, [2, 3]);
+		let dims: SmallVec<usize, 4> = smallvec![2, 3];
+		assert_eq!(*dims.to_shape(None)?, [2, 3]);
+		assert_eq!(dims.to_shape(Some(5)).expect_err("size should mismatch").code(), ErrorCode::InvalidArgument);
+		Ok(())
+	}
 }
@@ -0,0 +1,1117 @@
+use alloc::{boxed::Box, format, sync::Arc, vec, vec::Vec};
+use core::{any::Any, cmp::Ordering, fmt::Debug};
+
+use super::{Tensor, create::tensor_from_array};
+use crate::{
+	error::{Error, ErrorCode, Result},
+	tensor::{CastElement, PrimitiveTensorElementType, Shape}
+};
+
+/// Resolves a possibly negative `axis` into an index into a shape of the given `rank`.
+pub(crate) fn normalize_axis(axis: isize, rank: usize) -> Result<usize> {
+	let resolved = if axis < 0 { axis + rank as isize } else { axis };
+	if resolved < 0 || resolved as usize >= rank {
+		return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Axis {axis} is out of bounds for a tensor of rank {rank}")));
+	}
+	Ok(resolved as usize)
+}
+
+impl<T: PrimitiveTensorElementType + Clone + Debug + 'static> Tensor<T> {
+	/// Concatenates CPU tensors along the given `axis` into a new tensor. All tensors must have the same rank, and
+	/// their shapes must match in every dimension except `axis`.
+	///
+	/// `axis` may be negative, in which case it counts from the last dimension.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let a = Tensor::from_array(([1usize, 2], vec![1_i64, 2]))?;
+	/// let b = Tensor::from_array(([2usize, 2], vec![3_i64, 4, 5, 6]))?;
+	///
+	/// let c = Tensor::concat(&[&a, &b], 0)?;
+	/// let (shape, data) = c.extract_tensor();
+	/// assert_eq!(**shape, [3, 2]);
+	/// assert_eq!(data, &[1, 2, 3, 4, 5, 6]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if `tensors` is empty, if any tensor is not CPU-accessible, if `axis` is out of bounds, or if
+	/// the tensors' shapes are incompatible.
+	pub fn concat(tensors: &[&Tensor<T>], axis: isize) -> Result<Tensor<T>> {
+		let Some(first) = tensors.first() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Cannot concatenate an empty list of tensors"));
+		};
+
+		let first_shape = first.shape();
+		let axis = normalize_axis(axis, first_shape.len())?;
+
+		let mut parts = Vec::with_capacity(tensors.len());
+		let mut axis_len = 0;
+		for tensor in tensors {
+			let (shape, data) = tensor.try_extract_tensor::<T>()?;
+			if shape.len() != first_shape.len() || shape.iter().zip(first_shape.iter()).enumerate().any(|(i, (a, b))| i != axis && a != b) {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Cannot concatenate tensors of shape {first_shape} and {shape} along axis {axis}")
+				));
+			}
+			axis_len += shape[axis];
+			parts.push((shape[axis..].iter().product::<i64>() as usize, data));
+		}
+
+		let outer = first_shape[..axis].iter().product::<i64>() as usize;
+		let mut data = Vec::with_capacity(parts.iter().map(|(_, d)| d.len()).sum());
+		for o in 0..outer {
+			for (chunk, part) in &parts {
+				data.extend_from_slice(&part[o * chunk..(o + 1) * chunk]);
+			}
+		}
+
+		let mut shape: Shape = first_shape.clone();
+		shape[axis] = axis_len;
+		Tensor::from_array((shape, data))
+	}
+
+	/// Pads a CPU tensor with a constant `value`. `pads` gives the number of elements to add `(before, after)` the
+	/// existing data along each axis.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2usize, 2], vec![1_i64, 2, 3, 4]))?;
+	///
+	/// let padded = tensor.pad(&[(1, 0), (0, 1)], 0)?;
+	/// let (shape, data) = padded.extract_tensor();
+	/// assert_eq!(**shape, [3, 3]);
+	/// assert_eq!(data, &[0, 0, 0, 1, 2, 0, 3, 4, 0]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the tensor is not CPU-accessible, or if the length of `pads` does not match the tensor's
+	/// rank.
+	pub fn pad(&self, pads: &[(usize, usize)], value: T) -> Result<Tensor<T>> {
+		let (shape, data) = self.try_extract_tensor::<T>()?;
+		let rank = shape.len();
+		if pads.len() != rank {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Expected padding for {rank} axes to pad tensor of shape {shape}, got {}", pads.len())
+			));
+		}
+
+		let out_shape: Shape = shape
+			.iter()
+			.zip(pads)
+			.map(|(dim, (before, after))| dim + (before + after) as i64)
+			.collect();
+		let mut out_strides = vec![1; rank];
+		for axis in (0..rank.saturating_sub(1)).rev() {
+			out_strides[axis] = out_strides[axis + 1] * out_shape[axis + 1] as usize;
+		}
+
+		let mut out = vec![value; out_shape.num_elements()];
+		if !data.is_empty() {
+			// Copy each contiguous row along the last axis into the interior of the padded buffer.
+			let row_len = shape.last().map_or(1, |d| *d as usize);
+			let mut index = vec![0; rank.saturating_sub(1)];
+			for row in data.chunks(row_len) {
+				let offset = (0..rank)
+					.map(|axis| (index.get(axis).copied().unwrap_or(0) + pads[axis].0) * out_strides[axis])
+					.sum::<usize>();
+				out[offset..offset + row_len].clone_from_slice(row);
+
+				for axis in (0..index.len()).rev() {
+					index[axis] += 1;
+					if index[axis] < shape[axis] as usize {
+						break;
+					}
+					index[axis] = 0;
+				}
+			}
+		}
+
+		Tensor::from_array((out_shape, out))
+	}
+
+	/// Permutes the axes of a CPU tensor, returning a new contiguous tensor whose `i`th axis is this tensor's
+	/// `axes[i]`th axis.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// // NHWC -> NCHW
+	/// let image = Tensor::from_array(([1usize, 2, 2, 3], (0..12).collect::<Vec<i32>>()))?;
+	/// let image = image.permute(&[0, 3, 1, 2])?;
+	/// let (shape, data) = image.extract_tensor();
+	/// assert_eq!(**shape, [1, 3, 2, 2]);
+	/// assert_eq!(data, &[0, 3, 6, 9, 1, 4, 7, 10, 2, 5, 8, 11]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the tensor is not CPU-accessible, or if `axes` is not a permutation of `0..rank`.
+	pub fn permute(&self, axes: &[usize]) -> Result<Tensor<T>> {
+		let (shape, data) = self.try_extract_tensor::<T>()?;
+		let rank = shape.len();
+		let mut seen = vec![false; rank];
+		if axes.len() != rank || !axes.iter().all(|&axis| axis < rank && !core::mem::replace(&mut seen[axis], true)) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Axes {axes:?} are not a valid permutation for a tensor of shape {shape}")));
+		}
+
+		let mut in_strides = vec![1; rank];
+		for axis in (0..rank.saturating_sub(1)).rev() {
+			in_strides[axis] = in_strides[axis + 1] * shape[axis + 1] as usize;
+		}
+		let out_shape: Shape = axes.iter().map(|&axis| shape[axis]).collect();
+		let strides: Vec<usize> = axes.iter().map(|&axis| in_strides[axis]).collect();
+
+		let mut out = Vec::with_capacity(data.len());
+		let mut index = vec![0; rank];
+		let mut offset = 0;
+		for _ in 0..data.len() {
+			out.push(data[offset].clone());
+
+			for axis in (0..rank).rev() {
+				index[axis] += 1;
+				offset += strides[axis];
+				if index[axis] < out_shape[axis] as usize {
+					break;
+				}
+				offset -= index[axis] * strides[axis];
+				index[axis] = 0;
+			}
+		}
+
+		Tensor::from_array((out_shape, out))
+	}
+
+	/// Converts each element of a CPU tensor to the element type `U`, returning a new tensor of the same shape.
+	///
+	/// Conversion follows the semantics of Rust's `as` operator; see [`CastElement`] for details. Notably, narrowing
+	/// integer casts truncate, and float-to-integer casts saturate.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let classes = Tensor::from_array(([3usize], vec![0_i64, 2, 1]))?;
+	/// let classes = classes.cast::<i32>()?;
+	/// assert_eq!(classes.extract_tensor().1, &[0, 2, 1]);
+	///
+	/// let logits = Tensor::from_array(([3usize], vec![-1.5_f32, 300.0, f32::NAN]))?;
+	/// assert_eq!(logits.cast::<u8>()?.extract_tensor().1, &[0, 255, 0]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the tensor is not CPU-accessible.
+	pub fn cast<U>(&self) -> Result<Tensor<U>>
+	where
+		T: Copy,
+		U: CastElement<T> + Debug + Clone + 'static
+	{
+		let (shape, data) = self.try_extract_tensor::<T>()?;
+		Tensor::from_array((shape.clone(), data.iter().map(|&x| U::cast_from(x)).collect::<Vec<_>>()))
+	}
+
+	/// Inserts a dimension of size 1 at index `axis` of this tensor's shape, e.g. to add a batch dimension. The data
+	/// is not copied, and may reside on any device.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let mut tensor = Tensor::from_array(([2usize, 3], vec![0_i64; 6]))?;
+	/// tensor.unsqueeze(0)?;
+	/// assert_eq!(**tensor.shape(), [1, 2, 3]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if `axis` is greater than the tensor's rank.
+	pub fn unsqueeze(&mut self, axis: usize) -> Result<()> {
+		let shape = self.shape();
+		if axis > shape.len() {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot insert a dimension at axis {axis} of a tensor of rank {}", shape.len())
+			));
+		}
+		let mut shape = shape.to_vec();
+		shape.insert(axis, 1);
+		self.set_shape(shape.into())
+	}
+
+	/// Removes dimensions of size 1 from this tensor's shape: only the dimension at index `axis` if it is `Some`, or
+	/// all of them if it is `None`. The data is not copied, and may reside on any device.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let mut tensor = Tensor::from_array(([1usize, 3, 1], vec![0_i64; 3]))?;
+	/// tensor.squeeze(Some(2))?;
+	/// assert_eq!(**tensor.shape(), [1, 3]);
+	/// tensor.squeeze(None)?;
+	/// assert_eq!(**tensor.shape(), [3]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if `axis` is out of bounds, or if the dimension at `axis` does not have size 1.
+	pub fn squeeze(&mut self, axis: Option<usize>) -> Result<()> {
+		let shape = self.shape();
+		let new_shape: Shape = match axis {
+			Some(axis) => {
+				match shape.get(axis) {
+					Some(1) => {}
+					Some(dim) => {
+						return Err(Error::new_with_code(
+							ErrorCode::InvalidArgument,
+							format!("Cannot squeeze axis {axis} of a tensor of shape {shape}; dimension has size {dim}, not 1")
+						));
+					}
+					None => {
+						return Err(Error::new_with_code(
+							ErrorCode::InvalidArgument,
+							format!("Axis {axis} is out of bounds for a tensor of rank {}", shape.len())
+						));
+					}
+				}
+				shape.iter().enumerate().filter(|&(i, _)| i != axis).map(|(_, &dim)| dim).collect()
+			}
+			None => shape.iter().copied().filter(|&dim| dim != 1).collect()
+		};
+		if new_shape.len() == shape.len() {
+			return Ok(());
+		}
+		self.set_shape(new_shape)
+	}
+
+	/// Sets the elements of a CPU tensor to `value` wherever `mask` is `true`. `mask` is broadcast to the tensor's
+	/// shape following NumPy's rules: aligned from the last dimension, each of its dimensions must either match the
+	/// tensor's or be 1, and it may have fewer dimensions than the tensor.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// // a 2x3 segmentation map, and a mask selecting the last column of each row
+	/// let mut segmentation = Tensor::from_array(([2usize, 3], vec![1_i64, 2, 3, 4, 5, 6]))?;
+	/// let mask = Tensor::from_array(([3usize], vec![false, false, true]))?;
+	/// segmentation.masked_fill(&mask, 0)?;
+	/// assert_eq!(segmentation.extract_tensor().1, &[1, 2, 0, 4, 5, 0]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if either tensor is not CPU-accessible, or if `mask`'s shape cannot be broadcast to the
+	/// tensor's.
+	#[doc(alias = "where")]
+	pub fn masked_fill(&mut self, mask: &Tensor<bool>, value: T) -> Result<()> {
+		let (mask_shape, mask_data) = mask.try_extract_tensor::<bool>()?;
+		let (shape, data) = self.try_extract_tensor_mut::<T>()?;
+		if mask_shape.len() > shape.len() || mask_shape.iter().rev().zip(shape.iter().rev()).any(|(&m, &d)| m != d && m != 1) {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Mask of shape {mask_shape} cannot be broadcast to a tensor of shape {shape}")
+			));
+		}
+
+		// The mask's strides, aligned to the tensor's dimensions; broadcast dimensions have a stride of 0.
+		let rank = shape.len();
+		let leading = rank - mask_shape.len();
+		let mut strides = vec![0; rank];
+		let mut stride = 1;
+		for (axis, &dim) in mask_shape.iter().enumerate().rev() {
+			if dim != 1 {
+				strides[leading + axis] = stride;
+			}
+			stride *= dim as usize;
+		}
+
+		let dims: Vec<usize> = shape.iter().map(|&dim| dim as usize).collect();
+		let mut index = vec![0; rank];
+		let mut mask_index = 0;
+		for x in data.iter_mut() {
+			if mask_data[mask_index] {
+				*x = value.clone();
+			}
+			// advance to the next element, carrying over from the last dimension
+			for axis in (0..rank).rev() {
+				index[axis] += 1;
+				mask_index += strides[axis];
+				if index[axis] < dims[axis] {
+					break;
+				}
+				mask_index -= strides[axis] * dims[axis];
+				index[axis] = 0;
+			}
+		}
+		Ok(())
+	}
+
+	/// Replaces this tensor with a new value viewing the same data with the given shape, which must have the same
+	/// number of elements.
+	fn set_shape(&mut self, shape: Shape) -> Result<()> {
+		let data = self.data_ptr()?.cast_mut();
+		let memory_info = self.memory_info().clone();
+		// The new value doesn't own the data, so keep the old value (which does) alive for as long as the new one.
+		let backing: Box<dyn Any> = Box::new(Arc::clone(&self.inner));
+		let tensor = tensor_from_array(memory_info, shape, data, size_of::<T>(), T::into_tensor_element_type(), Some(backing))?;
+		self.inner = tensor.inner;
+		Ok(())
+	}
+}
+
+impl<T: PrimitiveTensorElementType + PartialOrd + Debug> Tensor<T> {
+	/// Returns the indices of the maximum values along `axis`, as a tensor with `axis` removed from the shape. If the
+	/// maximum value occurs multiple times, the index of the first occurrence is returned.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let logits = Tensor::from_array(([2usize, 3], vec![0.1_f32, 0.7, 0.2, 0.9, 0.05, 0.05]))?;
+	/// let classes = logits.argmax(-1)?;
+	/// let (shape, data) = classes.extract_tensor();
+	/// assert_eq!(**shape, [2]);
+	/// assert_eq!(data, &[1, 0]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the tensor is not CPU-accessible, if `axis` is out of bounds, or if `axis` has length 0.
+	pub fn argmax(&self, axis: isize) -> Result<Tensor<i64>> {
+		self.arg_reduce(axis, |value, best| value > best)
+	}
+
+	/// Returns the indices of the minimum values along `axis`, as a tensor with `axis` removed from the shape. If the
+	/// minimum value occurs multiple times, the index of the first occurrence is returned.
+	///
+	/// See [`Tensor::argmax`] for more details.
+	pub fn argmin(&self, axis: isize) -> Result<Tensor<i64>> {
+		self.arg_reduce(axis, |value, best| value < best)
+	}
+
+	/// Returns the largest element of a CPU tensor.
+	///
+	/// Elements that are unordered with respect to the current maximum (i.e. `NaN`s) are skipped, unless the first
+	/// element is `NaN`.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let t = Tensor::from_array(([2usize, 2], vec![3_i32, -1, 8, 2]))?;
+	/// assert_eq!(t.max()?, 8);
+	/// assert_eq!(t.min()?, -1);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the tensor is not CPU-accessible or is empty.
+	pub fn max(&self) -> Result<T>
+	where
+		T: Clone
+	{
+		self.reduce_by(|value, best| value > best)
+	}
+
+	/// Returns the smallest element of a CPU tensor.
+	///
+	/// See [`Tensor::max`] for more details.
+	pub fn min(&self) -> Result<T>
+	where
+		T: Clone
+	{
+		self.reduce_by(|value, best| value < best)
+	}
+
+	/// Clamps each element of a CPU tensor in place to the range `[min, max]`; e.g. to limit depth predictions to a
+	/// sensor's valid range.
+	///
+	/// Floating point `NaN` elements are left unchanged.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let mut depth = Tensor::from_array(([4usize], vec![-0.5_f32, 0.3, 12.0, f32::NAN]))?;
+	/// depth.clamp(0.0, 10.0)?;
+	/// let (_, data) = depth.extract_tensor();
+	/// assert_eq!(data[..3], [0.0, 0.3, 10.0]);
+	/// assert!(data[3].is_nan());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// Returns an error if the tensor is not CPU-accessible, or if `min` is greater than `max` (or either is `NaN`).
+	#[doc(alias = "clip")]
+	pub fn clamp(&mut self, min: T, max: T) -> Result<()>
+	where
+		T: Clone
+	{
+		if !matches!(min.partial_cmp(&max), Some(Ordering::Less | Ordering::Equal)) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Invalid clamp range: min {min:?} is greater than max {max:?}")));
+		}
+		let (_, data) = self.try_extract_tensor_mut::<T>()?;
+		for x in data {
+			if *x < min {
+				*x = min.clone();
+			} else if *x > max {
+				*x = max.clone();
+			}
+		}
+		Ok(())
+	}
+
+	fn reduce_by(&self, is_better: impl Fn(&T, &T) -> bool) -> Result<T>
+	where
+		T: Clone
+	{
+		let (shape, data) = self.try_extract_tensor::<T>()?;
+		let mut iter = data.iter();
+		let Some(mut best) = iter.next() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot reduce empty tensor with shape {shape}")));
+		};
+		for value in iter {
+			if is_better(value, best) {
+				best = value;
+			}
+		}
+		Ok(best.clone())
+	}
+
+	fn arg_reduce(&self, axis: isize, is_better: impl Fn(&T, &T) -> bool) -> Result<Tensor<i64>> {
+		let (shape, data) = self.try_extract_tensor::<T>()?;
+		let axis = normalize_axis(axis, shape.len())?;
+		let axis_len = shape[axis] as usize;
+		if axis_len == 0 {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot reduce over axis {axis} of tensor with shape {shape}: axis is empty")
+			));
+		}
+
+		let outer = shape[..axis].iter().product::<i64>() as usize;
+		let inner = shape[axis + 1..].iter().product::<i64>() as usize;
+		let mut indices = Vec::with_capacity(outer * inner);
+		for o in 0..outer {
+			let base = o * axis_len * inner;
+			for i in 0..inner {
+				let mut best = 0;
+				for a in 1..axis_len {
+					if is_better(&data[base + a * inner + i], &data[base + best * inner + i]) {
+						best = a;
+					}
+				}
+				indices.push(best as i64);
+			}
+		}
+
+		let out_shape: Shape = shape.iter().enumerate().filter(|(i, _)| *i != axis).map(|(_, d)| *d).collect();
+		Tensor::from_array((out_shape, indices))
+	}
+}
+
+impl<T: PrimitiveTensorElementType + PartialEq + Debug> Tensor<T> {
+	/// Returns `true` if this tensor and `other` have the same shape and all of their elements are equal. Both tensors
+	/// must be CPU-accessible.
+	///
+	/// Note that, like `==`, floating point `NaN`s never compare equal; use [`Tensor::all_close`] to compare floating
+	/// point tensors.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let a = Tensor::from_array(([2usize, 2], vec![1_i64, 2, 3, 4]))?;
+	/// let b = Tensor::from_array(([4usize], vec![1_i64, 2, 3, 4]))?;
+	/// assert!(a.tensor_eq(&a)?);
+	/// assert!(!a.tensor_eq(&b)?);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn tensor_eq(&self, other: &Tensor<T>) -> Result<bool> {
+		let (shape, data) = self.try_extract_tensor::<T>()?;
+		let (other_shape, other_data) = other.try_extract_tensor::<T>()?;
+		Ok(shape == other_shape && data == other_data)
+	}
+}
+
+macro_rules! impl_all_close {
+	($($t:ty),+) => {
+		$(
+			impl Tensor<$t> {
+				/// Returns `true` if this tensor and `other` have the same shape and all of their elements are close,
+				/// following the semantics of NumPy's `allclose`: two elements `a` and `b` are close if
+				/// `|a - b| <= atol + rtol * |b|`. Both tensors must be CPU-accessible.
+				///
+				/// If `equal_nan` is `true`, `NaN`s in the same position in both tensors are considered equal. Infinities
+				/// are only considered close to infinities of the same sign.
+				///
+				/// ```
+				/// # use ort::value::Tensor;
+				/// # fn main() -> ort::Result<()> {
+				/// let a = Tensor::from_array(([3usize], vec![1.0_f32, 2.0, f32::NAN]))?;
+				/// let b = Tensor::from_array(([3usize], vec![1.0_f32, 2.000001, f32::NAN]))?;
+				/// assert!(a.all_close(&b, 1e-5, 1e-8, true)?);
+				/// assert!(!a.all_close(&b, 1e-5, 1e-8, false)?);
+				/// # 	Ok(())
+				/// # }
+				/// ```
+				pub fn all_close(&self, other: &Tensor<$t>, rtol: f64, atol: f64, equal_nan: bool) -> Result<bool> {
+					let (shape, data) = self.try_extract_tensor::<$t>()?;
+					let (other_shape, other_data) = other.try_extract_tensor::<$t>()?;
+					if shape != other_shape {
+						return Ok(false);
+					}
+					Ok(data.iter().zip(other_data).all(|(&a, &b)| {
+						let (a, b) = (a as f64, b as f64);
+						if a.is_nan() || b.is_nan() {
+							equal_nan && a.is_nan() && b.is_nan()
+						} else if a.is_infinite() || b.is_infinite() {
+							a == b
+						} else {
+							(a - b).abs() <= atol + rtol * b.abs()
+						}
+					}))
+				}
+			}
+		)+
+	};
+}
+
+impl_all_close!(f32, f64);
+
+macro_rules! impl_softmax {
+	($($t:ty),+) => {
+		$(
+			impl Tensor<$t> {
+				/// Computes the softmax of a CPU tensor along `axis`, returning a new tensor of the same shape. The maximum
+				/// value along the axis is subtracted before exponentiating to avoid overflow.
+				///
+				/// `axis` may be negative, in which case it counts from the last dimension.
+				///
+				/// ```
+				/// # use ort::value::Tensor;
+				/// # fn main() -> ort::Result<()> {
+				/// let logits = Tensor::from_array(([1usize, 2], vec![1.0_f32, 1.0]))?;
+				/// let probabilities = logits.softmax(-1)?;
+				/// assert_eq!(probabilities.extract_tensor().1, &[0.5, 0.5]);
+				/// # 	Ok(())
+				/// # }
+				/// ```
+				///
+				/// # Errors
+				/// Returns an error if the tensor is not CPU-accessible or if `axis` is out of bounds.
+				#[cfg(feature = "std")]
+				#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+				pub fn softmax(&self, axis: isize) -> Result<Tensor<$t>> {
+					let (shape, data) = self.try_extract_tensor::<$t>()?;
+					let axis = normalize_axis(axis, shape.len())?;
+					let axis_len = shape[axis] as usize;
+					let inner = shape[axis + 1..].iter().product::<i64>() as usize;
+
+					let mut out = data.to_vec();
+					if !out.is_empty() {
+						for outer in out.chunks_mut(axis_len * inner) {
+							for i in 0..inner {
+								let max = (0..axis_len).map(|a| outer[a * inner + i]).fold(<$t>::NEG_INFINITY, <$t>::max);
+								let mut sum = 0.0;
+								for a in 0..axis_len {
+									let value = (outer[a * inner + i] - max).exp();
+									outer[a * inner + i] = value;
+									sum += value;
+								}
+								for a in 0..axis_len {
+									outer[a * inner + i] /= sum;
+								}
+							}
+						}
+					}
+
+					Tensor::from_array((shape.clone(), out))
+				}
+			}
+		)+
+	};
+}
+
+impl_softmax!(f32, f64);
+
+macro_rules! impl_normalize_channels {
+	($($t:ty),+) => {
+		$(
+			impl Tensor<$t> {
+				/// Normalizes a CPU tensor in place by subtracting `mean[c]` from, then dividing by `std[c]`, each element in
+				/// channel `c` along `channel_axis`; e.g. ImageNet normalization of an `NCHW` image tensor with
+				/// `channel_axis = 1`.
+				///
+				/// ```
+				/// # use ort::value::Tensor;
+				/// # fn main() -> ort::Result<()> {
+				/// // 1 image, 2x1 pixels, 3 channels (NHWC)
+				/// let mut image = Tensor::from_array(([1usize, 2, 1, 3], vec![0.485_f32, 0.456, 0.406, 0.714, 0.680, 0.631]))?;
+				/// image.normalize_channels(&[0.485, 0.456, 0.406], &[0.229, 0.224, 0.225], 3)?;
+				/// let (_, data) = image.extract_tensor();
+				/// assert!(data[..3].iter().all(|x| x.abs() < 1e-6));
+				/// assert!(data[3..].iter().all(|x| (x - 1.0).abs() < 1e-3));
+				/// # 	Ok(())
+				/// # }
+				/// ```
+				///
+				/// # Errors
+				/// Returns an error if the tensor is not CPU-accessible, if `channel_axis` is out of bounds, if `mean` and
+				/// `std` do not both have one element per channel, or if any element of `std` is zero.
+				pub fn normalize_channels(&mut self, mean: &[$t], std: &[$t], channel_axis: usize) -> Result<()> {
+					let (shape, data) = self.try_extract_tensor_mut::<$t>()?;
+					if channel_axis >= shape.len() {
+						return Err(Error::new_with_code(
+							ErrorCode::InvalidArgument,
+							format!("Channel axis {channel_axis} is out of bounds for a tensor of rank {}", shape.len())
+						));
+					}
+					let channels = shape[channel_axis] as usize;
+					if mean.len() != channels || std.len() != channels {
+						return Err(Error::new_with_code(
+							ErrorCode::InvalidArgument,
+							format!(
+								"Expected {channels} mean & std values for tensor of shape {shape} (got {} and {})",
+								mean.len(),
+								std.len()
+							)
+						));
+					}
+					if std.contains(&0.0) {
+						return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Standard deviation must not be zero"));
+					}
+
+					let inner = shape[channel_axis + 1..].iter().product::<i64>() as usize;
+					if !data.is_empty() {
+						for outer in data.chunks_mut(channels * inner) {
+							for (c, channel) in outer.chunks_mut(inner).enumerate() {
+								for x in channel {
+									*x = (*x - mean[c]) / std[c];
+								}
+							}
+						}
+					}
+					Ok(())
+				}
+			}
+		)+
+	};
+}
+
+impl_normalize_channels!(f32, f64);
+
+macro_rules! impl_one_hot {
+	($($t:ty),+) => {
+		$(
+			impl Tensor<$t> {
+				/// Creates a one-hot encoding of the class `indices` of a CPU tensor. The result has the shape of `indices`
+				/// with a trailing dimension of size `num_classes`, where the element at each index is `1` and every other
+				/// element is `0`.
+				///
+				/// ```
+				/// # use ort::value::Tensor;
+				/// # fn main() -> ort::Result<()> {
+				/// let labels = Tensor::from_array(([3usize], vec![2_i64, 0, 1]))?;
+				/// let one_hot = Tensor::<f32>::one_hot(&labels, 3)?;
+				/// let (shape, data) = one_hot.extract_tensor();
+				/// assert_eq!(**shape, [3, 3]);
+				/// assert_eq!(data, &[0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+				/// # 	Ok(())
+				/// # }
+				/// ```
+				///
+				/// # Errors
+				/// Returns an error if `indices` is not CPU-accessible, or if any index is not in `0..num_classes`.
+				pub fn one_hot(indices: &Tensor<i64>, num_classes: usize) -> Result<Tensor<$t>> {
+					let (shape, data) = indices.try_extract_tensor::<i64>()?;
+					let mut out = vec![0.0; data.len() * num_classes];
+					for (i, &index) in data.iter().enumerate() {
+						match usize::try_from(index) {
+							Ok(index) if index < num_classes => out[i * num_classes + index] = 1.0,
+							_ => {
+								return Err(Error::new_with_code(
+									ErrorCode::InvalidArgument,
+									format!("Index {index} is out of range for one-hot encoding with {num_classes} classes")
+								));
+							}
+						}
+					}
+
+					let out_shape: Shape = shape.iter().copied().chain([num_classes as i64]).collect();
+					Tensor::from_array((out_shape, out))
+				}
+			}
+		)+
+	};
+}
+
+impl_one_hot!(f32, f64);
+
+macro_rules! impl_resize_bilinear {
+	($($t:ty),+) => {
+		$(
+			impl Tensor<$t> {
+				/// Resizes the spatial dimensions of a CPU image tensor in `NCHW` layout to `new_h`&times;`new_w` using
+				/// bilinear interpolation, independently for each image & channel.
+				///
+				/// Pixels are treated as areas with their values at their centers, matching the default
+				/// (`half_pixel`) coordinate transformation of ONNX's `Resize` operator and PyTorch's
+				/// `interpolate(..., align_corners=False)`. Like those, no antialiasing is applied when downscaling.
+				///
+				/// ```
+				/// # use ort::value::Tensor;
+				/// # fn main() -> ort::Result<()> {
+				/// let image = Tensor::from_array(([1usize, 1, 1, 2], vec![0.0_f32, 1.0]))?;
+				/// let resized = image.resize_bilinear(1, 4)?;
+				/// let (shape, data) = resized.extract_tensor();
+				/// assert_eq!(**shape, [1, 1, 1, 4]);
+				/// assert_eq!(data, &[0.0, 0.25, 0.75, 1.0]);
+				/// # 	Ok(())
+				/// # }
+				/// ```
+				///
+				/// # Errors
+				/// Returns an error if the tensor is not CPU-accessible, if it is not of rank 4, or if either its spatial
+				/// dimensions or the requested size are zero.
+				pub fn resize_bilinear(&self, new_h: usize, new_w: usize) -> Result<Tensor<$t>> {
+					/// For each output coordinate along an axis, the two input coordinates to interpolate between & the
+					/// weight of the second.
+					fn sample_points(input: usize, output: usize) -> Vec<(usize, usize, $t)> {
+						let scale = input as $t / output as $t;
+						(0..output)
+							.map(|i| {
+								let x = ((i as $t + 0.5) * scale - 0.5).clamp(0.0, (input - 1) as $t);
+								// `x` is non-negative, so truncating is equivalent to `floor`
+								let x0 = x as usize;
+								(x0, (x0 + 1).min(input - 1), x - x0 as $t)
+							})
+							.collect()
+					}
+
+					let (shape, data) = self.try_extract_tensor::<$t>()?;
+					if shape.len() != 4 {
+						return Err(Error::new_with_code(
+							ErrorCode::InvalidArgument,
+							format!("Bilinear resizing requires a tensor of rank 4 (NCHW), got shape {shape}")
+						));
+					}
+					let (h, w) = (shape[2] as usize, shape[3] as usize);
+					if h == 0 || w == 0 || new_h == 0 || new_w == 0 {
+						return Err(Error::new_with_code(
+							ErrorCode::InvalidArgument,
+							format!("Cannot resize an image of size {h}x{w} to {new_h}x{new_w}")
+						));
+					}
+
+					let rows = sample_points(h, new_h);
+					let cols = sample_points(w, new_w);
+					let planes = (shape[0] * shape[1]) as usize;
+					let mut out = Vec::with_capacity(planes * new_h * new_w);
+					for plane in data.chunks(h * w).take(planes) {
+						for &(y0, y1, dy) in &rows {
+							let (top, bottom) = (&plane[y0 * w..(y0 + 1) * w], &plane[y1 * w..(y1 + 1) * w]);
+							for &(x0, x1, dx) in &cols {
+								let upper = top[x0] + (top[x1] - top[x0]) * dx;
+								let lower = bottom[x0] + (bottom[x1] - bottom[x0]) * dx;
+								out.push(upper + (lower - upper) * dy);
+							}
+						}
+					}
+
+					Tensor::from_array(([shape[0], shape[1], new_h as i64, new_w as i64], out))
+				}
+			}
+		)+
+	};
+}
+
+impl_resize_bilinear!(f32, f64);
+
+macro_rules! impl_sum_mean {
+	($($t:ty),+) => {
+		$(
+			impl Tensor<$t> {
+				/// Returns the sum of all elements of a CPU tensor. The sum of an empty tensor is `0`.
+				///
+				/// Like [`Iterator::sum`], integer overflow panics when debug assertions are enabled and wraps otherwise.
+				///
+				/// ```
+				/// # use ort::value::Tensor;
+				/// # fn main() -> ort::Result<()> {
+				/// let t = Tensor::from_array(([2usize, 2], vec![1_i64, 2, 3, 4]))?;
+				/// assert_eq!(t.sum()?, 10);
+				/// assert_eq!(t.mean()?, 2.5);
+				/// # 	Ok(())
+				/// # }
+				/// ```
+				///
+				/// # Errors
+				/// Returns an error if the tensor is not CPU-accessible.
+				pub fn sum(&self) -> Result<$t> {
+					let (_, data) = self.try_extract_tensor::<$t>()?;
+					Ok(data.iter().sum())
+				}
+
+				/// Returns the arithmetic mean of all elements of a CPU tensor. Elements are accumulated in `f64` to reduce
+				/// rounding error for large tensors.
+				///
+				/// # Errors
+				/// Returns an error if the tensor is not CPU-accessible or is empty.
+				pub fn mean(&self) -> Result<f64> {
+					let (shape, data) = self.try_extract_tensor::<$t>()?;
+					if data.is_empty() {
+						return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot take the mean of empty tensor with shape {shape}")));
+					}
+					Ok(data.iter().map(|x| *x as f64).sum::<f64>() / data.len() as f64)
+				}
+			}
+		)+
+	};
+}
+
+impl_sum_mean!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64);
+
+#[cfg(test)]
+mod tests {
+	use super::normalize_axis;
+	use crate::{memory::Allocator, value::Tensor};
+
+	#[test]
+	fn test_normalize_axis() {
+		assert_eq!(normalize_axis(0, 3).ok(), Some(0));
+		assert_eq!(normalize_axis(-1, 3).ok(), Some(2));
+		assert!(normalize_axis(3, 3).is_err());
+		assert!(normalize_axis(-4, 3).is_err());
+	}
+
+	#[test]
+	fn test_concat() -> crate::Result<()> {
+		let a = Tensor::from_array(([2usize, 1], vec![1_i32, 2]))?;
+		let b = Tensor::from_array(([2usize, 2], vec![3_i32, 4, 5, 6]))?;
+
+		let c = Tensor::concat(&[&a, &b], -1)?;
+		let (shape, data) = c.extract_tensor();
+		assert_eq!(**shape, [2, 3]);
+		assert_eq!(data, &[1, 3, 4, 2, 5, 6]);
+
+		assert!(Tensor::concat(&[&a, &b], 0).is_err());
+		assert!(Tensor::<i32>::concat(&[], 0).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_pad() -> crate::Result<()> {
+		let t = Tensor::from_array(([1usize, 2], vec![1.0_f32, 2.0]))?;
+
+		let padded = t.pad(&[(1, 1), (2, 0)], -1.0)?;
+		let (shape, data) = padded.extract_tensor();
+		assert_eq!(**shape, [3, 4]);
+		assert_eq!(data, &[-1.0, -1.0, -1.0, -1.0, -1.0, -1.0, 1.0, 2.0, -1.0, -1.0, -1.0, -1.0]);
+
+		assert!(t.pad(&[(1, 1)], 0.0).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_permute() -> crate::Result<()> {
+		let t = Tensor::from_array(([2usize, 3], vec![1_u8, 2, 3, 4, 5, 6]))?;
+
+		let transposed = t.permute(&[1, 0])?;
+		let (shape, data) = transposed.extract_tensor();
+		assert_eq!(**shape, [3, 2]);
+		assert_eq!(data, &[1, 4, 2, 5, 3, 6]);
+
+		assert!(t.permute(&[0, 0]).is_err());
+		assert!(t.permute(&[0]).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn test_softmax() -> crate::Result<()> {
+		let t = Tensor::from_array(([2usize, 2], vec![0.0_f64, 0.0, 1000.0, 1000.0]))?;
+
+		let softmax = t.softmax(0)?;
+		assert_eq!(softmax.extract_tensor().1, &[0.0, 0.0, 1.0, 1.0]);
+
+		let softmax = t.softmax(1)?;
+		assert_eq!(softmax.extract_tensor().1, &[0.5, 0.5, 0.5, 0.5]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_normalize_channels() -> crate::Result<()> {
+		// NCHW, 2 channels of 2 pixels each
+		let mut t = Tensor::from_array(([1usize, 2, 1, 2], vec![1.0_f32, 3.0, 10.0, 20.0]))?;
+		t.normalize_channels(&[1.0, 10.0], &[2.0, 5.0], 1)?;
+		assert_eq!(t.extract_tensor().1, &[0.0, 1.0, 0.0, 2.0]);
+
+		assert!(t.normalize_channels(&[0.0], &[1.0], 1).is_err());
+		assert!(t.normalize_channels(&[0.0, 0.0], &[1.0, 0.0], 1).is_err());
+		assert!(t.normalize_channels(&[0.0, 0.0], &[1.0, 1.0], 4).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_one_hot() -> crate::Result<()> {
+		let indices = Tensor::from_array(([2usize, 2], vec![0_i64, 1, 1, 0]))?;
+		let one_hot = Tensor::<f64>::one_hot(&indices, 2)?;
+		let (shape, data) = one_hot.extract_tensor();
+		assert_eq!(**shape, [2, 2, 2]);
+		assert_eq!(data, &[1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0]);
+
+		let indices = Tensor::from_array(([2usize], vec![1_i64, -1]))?;
+		assert!(Tensor::<f32>::one_hot(&indices, 2).is_err());
+		assert!(Tensor::<f32>::one_hot(&indices, 0).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_resize_bilinear() -> crate::Result<()> {
+		// 2 channels of 2x2 pixels
+		let image = Tensor::from_array(([1usize, 2, 2, 2], vec![0.0_f32, 1.0, 2.0, 3.0, 5.0, 5.0, 5.0, 5.0]))?;
+		let resized = image.resize_bilinear(4, 4)?;
+		let (shape, data) = resized.extract_tensor();
+		assert_eq!(**shape, [1, 2, 4, 4]);
+		assert_eq!(&data[..4], &[0.0, 0.25, 0.75, 1.0]);
+		assert_eq!(&data[12..16], &[2.0, 2.25, 2.75, 3.0]);
+		assert!(data[16..].iter().all(|&x| x == 5.0));
+
+		let same = image.resize_bilinear(2, 2)?;
+		assert_eq!(same.extract_tensor().1, image.extract_tensor().1);
+
+		let downscaled = image.resize_bilinear(1, 1)?;
+		assert_eq!(downscaled.extract_tensor().1, &[1.5, 5.0]);
+
+		assert!(image.resize_bilinear(0, 2).is_err());
+		assert!(Tensor::from_array(([2usize, 2], vec![0.0_f32; 4]))?.resize_bilinear(4, 4).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_masked_fill() -> crate::Result<()> {
+		let mut t = Tensor::from_array(([2usize, 3], vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]))?;
+		let mask = Tensor::from_array(([2usize, 3], vec![true, false, false, false, false, true]))?;
+		t.masked_fill(&mask, 0.0)?;
+		assert_eq!(t.extract_tensor().1, &[0.0, 2.0, 3.0, 4.0, 5.0, 0.0]);
+
+		// broadcast along the last axis
+		let mask = Tensor::from_array(([2usize, 1], vec![false, true]))?;
+		t.masked_fill(&mask, -1.0)?;
+		assert_eq!(t.extract_tensor().1, &[0.0, 2.0, 3.0, -1.0, -1.0, -1.0]);
+
+		// scalar mask
+		let mask = Tensor::from_array(([0usize; 0], vec![true]))?;
+		t.masked_fill(&mask, 9.0)?;
+		assert!(t.extract_tensor().1.iter().all(|&x| x == 9.0));
+
+		assert!(t.masked_fill(&Tensor::from_array(([2usize], vec![true; 2]))?, 0.0).is_err());
+		assert!(t.masked_fill(&Tensor::from_array(([1usize, 2, 3], vec![true; 6]))?, 0.0).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_clamp() -> crate::Result<()> {
+		let mut t = Tensor::from_array(([5usize], vec![-3_i32, 0, 2, 5, 9]))?;
+		t.clamp(0, 5)?;
+		assert_eq!(t.extract_tensor().1, &[0, 0, 2, 5, 5]);
+		assert!(t.clamp(5, 0).is_err());
+
+		let mut t = Tensor::from_array(([2usize], vec![f64::NAN, 2.0]))?;
+		assert!(t.clamp(f64::NAN, 1.0).is_err());
+		t.clamp(0.0, 1.0)?;
+		assert!(t.extract_tensor().1[0].is_nan());
+		assert_eq!(t.extract_tensor().1[1], 1.0);
+		Ok(())
+	}
+
+	#[test]
+	fn test_argmax_argmin() -> crate::Result<()> {
+		let t = Tensor::from_array(([2usize, 3], vec![1_i32, 5, 5, 7, 0, 7]))?;
+
+		let argmax = t.argmax(1)?;
+		let (shape, data) = argmax.extract_tensor();
+		assert_eq!(**shape, [2]);
+		assert_eq!(data, &[1, 0]);
+
+		let argmin = t.argmin(0)?;
+		let (shape, data) = argmin.extract_tensor();
+		assert_eq!(**shape, [3]);
+		assert_eq!(data, &[0, 1, 0]);
+
+		assert!(t.argmax(2).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_reductions() -> crate::Result<()> {
+		let t = Tensor::from_array(([2usize, 2], vec![1.5_f32, -2.0, f32::NAN, 4.0]))?;
+		assert_eq!(t.max()?, 4.0);
+		assert_eq!(t.min()?, -2.0);
+
+		let t = Tensor::from_array(([3usize], vec![250_u8, 3, 7]))?;
+		assert_eq!(t.mean()?, 260.0 / 3.0);
+		assert_eq!(t.max()?, 250);
+
+		// `Tensor::from_array` rejects zero-sized dimensions, so allocate the empty tensor instead.
+		let empty = Tensor::<i32>::new(&Allocator::default(), [0usize])?;
+		assert_eq!(empty.extract_tensor().1.len(), 0);
+		assert_eq!(empty.sum()?, 0);
+		assert!(empty.mean().is_err());
+		assert!(empty.max().is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_eq_all_close() -> crate::Result<()> {
+		let a = Tensor::from_array(([2usize], vec![1_u16, 2]))?;
+		let b = Tensor::from_array(([2usize], vec![1_u16, 3]))?;
+		assert!(a.tensor_eq(&a)?);
+		assert!(!a.tensor_eq(&b)?);
+
+		let a = Tensor::from_array(([3usize], vec![100.0_f64, f64::INFINITY, f64::NAN]))?;
+		let b = Tensor::from_array(([3usize], vec![100.1_f64, f64::INFINITY, f64::NAN]))?;
+		assert!(a.all_close(&b, 1e-3, 0.0, true)?);
+		assert!(!a.all_close(&b, 1e-4, 0.0, true)?);
+		assert!(!a.all_close(&b, 1e-3, 0.0, false)?);
+
+		let c = Tensor::from_array(([1usize, 3], vec![100.0_f64, f64::INFINITY, f64::NAN]))?;
+		assert!(!a.all_close(&c, 1e-3, 0.0, true)?);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_cast() -> crate::Result<()> {
+		let t = Tensor::from_array(([2usize, 2], vec![-1_i32, 256, 3, 70000]))?;
+
+		let narrowed = t.cast::<u8>()?;
+		let (shape, data) = narrowed.extract_tensor();
+		assert_eq!(**shape, [2, 2]);
+		assert_eq!(data, &[255, 0, 3, 112]);
+
+		assert_eq!(t.cast::<f64>()?.extract_tensor().1, &[-1.0, 256.0, 3.0, 70000.0]);
+
+		let t = Tensor::from_array(([3usize], vec![2.9_f64, -2.9, 1e20]))?;
+		assert_eq!(t.cast::<i16>()?.extract_tensor().1, &[2, -2, i16::MAX]);
+
+		Ok(())
+	}
+}
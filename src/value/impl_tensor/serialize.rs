@@ -0,0 +1,466 @@
+//! A simple, self-describing binary format for tensors, see [`Tensor::to_bytes`], and `serde` support for tensors.
+
+use alloc::{format, string::String, vec::Vec};
+use core::slice;
+
+use super::{DynTensor, Tensor, TensorValueTypeMarker};
+use crate::{
+	error::{Error, ErrorCode, Result},
+	memory::Allocator,
+	tensor::{Shape, TensorElementType, check_bool_data},
+	value::{Value, ValueType}
+};
+
+const MAGIC: &[u8; 4] = b"ORTT";
+const VERSION: u8 = 1;
+
+const ELEMENT_TYPES: [TensorElementType; 22] = [
+	TensorElementType::Float32,
+	TensorElementType::Uint8,
+	TensorElementType::Int8,
+	TensorElementType::Uint16,
+	TensorElementType::Int16,
+	TensorElementType::Int32,
+	TensorElementType::Int64,
+	TensorElementType::String,
+	TensorElementType::Bool,
+	TensorElementType::Float16,
+	TensorElementType::Float64,
+	TensorElementType::Uint32,
+	TensorElementType::Uint64,
+	TensorElementType::Complex64,
+	TensorElementType::Complex128,
+	TensorElementType::Bfloat16,
+	TensorElementType::Float8E4M3FN,
+	TensorElementType::Float8E4M3FNUZ,
+	TensorElementType::Float8E5M2,
+	TensorElementType::Float8E5M2FNUZ,
+	TensorElementType::Uint4,
+	TensorElementType::Int4
+];
+
+fn element_type_tag(ty: TensorElementType) -> u8 {
+	ort_sys::ONNXTensorElementDataType::from(ty) as u8
+}
+
+/// The size of the scalar units in this type which need to be byte-swapped on big-endian platforms.
+#[cfg(target_endian = "big")]
+fn swap_width(ty: TensorElementType) -> usize {
+	match ty {
+		TensorElementType::Int16 | TensorElementType::Uint16 | TensorElementType::Float16 | TensorElementType::Bfloat16 => 2,
+		TensorElementType::Int32 | TensorElementType::Uint32 | TensorElementType::Float32 | TensorElementType::Complex64 => 4,
+		TensorElementType::Int64 | TensorElementType::Uint64 | TensorElementType::Float64 | TensorElementType::Complex128 => 8,
+		_ => 1
+	}
+}
+
+/// Converts between native & little-endian byte order in place; a no-op on little-endian platforms.
+fn to_from_le(ty: TensorElementType, data: &mut [u8]) {
+	#[cfg(target_endian = "big")]
+	for unit in data.chunks_exact_mut(swap_width(ty)) {
+		unit.reverse();
+	}
+	#[cfg(target_endian = "little")]
+	let _ = (ty, data);
+}
+
+impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
+	/// Serializes this tensor's type, shape, and data into a portable, self-describing byte format, which can be read
+	/// back with [`DynTensor::from_bytes`].
+	///
+	/// The format is as follows, with all integers in little-endian byte order:
+	///
+	/// | Field        | Size                   | Description                                                          |
+	/// |:------------ |:---------------------- |:-------------------------------------------------------------------- |
+	/// | Magic        | 4 bytes                | The ASCII bytes `ORTT`.                                              |
+	/// | Version      | `u8`                   | Currently `1`.                                                       |
+	/// | Element type | `u8`                   | The ONNX `TensorProto.DataType` value of the tensor's element type.  |
+	/// | Rank         | `u32`                  | The number of dimensions.                                            |
+	/// | Dimensions   | `i64` &times; rank     | The size of each dimension.                                          |
+	/// | Data         | remainder              | The tensor's elements, in row-major order.                           |
+	///
+	/// Elements of numeric types are stored in little-endian byte order with no padding; complex numbers are stored as
+	/// their real part followed by their imaginary part, and 4-bit integers are packed two per byte, as in ONNX
+	/// Runtime. Each element of a string tensor is stored as its length in bytes as a `u64`, followed by its UTF-8
+	/// contents.
+	///
+	/// Returns an error if the tensor's data is not CPU-accessible.
+	///
+	/// ```
+	/// # use ort::value::{DynTensor, Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2usize, 2], vec![1.0_f32, 2.0, 3.0, 4.0]))?;
+	/// let bytes = tensor.to_bytes()?;
+	///
+	/// let tensor = DynTensor::from_bytes(&bytes)?;
+	/// assert_eq!(tensor.try_extract_tensor::<f32>()?.1, &[1.0, 2.0, 3.0, 4.0]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn to_bytes(&self) -> Result<Vec<u8>> {
+		let (ty, shape) = self.type_and_shape();
+		self.check_cpu_accessible()?;
+
+		let mut out = Vec::with_capacity(MAGIC.len() + 6 + shape.len() * 8);
+		out.extend_from_slice(MAGIC);
+		out.push(VERSION);
+		out.push(element_type_tag(ty));
+		out.extend_from_slice(&(shape.len() as u32).to_le_bytes());
+		for dim in shape.iter() {
+			out.extend_from_slice(&dim.to_le_bytes());
+		}
+
+		if ty == TensorElementType::String {
+			for string in self.try_extract_strings()?.1 {
+				out.extend_from_slice(&(string.len() as u64).to_le_bytes());
+				out.extend_from_slice(string.as_bytes());
+			}
+		} else {
+			self.write_le_data(ty, shape, &mut out)?;
+		}
+		Ok(out)
+	}
+
+	fn type_and_shape(&self) -> (TensorElementType, &Shape) {
+		match self.dtype() {
+			ValueType::Tensor { ty, shape, .. } => (*ty, shape),
+			_ => unreachable!()
+		}
+	}
+
+	fn check_cpu_accessible(&self) -> Result<()> {
+		if !self.memory_info().is_cpu_accessible() {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				"Cannot serialize a tensor whose data is not CPU-accessible; copy it to the CPU first"
+			));
+		}
+		Ok(())
+	}
+
+	/// Appends the elements of this (non-string, CPU-accessible) tensor to `out` in little-endian byte order.
+	fn write_le_data(&self, ty: TensorElementType, shape: &Shape, out: &mut Vec<u8>) -> Result<()> {
+		let len = le_data_len(ty, shape.num_elements())?;
+		if len > 0 {
+			let start = out.len();
+			// SAFETY: the data is CPU-accessible, and a tensor of this shape & type holds `len` bytes.
+			out.extend_from_slice(unsafe { slice::from_raw_parts(self.data_ptr()?.cast::<u8>(), len) });
+			to_from_le(ty, &mut out[start..]);
+		}
+		Ok(())
+	}
+}
+
+impl DynTensor {
+	/// Deserializes a tensor in the format written by [`Tensor::to_bytes`]. The tensor is allocated in CPU memory.
+	///
+	/// Returns an error with [`ErrorCode::InvalidArgument`] if `bytes` is truncated, has trailing data, or is otherwise
+	/// malformed, including if a `bool` tensor contains bytes other than `0` or `1`.
+	pub fn from_bytes(bytes: &[u8]) -> Result<DynTensor> {
+		let mut reader = Reader(bytes);
+		if reader.take(MAGIC.len())? != MAGIC {
+			return Err(malformed("missing magic bytes"));
+		}
+		let version = reader.take(1)?[0];
+		if version != VERSION {
+			return Err(malformed(&format!("unsupported version {version}")));
+		}
+		let tag = reader.take(1)?[0];
+		let ty = ELEMENT_TYPES
+			.into_iter()
+			.find(|ty| element_type_tag(*ty) == tag)
+			.ok_or_else(|| malformed(&format!("unknown element type {tag}")))?;
+
+		let rank = reader.u32()?;
+		let shape = (0..rank)
+			.map(|_| {
+				let dim = i64::from_le_bytes(reader.array()?);
+				if dim < 0 {
+					return Err(malformed(&format!("negative dimension {dim}")));
+				}
+				Ok(dim)
+			})
+			.collect::<Result<Shape>>()?;
+		let num_elements = checked_num_elements(&shape)?;
+
+		let tensor = if ty == TensorElementType::String {
+			let strings = (0..num_elements)
+				.map(|_| {
+					let len = usize::try_from(u64::from_le_bytes(reader.array()?)).map_err(|_| malformed("string too long"))?;
+					String::from_utf8(reader.take(len)?.to_vec()).map_err(|_| malformed("string is not valid UTF-8"))
+				})
+				.collect::<Result<Vec<_>>>()?;
+			DynTensor::from_strings(shape, &strings)?
+		} else {
+			let data = reader.take(le_data_len(ty, num_elements)?)?;
+			DynTensor::from_le_data(ty, shape, data)?
+		};
+		if !reader.0.is_empty() {
+			return Err(malformed("trailing data"));
+		}
+		Ok(tensor)
+	}
+
+	/// Creates a CPU string tensor of the given shape, which must have been validated by [`checked_num_elements`].
+	fn from_strings(shape: Shape, strings: &[String]) -> Result<DynTensor> {
+		if strings.is_empty() {
+			DynTensor::new(&Allocator::default(), TensorElementType::String, shape)
+		} else {
+			Ok(Tensor::from_string_array((shape, strings))?.upcast())
+		}
+	}
+
+	/// Creates a CPU tensor from elements in little-endian byte order. `data` must be exactly [`le_data_len`] bytes
+	/// long, and `shape` must have been validated by [`checked_num_elements`].
+	///
+	/// Returns an error if `ty` is [`TensorElementType::Bool`] and `data` contains bytes other than `0` or `1`, which
+	/// would be invalid `bool`s.
+	fn from_le_data(ty: TensorElementType, shape: Shape, data: &[u8]) -> Result<DynTensor> {
+		if ty == TensorElementType::Bool {
+			check_bool_data(data)?;
+		}
+		let mut tensor = DynTensor::new(&Allocator::default(), ty, shape)?;
+		if !data.is_empty() {
+			// SAFETY: the tensor was allocated on the CPU with room for `data.len()` bytes.
+			let buffer = unsafe { slice::from_raw_parts_mut(tensor.data_ptr_mut()?.cast::<u8>(), data.len()) };
+			buffer.copy_from_slice(data);
+			to_from_le(ty, buffer);
+		}
+		Ok(tensor)
+	}
+}
+
+/// Returns the number of elements in a tensor of the given (untrusted) shape.
+fn checked_num_elements(shape: &Shape) -> Result<usize> {
+	if let Some(dim) = shape.iter().find(|dim| **dim < 0) {
+		return Err(malformed(&format!("negative dimension {dim}")));
+	}
+	shape.checked_num_elements().ok_or_else(|| malformed("number of elements overflows"))
+}
+
+/// Returns the size in bytes of the data of a non-string tensor with `num_elements` elements.
+fn le_data_len(ty: TensorElementType, num_elements: usize) -> Result<usize> {
+	let len = match ty {
+		TensorElementType::Int4 | TensorElementType::Uint4 => Some(num_elements.div_ceil(2)),
+		TensorElementType::Float8E4M3FN | TensorElementType::Float8E4M3FNUZ | TensorElementType::Float8E5M2 | TensorElementType::Float8E5M2FNUZ => {
+			Some(num_elements)
+		}
+		_ => num_elements.checked_mul(ty.byte_size(1))
+	};
+	len.ok_or_else(|| malformed("tensor too large"))
+}
+
+struct Reader<'b>(&'b [u8]);
+
+impl<'b> Reader<'b> {
+	fn take(&mut self, len: usize) -> Result<&'b [u8]> {
+		if self.0.len() < len {
+			return Err(malformed("unexpected end of data"));
+		}
+		let (data, rest) = self.0.split_at(len);
+		self.0 = rest;
+		Ok(data)
+	}
+
+	fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+		Ok(self.take(N)?.try_into().expect("took exactly N bytes"))
+	}
+
+	fn u32(&mut self) -> Result<u32> {
+		self.array().map(u32::from_le_bytes)
+	}
+}
+
+fn malformed(reason: &str) -> Error {
+	Error::new_with_code(ErrorCode::InvalidArgument, format!("Malformed serialized tensor: {reason}"))
+}
+
+/// `serde` support for tensors.
+///
+/// A tensor is serialized as a struct with 3 fields: `dtype`, its [`TensorElementType`]; `shape`, a sequence of
+/// `i64`s; and `data`, an enum which is either `bytes`, holding the tensor's elements in the same encoding as
+/// [`Tensor::to_bytes`], or `strings`, holding the elements of a string tensor.
+#[cfg(feature = "serde")]
+mod serde_support {
+	use alloc::{string::String, vec::Vec};
+	use core::fmt;
+
+	use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser};
+
+	use super::{DynTensor, TensorValueTypeMarker, checked_num_elements, le_data_len};
+	use crate::{
+		tensor::{Shape, TensorElementType},
+		value::Value
+	};
+
+	#[derive(Serialize, Deserialize)]
+	#[serde(rename = "Tensor")]
+	struct SerdeTensor {
+		dtype: TensorElementType,
+		shape: Shape,
+		data: SerdeTensorData
+	}
+
+	#[derive(Serialize, Deserialize)]
+	#[serde(rename_all = "lowercase")]
+	enum SerdeTensorData {
+		Bytes(Bytes),
+		Strings(Vec<String>)
+	}
+
+	/// A byte buffer which uses `serialize_bytes` instead of serializing each byte as an element of a sequence.
+	struct Bytes(Vec<u8>);
+
+	impl Serialize for Bytes {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			serializer.serialize_bytes(&self.0)
+		}
+	}
+
+	impl<'de> Deserialize<'de> for Bytes {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			struct BytesVisitor;
+
+			impl<'de> de::Visitor<'de> for BytesVisitor {
+				type Value = Bytes;
+
+				fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+					f.write_str("a byte array")
+				}
+
+				fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+					Ok(Bytes(v.to_vec()))
+				}
+
+				fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+					Ok(Bytes(v))
+				}
+
+				fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+					let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+					while let Some(byte) = seq.next_element()? {
+						bytes.push(byte);
+					}
+					Ok(Bytes(bytes))
+				}
+			}
+
+			deserializer.deserialize_bytes(BytesVisitor)
+		}
+	}
+
+	impl<Type: TensorValueTypeMarker + ?Sized> Serialize for Value<Type> {
+		/// Serializes this tensor's type, shape, and data. Returns an error if the tensor's data is not
+		/// CPU-accessible.
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			let (ty, shape) = self.type_and_shape();
+			self.check_cpu_accessible().map_err(ser::Error::custom)?;
+			let data = if ty == TensorElementType::String {
+				SerdeTensorData::Strings(self.try_extract_strings().map_err(ser::Error::custom)?.1)
+			} else {
+				let mut bytes = Vec::new();
+				self.write_le_data(ty, shape, &mut bytes).map_err(ser::Error::custom)?;
+				SerdeTensorData::Bytes(Bytes(bytes))
+			};
+			SerdeTensor {
+				dtype: ty,
+				shape: shape.clone(),
+				data
+			}
+			.serialize(serializer)
+		}
+	}
+
+	impl<'de> Deserialize<'de> for DynTensor {
+		/// Deserializes a tensor serialized by [`Tensor`](crate::value::Tensor)'s `Serialize` implementation. The
+		/// tensor is allocated in CPU memory.
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			let SerdeTensor { dtype, shape, data } = SerdeTensor::deserialize(deserializer)?;
+			let num_elements = checked_num_elements(&shape).map_err(de::Error::custom)?;
+			match data {
+				SerdeTensorData::Strings(strings) => {
+					if dtype != TensorElementType::String {
+						return Err(de::Error::custom(format_args!("expected `bytes` data for a tensor of type {dtype}")));
+					}
+					if strings.len() != num_elements {
+						return Err(de::Error::invalid_length(strings.len(), &"the number of elements in the tensor's shape"));
+					}
+					DynTensor::from_strings(shape, &strings)
+				}
+				SerdeTensorData::Bytes(Bytes(bytes)) => {
+					if dtype == TensorElementType::String {
+						return Err(de::Error::custom("expected `strings` data for a string tensor"));
+					}
+					if bytes.len() != le_data_len(dtype, num_elements).map_err(de::Error::custom)? {
+						return Err(de::Error::invalid_length(bytes.len(), &"the size of the tensor's data"));
+					}
+					DynTensor::from_le_data(dtype, shape, &bytes)
+				}
+			}
+			.map_err(de::Error::custom)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{DynTensor, MAGIC};
+	use crate::{error::ErrorCode, memory::Allocator, tensor::TensorElementType, value::Tensor};
+
+	#[test]
+	fn test_tensor_bytes_roundtrip() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2usize, 3], vec![1_i64, -2, 3, -4, 5, i64::MAX]))?;
+		let bytes = tensor.to_bytes()?;
+		assert_eq!(&bytes[..4], MAGIC);
+		assert_eq!(bytes.len(), 4 + 1 + 1 + 4 + 2 * 8 + 6 * 8);
+		let deserialized = DynTensor::from_bytes(&bytes)?;
+		assert_eq!(deserialized.try_extract_tensor::<i64>()?, tensor.extract_tensor());
+
+		let tensor = Tensor::from_string_array(([3usize], &["hello", "", "wörld"][..]))?;
+		let deserialized = DynTensor::from_bytes(&tensor.to_bytes()?)?;
+		assert_eq!(deserialized.try_extract_strings()?.1, ["hello", "", "wörld"]);
+
+		let tensor = DynTensor::new(&Allocator::default(), TensorElementType::Float32, [0_usize, 4])?;
+		let deserialized = DynTensor::from_bytes(&tensor.to_bytes()?)?;
+		assert_eq!(**deserialized.shape(), [0, 4]);
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_bytes_malformed() {
+		let err = |bytes: &[u8]| DynTensor::from_bytes(bytes).expect_err("should be malformed").code();
+		assert_eq!(err(b""), ErrorCode::InvalidArgument);
+		assert_eq!(err(b"ORTX\x01\x01\0\0\0\0"), ErrorCode::InvalidArgument);
+		assert_eq!(err(b"ORTT\x02\x01\0\0\0\0"), ErrorCode::InvalidArgument);
+		assert_eq!(err(b"ORTT\x01\x00\0\0\0\0"), ErrorCode::InvalidArgument);
+		// rank 1, but no dimension
+		assert_eq!(err(b"ORTT\x01\x01\x01\0\0\0"), ErrorCode::InvalidArgument);
+		// 2 floats expected, only 1 given
+		assert_eq!(err(b"ORTT\x01\x01\x01\0\0\0\x02\0\0\0\0\0\0\0\0\0\x80\x3f"), ErrorCode::InvalidArgument);
+		// 2 bools, the second of which is not 0 or 1
+		assert_eq!(err(b"ORTT\x01\x09\x01\0\0\0\x02\0\0\0\0\0\0\0\x01\x02"), ErrorCode::InvalidArgument);
+		assert!(DynTensor::from_bytes(b"ORTT\x01\x09\x01\0\0\0\x02\0\0\0\0\0\0\0\x01\x00").is_ok());
+	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn test_tensor_serde() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2usize], vec![1_i16, -2]))?;
+		let json = serde_json::to_string(&tensor).expect("tensor should serialize");
+		assert_eq!(json, r#"{"dtype":"Int16","shape":[2],"data":{"bytes":[1,0,254,255]}}"#);
+		let deserialized: DynTensor = serde_json::from_str(&json).expect("tensor should deserialize");
+		assert_eq!(deserialized.try_extract_tensor::<i16>()?, tensor.extract_tensor());
+
+		let tensor = Tensor::from_string_array(([2usize], &["a", "b"][..]))?;
+		let json = serde_json::to_string(&tensor).expect("tensor should serialize");
+		assert_eq!(json, r#"{"dtype":"String","shape":[2],"data":{"strings":["a","b"]}}"#);
+		let deserialized: DynTensor = serde_json::from_str(&json).expect("tensor should deserialize");
+		assert_eq!(deserialized.try_extract_strings()?.1, ["a", "b"]);
+
+		assert!(serde_json::from_str::<DynTensor>(r#"{"dtype":"Int16","shape":[2],"data":{"bytes":[1,0]}}"#).is_err());
+		assert!(serde_json::from_str::<DynTensor>(r#"{"dtype":"Int16","shape":[1],"data":{"strings":["a"]}}"#).is_err());
+		assert!(serde_json::from_str::<DynTensor>(r#"{"dtype":"Bool","shape":[2],"data":{"bytes":[1,0]}}"#).is_ok());
+		assert!(serde_json::from_str::<DynTensor>(r#"{"dtype":"Bool","shape":[2],"data":{"bytes":[1,2]}}"#).is_err());
+		Ok(())
+	}
+}
@@ -37,8 +37,8 @@ pub use self::{
 		DynSequence, DynSequenceRef, DynSequenceRefMut, DynSequenceValueType, Sequence, SequenceRef, SequenceRefMut, SequenceValueType, SequenceValueTypeMarker
 	},
 	impl_tensor::{
-		DynTensor, DynTensorRef, DynTensorRefMut, DynTensorValueType, OwnedTensorArrayData, Tensor, TensorArrayData, TensorArrayDataMut, TensorArrayDataParts,
-		TensorRef, TensorRefMut, TensorValueType, TensorValueTypeMarker, ToShape
+		DeviceTensorBuilder, DynTensor, DynTensorRef, DynTensorRefMut, DynTensorValueType, IndexedIter, OwnedTensorArrayData, Tensor, TensorArrayData,
+		TensorArrayDataMut, TensorArrayDataParts, TensorIndex, TensorRef, TensorRefMut, TensorValueType, TensorValueTypeMarker, ToShape
 	},
 	r#type::ValueType
 };
@@ -45,6 +45,7 @@ use crate::{
 /// # }
 /// ```
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ValueType {
 	/// Value is a tensor/multi-dimensional array.
 	Tensor {
@@ -316,4 +317,17 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn test_value_type_serde() {
+		let ty = ValueType::Sequence(Box::new(ValueType::Tensor {
+			ty: TensorElementType::Float32,
+			shape: Shape::new([-1, 3]),
+			dimension_symbols: SymbolicDimensions::new(["batch".to_string(), String::default()])
+		}));
+		let json = serde_json::to_string(&ty).expect("type should serialize");
+		assert_eq!(json, r#"{"Sequence":{"Tensor":{"ty":"Float32","shape":[-1,3],"dimension_symbols":["batch",""]}}}"#);
+		assert_eq!(serde_json::from_str::<ValueType>(&json).expect("type should deserialize"), ty);
+	}
 }